@@ -0,0 +1,8 @@
+CREATE TABLE character_item
+(
+    character_id INTEGER NOT NULL,
+    item_name    TEXT    NOT NULL COLLATE NOCASE,
+    amount       INTEGER NOT NULL DEFAULT 1,
+    PRIMARY KEY (character_id, item_name),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
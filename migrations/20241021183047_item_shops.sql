@@ -0,0 +1,20 @@
+CREATE TABLE shop
+(
+    id       INTEGER NOT NULL PRIMARY KEY,
+    name     TEXT    NOT NULL COLLATE NOCASE,
+    guild_id INTEGER NOT NULL,
+    wallet_id INTEGER,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (wallet_id) REFERENCES wallet (id),
+    UNIQUE (name, guild_id)
+);
+
+CREATE TABLE shop_item
+(
+    shop_id   INTEGER NOT NULL,
+    item_name TEXT    NOT NULL COLLATE NOCASE,
+    price     INTEGER,
+    stock     INTEGER,
+    PRIMARY KEY (shop_id, item_name),
+    FOREIGN KEY (shop_id) REFERENCES shop (id)
+);
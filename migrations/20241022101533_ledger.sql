@@ -0,0 +1,21 @@
+CREATE TABLE ledger
+(
+    id             INTEGER NOT NULL PRIMARY KEY,
+    guild_id       INTEGER NOT NULL,
+    interaction_id INTEGER NOT NULL,
+    actor_id       INTEGER NOT NULL,
+    character_id   INTEGER,
+    wallet_id      INTEGER,
+    user_id        INTEGER,
+    stat           TEXT    NOT NULL,
+    amount         INTEGER NOT NULL,
+    action_type    TEXT    NOT NULL,
+    timestamp      INTEGER NOT NULL,
+    reverts_id     INTEGER UNIQUE,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (character_id) REFERENCES character (id),
+    FOREIGN KEY (wallet_id) REFERENCES wallet (id),
+    FOREIGN KEY (reverts_id) REFERENCES ledger (id)
+);
+
+CREATE INDEX ledger_interaction_id ON ledger (interaction_id);
@@ -0,0 +1,18 @@
+CREATE TABLE action_log
+(
+    id           INTEGER NOT NULL PRIMARY KEY,
+    guild_id     INTEGER NOT NULL,
+    actor_id     INTEGER NOT NULL,
+    character_id INTEGER,
+    wallet_id    INTEGER,
+    action_type  TEXT    NOT NULL,
+    amount       INTEGER,
+    message      TEXT    NOT NULL,
+    channel_id   INTEGER NOT NULL,
+    timestamp    INTEGER NOT NULL,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (character_id) REFERENCES character (id),
+    FOREIGN KEY (wallet_id) REFERENCES wallet (id)
+);
+
+CREATE INDEX action_log_guild_id_timestamp ON action_log (guild_id, timestamp);
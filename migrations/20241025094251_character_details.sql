@@ -0,0 +1,4 @@
+ALTER TABLE character ADD COLUMN nature TEXT COLLATE NOCASE;
+ALTER TABLE character ADD COLUMN held_item TEXT COLLATE NOCASE;
+ALTER TABLE character ADD COLUMN appearance TEXT;
+ALTER TABLE character ADD COLUMN backstory TEXT;
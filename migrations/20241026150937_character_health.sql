@@ -0,0 +1,10 @@
+ALTER TABLE character ADD COLUMN current_hp INTEGER;
+ALTER TABLE character ADD COLUMN current_will INTEGER;
+
+CREATE TABLE character_status_effect
+(
+    character_id       INTEGER NOT NULL,
+    status_effect_name TEXT    NOT NULL COLLATE NOCASE,
+    PRIMARY KEY (character_id, status_effect_name),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE character_ownership_change
+(
+    character_id     INTEGER NOT NULL,
+    previous_user_id INTEGER NOT NULL,
+    new_user_id      INTEGER NOT NULL,
+    timestamp        INTEGER NOT NULL,
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
+
+ALTER TABLE guild ADD COLUMN adoption_requires_gm_approval BOOLEAN NOT NULL DEFAULT false;
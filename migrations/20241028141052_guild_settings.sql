@@ -0,0 +1,18 @@
+CREATE TABLE guild_settings
+(
+    guild_id                        INTEGER NOT NULL PRIMARY KEY,
+    experience_per_level            INTEGER NOT NULL DEFAULT 100,
+    silver_rank_level               INTEGER NOT NULL DEFAULT 2,
+    gold_rank_level                 INTEGER NOT NULL DEFAULT 4,
+    platinum_rank_level             INTEGER NOT NULL DEFAULT 8,
+    diamond_rank_level              INTEGER NOT NULL DEFAULT 16,
+    master_rank_level               INTEGER NOT NULL DEFAULT 20,
+    base_combat_points              INTEGER NOT NULL DEFAULT 4,
+    combat_points_per_level         INTEGER NOT NULL DEFAULT 1,
+    base_social_points              INTEGER NOT NULL DEFAULT 4,
+    social_points_per_rank          INTEGER NOT NULL DEFAULT 2,
+    backpack_upgrade_base_price     INTEGER NOT NULL DEFAULT 500,
+    backpack_upgrade_price_increase INTEGER NOT NULL DEFAULT 500,
+    move_limit_bonus                INTEGER NOT NULL DEFAULT 2,
+    FOREIGN KEY (guild_id) REFERENCES guild (id)
+);
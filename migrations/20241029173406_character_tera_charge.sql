@@ -0,0 +1,85 @@
+CREATE TABLE character_tera_charge
+(
+    character_id INTEGER NOT NULL,
+    tera_type    TEXT    NOT NULL,
+    unlocked     INTEGER NOT NULL DEFAULT 0,
+    used         INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (character_id, tera_type),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
+
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Normal', tera_unlocked_normal, tera_used_normal FROM character WHERE tera_unlocked_normal > 0 OR tera_used_normal > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Fighting', tera_unlocked_fighting, tera_used_fighting FROM character WHERE tera_unlocked_fighting > 0 OR tera_used_fighting > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Flying', tera_unlocked_flying, tera_used_flying FROM character WHERE tera_unlocked_flying > 0 OR tera_used_flying > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Poison', tera_unlocked_poison, tera_used_poison FROM character WHERE tera_unlocked_poison > 0 OR tera_used_poison > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Ground', tera_unlocked_ground, tera_used_ground FROM character WHERE tera_unlocked_ground > 0 OR tera_used_ground > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Rock', tera_unlocked_rock, tera_used_rock FROM character WHERE tera_unlocked_rock > 0 OR tera_used_rock > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Bug', tera_unlocked_bug, tera_used_bug FROM character WHERE tera_unlocked_bug > 0 OR tera_used_bug > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Ghost', tera_unlocked_ghost, tera_used_ghost FROM character WHERE tera_unlocked_ghost > 0 OR tera_used_ghost > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Steel', tera_unlocked_steel, tera_used_steel FROM character WHERE tera_unlocked_steel > 0 OR tera_used_steel > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Fire', tera_unlocked_fire, tera_used_fire FROM character WHERE tera_unlocked_fire > 0 OR tera_used_fire > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Water', tera_unlocked_water, tera_used_water FROM character WHERE tera_unlocked_water > 0 OR tera_used_water > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Grass', tera_unlocked_grass, tera_used_grass FROM character WHERE tera_unlocked_grass > 0 OR tera_used_grass > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Electric', tera_unlocked_electric, tera_used_electric FROM character WHERE tera_unlocked_electric > 0 OR tera_used_electric > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Psychic', tera_unlocked_psychic, tera_used_psychic FROM character WHERE tera_unlocked_psychic > 0 OR tera_used_psychic > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Ice', tera_unlocked_ice, tera_used_ice FROM character WHERE tera_unlocked_ice > 0 OR tera_used_ice > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Dragon', tera_unlocked_dragon, tera_used_dragon FROM character WHERE tera_unlocked_dragon > 0 OR tera_used_dragon > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Dark', tera_unlocked_dark, tera_used_dark FROM character WHERE tera_unlocked_dark > 0 OR tera_used_dark > 0;
+INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used)
+SELECT id, 'Fairy', tera_unlocked_fairy, tera_used_fairy FROM character WHERE tera_unlocked_fairy > 0 OR tera_used_fairy > 0;
+
+ALTER TABLE character DROP COLUMN tera_unlocked_normal;
+ALTER TABLE character DROP COLUMN tera_unlocked_fighting;
+ALTER TABLE character DROP COLUMN tera_unlocked_flying;
+ALTER TABLE character DROP COLUMN tera_unlocked_poison;
+ALTER TABLE character DROP COLUMN tera_unlocked_ground;
+ALTER TABLE character DROP COLUMN tera_unlocked_rock;
+ALTER TABLE character DROP COLUMN tera_unlocked_bug;
+ALTER TABLE character DROP COLUMN tera_unlocked_ghost;
+ALTER TABLE character DROP COLUMN tera_unlocked_steel;
+ALTER TABLE character DROP COLUMN tera_unlocked_fire;
+ALTER TABLE character DROP COLUMN tera_unlocked_water;
+ALTER TABLE character DROP COLUMN tera_unlocked_grass;
+ALTER TABLE character DROP COLUMN tera_unlocked_electric;
+ALTER TABLE character DROP COLUMN tera_unlocked_psychic;
+ALTER TABLE character DROP COLUMN tera_unlocked_ice;
+ALTER TABLE character DROP COLUMN tera_unlocked_dragon;
+ALTER TABLE character DROP COLUMN tera_unlocked_dark;
+ALTER TABLE character DROP COLUMN tera_unlocked_fairy;
+ALTER TABLE character DROP COLUMN tera_used_normal;
+ALTER TABLE character DROP COLUMN tera_used_fighting;
+ALTER TABLE character DROP COLUMN tera_used_flying;
+ALTER TABLE character DROP COLUMN tera_used_poison;
+ALTER TABLE character DROP COLUMN tera_used_ground;
+ALTER TABLE character DROP COLUMN tera_used_rock;
+ALTER TABLE character DROP COLUMN tera_used_bug;
+ALTER TABLE character DROP COLUMN tera_used_ghost;
+ALTER TABLE character DROP COLUMN tera_used_steel;
+ALTER TABLE character DROP COLUMN tera_used_fire;
+ALTER TABLE character DROP COLUMN tera_used_water;
+ALTER TABLE character DROP COLUMN tera_used_grass;
+ALTER TABLE character DROP COLUMN tera_used_electric;
+ALTER TABLE character DROP COLUMN tera_used_psychic;
+ALTER TABLE character DROP COLUMN tera_used_ice;
+ALTER TABLE character DROP COLUMN tera_used_dragon;
+ALTER TABLE character DROP COLUMN tera_used_dark;
+ALTER TABLE character DROP COLUMN tera_used_fairy;
+
+ALTER TABLE guild_settings ADD COLUMN tera_charge_reset INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,23 @@
+CREATE TABLE team
+(
+    id                     INTEGER NOT NULL PRIMARY KEY,
+    name                   TEXT    NOT NULL COLLATE NOCASE,
+    guild_id               INTEGER NOT NULL,
+    leader_id              INTEGER NOT NULL,
+    wallet_id              INTEGER,
+    bot_message_channel_id INTEGER NOT NULL,
+    bot_message_id         INTEGER NOT NULL,
+    creation_timestamp     INTEGER NOT NULL,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (leader_id) REFERENCES character (id),
+    FOREIGN KEY (wallet_id) REFERENCES wallet (id),
+    UNIQUE (name, guild_id)
+);
+
+CREATE TABLE team_member
+(
+    team_id      INTEGER NOT NULL,
+    character_id INTEGER NOT NULL PRIMARY KEY,
+    FOREIGN KEY (team_id) REFERENCES team (id),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE character_achievement
+(
+    character_id INTEGER NOT NULL,
+    achievement  TEXT    NOT NULL COLLATE NOCASE,
+    timestamp    INTEGER NOT NULL,
+    PRIMARY KEY (character_id, achievement),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
@@ -0,0 +1,23 @@
+CREATE TABLE character_submission
+(
+    id                 INTEGER NOT NULL PRIMARY KEY,
+    guild_id           INTEGER NOT NULL,
+    user_id            INTEGER NOT NULL,
+    name               TEXT    NOT NULL COLLATE NOCASE,
+    species_api_id     INTEGER NOT NULL,
+    phenotype          INTEGER NOT NULL,
+    is_shiny           BOOLEAN NOT NULL,
+    backstory          TEXT,
+    status             INTEGER NOT NULL DEFAULT 1,
+    channel_id         INTEGER NOT NULL,
+    review_message_id  INTEGER,
+    creation_timestamp INTEGER NOT NULL,
+    reviewer_id        INTEGER,
+    feedback           TEXT,
+    character_id       INTEGER,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (user_id) REFERENCES user (id),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
+
+ALTER TABLE guild ADD COLUMN character_review_channel_id INTEGER;
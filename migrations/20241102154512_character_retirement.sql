@@ -0,0 +1,14 @@
+CREATE TABLE character_retirement
+(
+    id                     INTEGER NOT NULL PRIMARY KEY,
+    character_id           INTEGER NOT NULL,
+    reason                 TEXT,
+    epilogue               TEXT,
+    level                  INTEGER NOT NULL,
+    experience             INTEGER NOT NULL,
+    completed_quests       INTEGER NOT NULL,
+    achievements           TEXT    NOT NULL,
+    retirement_timestamp   INTEGER NOT NULL,
+    unretirement_timestamp INTEGER,
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE character_form
+(
+    character_id   INTEGER NOT NULL,
+    species_api_id INTEGER NOT NULL,
+    PRIMARY KEY (character_id, species_api_id),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
+
+ALTER TABLE character ADD COLUMN active_form_api_id INTEGER;
@@ -0,0 +1,24 @@
+CREATE TABLE companion
+(
+    id                 INTEGER NOT NULL PRIMARY KEY,
+    character_id       INTEGER NOT NULL,
+    name               TEXT    NOT NULL COLLATE NOCASE,
+    species_api_id     INTEGER NOT NULL,
+    emoji              TEXT,
+    phenotype          INTEGER NOT NULL,
+    ability            TEXT    NOT NULL,
+    experience         INTEGER NOT NULL,
+    stat_strength      INTEGER NOT NULL,
+    stat_dexterity     INTEGER NOT NULL,
+    stat_vitality      INTEGER NOT NULL,
+    stat_special       INTEGER NOT NULL,
+    stat_insight       INTEGER NOT NULL,
+    stat_tough         INTEGER NOT NULL,
+    stat_cool          INTEGER NOT NULL,
+    stat_beauty        INTEGER NOT NULL,
+    stat_clever        INTEGER NOT NULL,
+    stat_cute          INTEGER NOT NULL,
+    creation_timestamp INTEGER NOT NULL,
+    UNIQUE (character_id, name),
+    FOREIGN KEY (character_id) REFERENCES character (id)
+);
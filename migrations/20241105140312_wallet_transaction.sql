@@ -0,0 +1,15 @@
+CREATE TABLE wallet_transaction
+(
+    id                       INTEGER NOT NULL PRIMARY KEY,
+    wallet_id                INTEGER NOT NULL,
+    amount                   INTEGER NOT NULL,
+    counterpart_character_id INTEGER,
+    memo                     TEXT,
+    timestamp                INTEGER NOT NULL,
+    FOREIGN KEY (wallet_id) REFERENCES wallet (id),
+    FOREIGN KEY (counterpart_character_id) REFERENCES character (id)
+);
+
+CREATE INDEX wallet_transaction_wallet_id ON wallet_transaction (wallet_id, timestamp);
+
+ALTER TABLE wallet ADD COLUMN post_monthly_statement BOOLEAN NOT NULL DEFAULT false;
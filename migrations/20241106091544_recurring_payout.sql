@@ -0,0 +1,17 @@
+CREATE TABLE recurring_payout
+(
+    id                  INTEGER NOT NULL PRIMARY KEY,
+    guild_id            INTEGER NOT NULL,
+    source_wallet_id    INTEGER NOT NULL,
+    target_character_id INTEGER,
+    target_wallet_id    INTEGER,
+    amount              INTEGER NOT NULL,
+    schedule            INTEGER NOT NULL,
+    memo                TEXT,
+    creator_id          INTEGER NOT NULL,
+    creation_timestamp  INTEGER NOT NULL,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (source_wallet_id) REFERENCES wallet (id),
+    FOREIGN KEY (target_character_id) REFERENCES character (id),
+    FOREIGN KEY (target_wallet_id) REFERENCES wallet (id)
+);
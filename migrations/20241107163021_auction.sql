@@ -0,0 +1,24 @@
+CREATE TABLE auction
+(
+    id                          INTEGER NOT NULL PRIMARY KEY,
+    guild_id                    INTEGER NOT NULL,
+    seller_wallet_id            INTEGER NOT NULL,
+    name                        TEXT    NOT NULL,
+    description                 TEXT,
+    item_name                   TEXT,
+    starting_price              INTEGER NOT NULL,
+    minimum_increment           INTEGER NOT NULL,
+    end_timestamp               INTEGER NOT NULL,
+    channel_id                  INTEGER NOT NULL,
+    message_id                  INTEGER NOT NULL,
+    highest_bidder_character_id INTEGER,
+    highest_bid                 INTEGER,
+    is_settled                  BOOLEAN NOT NULL DEFAULT false,
+    creator_id                  INTEGER NOT NULL,
+    creation_timestamp          INTEGER NOT NULL,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (seller_wallet_id) REFERENCES wallet (id),
+    FOREIGN KEY (highest_bidder_character_id) REFERENCES character (id)
+);
+
+CREATE INDEX auction_end_timestamp ON auction (is_settled, end_timestamp);
@@ -0,0 +1,54 @@
+ALTER TABLE guild_settings ADD COLUMN shop_tax_percentage INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE guild_settings ADD COLUMN backpack_upgrade_fee_percentage INTEGER NOT NULL DEFAULT 0;
+
+-- Payouts without a source wallet are funded by the guild treasury.
+CREATE TABLE recurring_payout_new
+(
+    id                  INTEGER NOT NULL PRIMARY KEY,
+    guild_id            INTEGER NOT NULL,
+    source_wallet_id    INTEGER,
+    target_character_id INTEGER,
+    target_wallet_id    INTEGER,
+    amount              INTEGER NOT NULL,
+    schedule            INTEGER NOT NULL,
+    memo                TEXT,
+    creator_id          INTEGER NOT NULL,
+    creation_timestamp  INTEGER NOT NULL,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (source_wallet_id) REFERENCES wallet (id),
+    FOREIGN KEY (target_character_id) REFERENCES character (id),
+    FOREIGN KEY (target_wallet_id) REFERENCES wallet (id)
+);
+
+INSERT INTO recurring_payout_new (id, guild_id, source_wallet_id, target_character_id, target_wallet_id, amount, schedule, memo, creator_id, creation_timestamp)
+SELECT id, guild_id, source_wallet_id, target_character_id, target_wallet_id, amount, schedule, memo, creator_id, creation_timestamp
+FROM recurring_payout;
+
+DROP TABLE recurring_payout;
+ALTER TABLE recurring_payout_new RENAME TO recurring_payout;
+
+-- Transactions without a wallet belong to the guild treasury.
+CREATE TABLE wallet_transaction_new
+(
+    id                       INTEGER NOT NULL PRIMARY KEY,
+    guild_id                 INTEGER NOT NULL,
+    wallet_id                INTEGER,
+    amount                   INTEGER NOT NULL,
+    counterpart_character_id INTEGER,
+    memo                     TEXT,
+    timestamp                INTEGER NOT NULL,
+    FOREIGN KEY (guild_id) REFERENCES guild (id),
+    FOREIGN KEY (wallet_id) REFERENCES wallet (id),
+    FOREIGN KEY (counterpart_character_id) REFERENCES character (id)
+);
+
+INSERT INTO wallet_transaction_new (id, guild_id, wallet_id, amount, counterpart_character_id, memo, timestamp)
+SELECT wallet_transaction.id, wallet.guild_id, wallet_transaction.wallet_id, wallet_transaction.amount, wallet_transaction.counterpart_character_id, wallet_transaction.memo, wallet_transaction.timestamp
+FROM wallet_transaction
+    INNER JOIN wallet ON wallet.id = wallet_transaction.wallet_id;
+
+DROP TABLE wallet_transaction;
+ALTER TABLE wallet_transaction_new RENAME TO wallet_transaction;
+
+CREATE INDEX wallet_transaction_wallet_id ON wallet_transaction (wallet_id, timestamp);
+CREATE INDEX wallet_transaction_guild_id ON wallet_transaction (guild_id, timestamp);
@@ -0,0 +1,175 @@
+use chrono::Utc;
+use sqlx::{Pool, Sqlite};
+
+use crate::game_data::achievement::{Achievement, AchievementCounter};
+use crate::Error;
+
+struct AchievementCounters {
+    completed_quests: i64,
+    total_spar_count: i64,
+    total_new_player_tour_count: i64,
+    total_new_player_combat_tutorial_count: i64,
+    battle_points: i64,
+}
+
+impl AchievementCounters {
+    fn get(&self, counter: AchievementCounter) -> i64 {
+        match counter {
+            AchievementCounter::CompletedQuests => self.completed_quests,
+            AchievementCounter::RpSessions => self.total_spar_count,
+            AchievementCounter::GivenTours => self.total_new_player_tour_count,
+            AchievementCounter::GivenCombatTutorials => self.total_new_player_combat_tutorial_count,
+            AchievementCounter::BattlePoints => self.battle_points,
+        }
+    }
+}
+
+async fn load_counters(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+) -> Result<AchievementCounters, Error> {
+    let counters = sqlx::query_as!(
+        AchievementCounters,
+        "SELECT \
+            (SELECT COUNT(*) FROM quest_completion WHERE character_id = character.id) as \"completed_quests!: i64\", \
+            total_spar_count, total_new_player_tour_count, total_new_player_combat_tutorial_count, battle_points \
+         FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_one(database)
+    .await?;
+
+    Ok(counters)
+}
+
+/// Awards all achievements whose requirements are met by now and returns the ones which are new.
+pub async fn award_new_achievements<'a>(
+    database: &Pool<Sqlite>,
+    achievements: &'a [Achievement],
+    character_id: i64,
+) -> Result<Vec<&'a Achievement>, Error> {
+    if achievements.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let counters = load_counters(database, character_id).await?;
+    let timestamp = Utc::now().timestamp();
+    let mut result = Vec::new();
+    for achievement in achievements {
+        if counters.get(achievement.counter) < achievement.threshold {
+            continue;
+        }
+
+        let insert = sqlx::query!(
+            "INSERT OR IGNORE INTO character_achievement (character_id, achievement, timestamp) VALUES (?, ?, ?)",
+            character_id,
+            achievement.name,
+            timestamp
+        )
+        .execute(database)
+        .await?;
+
+        if insert.rows_affected() > 0 {
+            result.push(achievement);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Returns the badges of all earned achievements, in the order they were earned.
+/// Achievements which were removed from the data files are still listed by name.
+pub async fn get_earned_badges(
+    database: &Pool<Sqlite>,
+    achievements: &[Achievement],
+    character_id: i64,
+) -> Vec<String> {
+    match sqlx::query!(
+        "SELECT achievement FROM character_achievement WHERE character_id = ? ORDER BY timestamp, achievement",
+        character_id
+    )
+    .fetch_all(database)
+    .await
+    {
+        Ok(records) => records
+            .into_iter()
+            .map(|x| {
+                match achievements
+                    .iter()
+                    .find(|a| a.name.eq_ignore_ascii_case(&x.achievement))
+                {
+                    Some(achievement) => achievement.badge(),
+                    None => x.achievement,
+                }
+            })
+            .collect(),
+        Err(e) => {
+            log::error!(
+                "Was unable to load achievements for character {}: {}",
+                character_id,
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_data::achievement::{Achievement, AchievementCounter};
+    use crate::{achievements, database_helpers, Error};
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn achievements_should_only_be_awarded_once(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        let character_id = 300;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        database_helpers::create_mock::character_in_database(
+            &db,
+            guild_id,
+            user_id,
+            character_id,
+            "Test",
+            0,
+        )
+        .await;
+        sqlx::query!(
+            "UPDATE character SET total_new_player_tour_count = 5 WHERE id = ?",
+            character_id
+        )
+        .execute(&db)
+        .await?;
+
+        let definitions = vec![
+            Achievement {
+                name: String::from("Tour Guide"),
+                description: String::from("Give 5 tours."),
+                emoji: Some(String::from("🎫")),
+                counter: AchievementCounter::GivenTours,
+                threshold: 5,
+            },
+            Achievement {
+                name: String::from("Veteran"),
+                description: String::from("Complete 10 quests."),
+                emoji: None,
+                counter: AchievementCounter::CompletedQuests,
+                threshold: 10,
+            },
+        ];
+
+        let awarded = achievements::award_new_achievements(&db, &definitions, character_id).await?;
+        assert_eq!(1, awarded.len());
+        assert_eq!("Tour Guide", awarded[0].name);
+
+        let awarded = achievements::award_new_achievements(&db, &definitions, character_id).await?;
+        assert!(awarded.is_empty());
+
+        let badges = achievements::get_earned_badges(&db, &definitions, character_id).await;
+        assert_eq!(vec![String::from("🎫 Tour Guide")], badges);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,320 @@
+use serenity::all::{ButtonStyle, CreateActionRow};
+use sqlx::{Pool, Sqlite};
+
+use crate::errors::ValidationError;
+use crate::wallet_statement::WalletTransaction;
+use crate::{emoji, helpers, wallet_statement, Error};
+
+/// Each auction message offers a button to bid the minimum bid plus this many additional increments.
+const ADDITIONAL_INCREMENTS_PER_BUTTON: [i64; 3] = [0, 4, 9];
+
+/// Something which gets sold to the highest bidder. Bids are held in escrow until the auction ends.
+pub struct Auction {
+    pub id: i64,
+    pub guild_id: i64,
+    pub seller_wallet_id: i64,
+    pub seller_wallet_name: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub item_name: Option<String>,
+    pub starting_price: i64,
+    pub minimum_increment: i64,
+    pub end_timestamp: i64,
+    pub channel_id: i64,
+    pub message_id: i64,
+    pub highest_bidder_id: Option<i64>,
+    pub highest_bidder_name: Option<String>,
+    pub highest_bid: Option<i64>,
+    pub is_settled: bool,
+}
+
+impl Auction {
+    pub fn minimum_bid(&self) -> i64 {
+        match self.highest_bid {
+            Some(highest_bid) => highest_bid + self.minimum_increment,
+            None => self.starting_price,
+        }
+    }
+
+    pub fn build_message(&self) -> String {
+        let mut result = format!("## 🔨 Auction #{}: {}\n", self.id, self.name);
+        if let Some(description) = &self.description {
+            result.push_str(&format!("{}\n", description));
+        }
+        if let Some(item_name) = &self.item_name {
+            result.push_str(&format!("**Item:** {}\n", item_name));
+        }
+        result.push_str(&format!(
+            "**Sold by:** {}\n**Starting price:** {} {} | **Minimum increment:** {} {}\n",
+            self.seller_wallet_name,
+            self.starting_price,
+            emoji::POKE_COIN,
+            self.minimum_increment,
+            emoji::POKE_COIN
+        ));
+
+        match (&self.highest_bidder_name, self.highest_bid, self.is_settled) {
+            (Some(bidder), Some(bid), true) => result.push_str(&format!(
+                "**Sold to {} for {} {}!** Ended <t:{}:R>.",
+                bidder,
+                bid,
+                emoji::POKE_COIN,
+                self.end_timestamp
+            )),
+            (Some(bidder), Some(bid), false) => result.push_str(&format!(
+                "**Highest bid:** {} {} by {}\n**Ends** <t:{}:R>.",
+                bid,
+                emoji::POKE_COIN,
+                bidder,
+                self.end_timestamp
+            )),
+            (_, _, true) => result.push_str(&format!(
+                "**Ended without any bids** <t:{}:R>.",
+                self.end_timestamp
+            )),
+            (_, _, false) => result.push_str(&format!(
+                "**No bids yet.**\n**Ends** <t:{}:R>.",
+                self.end_timestamp
+            )),
+        }
+
+        result
+    }
+
+    pub fn create_bid_buttons(&self) -> Vec<CreateActionRow> {
+        if self.is_settled {
+            return Vec::new();
+        }
+
+        let minimum_bid = self.minimum_bid();
+        vec![CreateActionRow::Buttons(
+            ADDITIONAL_INCREMENTS_PER_BUTTON
+                .iter()
+                .map(|x| minimum_bid + x * self.minimum_increment)
+                .map(|amount| {
+                    helpers::create_styled_button(
+                        &format!("Bid {}", amount),
+                        &format!("auction-bid_{}_{}", self.id, amount),
+                        false,
+                        ButtonStyle::Success,
+                    )
+                })
+                .collect(),
+        )]
+    }
+}
+
+pub async fn load(database: &Pool<Sqlite>, auction_id: i64) -> Result<Option<Auction>, Error> {
+    let auction = sqlx::query_as!(
+        Auction,
+        "SELECT auction.id, auction.guild_id, auction.seller_wallet_id, wallet.name as seller_wallet_name, \
+            auction.name, auction.description, auction.item_name, auction.starting_price, auction.minimum_increment, \
+            auction.end_timestamp, auction.channel_id, auction.message_id, \
+            auction.highest_bidder_character_id as highest_bidder_id, character.name as \"highest_bidder_name?: String\", \
+            auction.highest_bid, auction.is_settled \
+         FROM auction \
+            JOIN wallet ON wallet.id = auction.seller_wallet_id \
+            LEFT JOIN character ON character.id = auction.highest_bidder_character_id \
+         WHERE auction.id = ?",
+        auction_id
+    )
+    .fetch_optional(database)
+    .await?;
+
+    Ok(auction)
+}
+
+/// Returns the IDs of all auctions which have ended, but haven't been settled yet.
+pub async fn get_due_auction_ids(database: &Pool<Sqlite>, now: i64) -> Result<Vec<i64>, Error> {
+    let records = sqlx::query!(
+        "SELECT id FROM auction WHERE is_settled = false AND end_timestamp <= ?",
+        now
+    )
+    .fetch_all(database)
+    .await?;
+
+    Ok(records.into_iter().map(|x| x.id).collect())
+}
+
+/// Takes the bid from the bidder and refunds whoever was outbid.
+/// Fails if anyone else has placed a bid since the auction was loaded.
+pub async fn place_bid(
+    database: &Pool<Sqlite>,
+    auction: &Auction,
+    character_id: i64,
+    character_name: &str,
+    amount: i64,
+    now: i64,
+) -> Result<(), Error> {
+    if auction.is_settled || now >= auction.end_timestamp {
+        return Err(Box::new(ValidationError::new(
+            "This auction has already ended.",
+        )));
+    }
+    if amount < auction.minimum_bid() {
+        return Err(Box::new(ValidationError::new(format!(
+            "The minimum bid for this auction is {} {}.",
+            auction.minimum_bid(),
+            emoji::POKE_COIN
+        ))));
+    }
+
+    let mut transaction = database.begin().await?;
+    let result = sqlx::query!(
+        "UPDATE auction SET highest_bid = ?, highest_bidder_character_id = ? \
+         WHERE id = ? AND is_settled = false AND end_timestamp > ? AND highest_bid IS ?",
+        amount,
+        character_id,
+        auction.id,
+        now,
+        auction.highest_bid
+    )
+    .execute(&mut *transaction)
+    .await?;
+    if result.rows_affected() != 1 {
+        return Err(Box::new(ValidationError::new(
+            "Someone else has placed a bid in the meantime. Please try again!",
+        )));
+    }
+
+    if let (Some(previous_bidder_id), Some(previous_bid)) =
+        (auction.highest_bidder_id, auction.highest_bid)
+    {
+        sqlx::query!(
+            "UPDATE character SET money = money + ? WHERE id = ?",
+            previous_bid,
+            previous_bidder_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    let result = sqlx::query!(
+        "UPDATE character SET money = money - ? WHERE id = ? AND money >= ?",
+        amount,
+        character_id,
+        amount
+    )
+    .execute(&mut *transaction)
+    .await?;
+    if result.rows_affected() != 1 {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} can't afford to bid {} {}.",
+            character_name,
+            amount,
+            emoji::POKE_COIN
+        ))));
+    }
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+/// Pays the highest bid into the seller's wallet and marks the auction as settled.
+/// Returns false if the auction has already been settled.
+pub async fn settle(database: &Pool<Sqlite>, auction: &Auction) -> Result<bool, Error> {
+    let mut transaction = database.begin().await?;
+    let result = sqlx::query!(
+        "UPDATE auction SET is_settled = true WHERE id = ? AND is_settled = false",
+        auction.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    if result.rows_affected() != 1 {
+        return Ok(false);
+    }
+
+    if let (Some(bidder_id), Some(bid)) = (auction.highest_bidder_id, auction.highest_bid) {
+        sqlx::query!(
+            "UPDATE wallet SET money = money + ? WHERE id = ?",
+            bid,
+            auction.seller_wallet_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+        wallet_statement::record(
+            &mut transaction,
+            WalletTransaction {
+                wallet_id: auction.seller_wallet_id,
+                amount: bid,
+                counterpart_character_id: Some(bidder_id),
+                memo: Some(&format!("Auction #{}: {}", auction.id, auction.name)),
+            },
+        )
+        .await?;
+    }
+
+    transaction.commit().await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Pool, Sqlite};
+
+    use crate::{auctions, database_helpers, Error};
+
+    async fn get_money(db: &Pool<Sqlite>, character_id: i64) -> i64 {
+        sqlx::query!("SELECT money FROM character WHERE id = ?", character_id)
+            .fetch_one(db)
+            .await
+            .unwrap()
+            .money
+    }
+
+    #[sqlx::test]
+    async fn outbid_characters_should_be_refunded(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        database_helpers::create_mock::character_in_database(
+            &db, guild_id, user_id, 1, "Alice", 500,
+        )
+        .await;
+        database_helpers::create_mock::character_in_database(&db, guild_id, user_id, 2, "Bob", 500)
+            .await;
+        database_helpers::create_mock::wallet_with_money(&db, guild_id, 1, "Dragapult", 0).await;
+        sqlx::query!(
+            "INSERT INTO auction (id, guild_id, seller_wallet_id, name, starting_price, minimum_increment, end_timestamp, channel_id, message_id, creator_id, creation_timestamp) \
+             VALUES (1, ?, 1, 'Hoarded Goods', 100, 10, 1000, 0, 0, ?, 0)",
+            guild_id,
+            user_id
+        )
+        .execute(&db)
+        .await?;
+
+        let auction = auctions::load(&db, 1).await?.expect("Auction should exist");
+        assert!(auctions::place_bid(&db, &auction, 1, "Alice", 90, 10)
+            .await
+            .is_err());
+        auctions::place_bid(&db, &auction, 1, "Alice", 100, 10).await?;
+        // The auction wasn't reloaded, so this bid is based on stale data.
+        assert!(auctions::place_bid(&db, &auction, 2, "Bob", 110, 10)
+            .await
+            .is_err());
+
+        let auction = auctions::load(&db, 1).await?.expect("Auction should exist");
+        assert!(auctions::place_bid(&db, &auction, 2, "Bob", 600, 10)
+            .await
+            .is_err());
+        auctions::place_bid(&db, &auction, 2, "Bob", 110, 10).await?;
+        assert_eq!(500, get_money(&db, 1).await);
+        assert_eq!(390, get_money(&db, 2).await);
+
+        let auction = auctions::load(&db, 1).await?.expect("Auction should exist");
+        assert!(auctions::place_bid(&db, &auction, 1, "Alice", 200, 1000)
+            .await
+            .is_err());
+        assert_eq!(vec![1], auctions::get_due_auction_ids(&db, 1000).await?);
+        assert!(auctions::settle(&db, &auction).await?);
+        assert!(!auctions::settle(&db, &auction).await?);
+
+        let wallet = sqlx::query!("SELECT money FROM wallet WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(110, wallet.money);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,375 @@
+use std::io::Cursor;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+
+use crate::character_stats::{CharacterStat, GenericCharacterStats, SingleCharacterStatType};
+use crate::enums::{MysteryDungeonRank, PokemonType, PokemonTypeWithoutShadow};
+use crate::tera_charges::TeraCharge;
+use crate::Error;
+
+const WIDTH: u32 = 600;
+const HEIGHT: u32 = 330;
+const PADDING: i64 = 20;
+const SPRITE_SIZE: u32 = 160;
+const STAT_COLUMN_X: i64 = 200;
+const SOCIAL_COLUMN_X: i64 = 420;
+const STAT_ROW_HEIGHT: i64 = 22;
+const DOT_RADIUS: i64 = 6;
+const DOT_SPACING: i64 = 15;
+
+/// Every glyph is 5 pixels wide and 7 pixels high, with one pixel of spacing between characters.
+const GLYPH_WIDTH: i64 = 5;
+const GLYPH_HEIGHT: i64 = 7;
+const GLYPH_ADVANCE: i64 = GLYPH_WIDTH + 1;
+
+const BACKGROUND: Rgba<u8> = Rgba([43, 45, 49, 255]);
+const PANEL: Rgba<u8> = Rgba([30, 31, 34, 255]);
+const TEXT: Rgba<u8> = Rgba([242, 243, 245, 255]);
+const TEXT_MUTED: Rgba<u8> = Rgba([148, 155, 164, 255]);
+const DOT_FILLED: Rgba<u8> = Rgba([242, 243, 245, 255]);
+const DOT_EMPTY: Rgba<u8> = Rgba([88, 91, 99, 255]);
+const DOT_LIMIT_BREAK: Rgba<u8> = Rgba([250, 196, 55, 255]);
+const EXPERIENCE_BAR: Rgba<u8> = Rgba([88, 101, 242, 255]);
+
+pub struct CharacterCard<'a> {
+    pub name: &'a str,
+    /// Cards are rendered without a sprite if no local sprite file could be found.
+    pub sprite: Option<DynamicImage>,
+    pub rank: MysteryDungeonRank,
+    pub level: i64,
+    pub experience: i64,
+    pub experience_per_level: i64,
+    pub type1: PokemonType,
+    pub type2: Option<PokemonType>,
+    pub combat_stats: &'a GenericCharacterStats,
+    pub social_stats: &'a GenericCharacterStats,
+    pub tera_charges: Vec<TeraCharge>,
+}
+
+/// Renders the card as PNG.
+pub fn render(card: &CharacterCard) -> Result<Vec<u8>, Error> {
+    let mut image = RgbaImage::from_pixel(WIDTH, HEIGHT, BACKGROUND);
+    fill_rect(&mut image, 0, 0, WIDTH as i64, 8, type_color(&card.type1));
+    fill_rect(
+        &mut image,
+        PADDING,
+        PADDING + 20,
+        SPRITE_SIZE as i64,
+        SPRITE_SIZE as i64,
+        PANEL,
+    );
+
+    if let Some(sprite) = &card.sprite {
+        let sprite = sprite.resize(SPRITE_SIZE, SPRITE_SIZE, FilterType::Nearest);
+        let x = PADDING + (SPRITE_SIZE - sprite.width()) as i64 / 2;
+        let y = PADDING + 20 + (SPRITE_SIZE - sprite.height()) as i64 / 2;
+        image::imageops::overlay(&mut image, &sprite.to_rgba8(), x, y);
+    }
+
+    // Long names need to be shrunk to not overlap the rank badge.
+    let name_scale = if text_width(card.name, 3) > WIDTH as i64 - STAT_COLUMN_X - 60 {
+        2
+    } else {
+        3
+    };
+    draw_text(
+        &mut image,
+        STAT_COLUMN_X,
+        PADDING + 4,
+        card.name,
+        name_scale,
+        TEXT,
+    );
+    draw_rank_badge(
+        &mut image,
+        WIDTH as i64 - PADDING - 14,
+        PADDING + 14,
+        &card.rank,
+    );
+
+    let mut type_x = STAT_COLUMN_X;
+    for pokemon_type in [Some(card.type1), card.type2].iter().flatten() {
+        type_x += draw_type_badge(&mut image, type_x, PADDING + 36, pokemon_type) + 8;
+    }
+
+    let level_y = PADDING + 64;
+    draw_text(
+        &mut image,
+        STAT_COLUMN_X,
+        level_y,
+        &format!("LV {}", card.level),
+        2,
+        TEXT,
+    );
+    draw_progress_bar(
+        &mut image,
+        STAT_COLUMN_X + 70,
+        level_y + 2,
+        WIDTH as i64 - PADDING - STAT_COLUMN_X - 70,
+        10,
+        card.experience.clamp(0, card.experience_per_level) as f32
+            / card.experience_per_level as f32,
+    );
+
+    let stats_y = level_y + 32;
+    #[rustfmt::skip]
+    let combat = [
+        ("STR", card.combat_stats.get(SingleCharacterStatType::Strength)),
+        ("DEX", card.combat_stats.get(SingleCharacterStatType::Dexterity)),
+        ("VIT", card.combat_stats.get(SingleCharacterStatType::Vitality)),
+        ("SPE", card.combat_stats.get(SingleCharacterStatType::Special)),
+        ("INS", card.combat_stats.get(SingleCharacterStatType::Insight)),
+    ];
+    #[rustfmt::skip]
+    let social = [
+        ("TOU", card.social_stats.get(SingleCharacterStatType::Tough)),
+        ("COO", card.social_stats.get(SingleCharacterStatType::Cool)),
+        ("BEA", card.social_stats.get(SingleCharacterStatType::Beauty)),
+        ("CUT", card.social_stats.get(SingleCharacterStatType::Cute)),
+        ("CLE", card.social_stats.get(SingleCharacterStatType::Clever)),
+    ];
+    for (index, (label, stat)) in combat.iter().enumerate() {
+        let y = stats_y + index as i64 * STAT_ROW_HEIGHT;
+        draw_stat_row(&mut image, STAT_COLUMN_X, y, label, stat);
+    }
+    for (index, (label, stat)) in social.iter().enumerate() {
+        let y = stats_y + index as i64 * STAT_ROW_HEIGHT;
+        draw_stat_row(&mut image, SOCIAL_COLUMN_X, y, label, stat);
+    }
+
+    let mut tera_x = PADDING;
+    let tera_y = HEIGHT as i64 - PADDING - 16;
+    for charge in card.tera_charges.iter().filter(|x| x.unlocked > 0) {
+        let text = format!("{}/{}", charge.remaining(), charge.unlocked);
+        let width = 16 + 6 + text_width(&text, 2);
+        if tera_x + width > WIDTH as i64 - PADDING {
+            break;
+        }
+
+        fill_rect(
+            &mut image,
+            tera_x,
+            tera_y,
+            16,
+            16,
+            type_without_shadow_color(&charge.tera_type),
+        );
+        draw_text(&mut image, tera_x + 22, tera_y + 1, &text, 2, TEXT);
+        tera_x += width + 14;
+    }
+
+    let mut cursor = Cursor::new(Vec::new());
+    DynamicImage::ImageRgba8(image).write_to(&mut cursor, ImageFormat::Png)?;
+    Ok(cursor.into_inner())
+}
+
+fn draw_stat_row(image: &mut RgbaImage, x: i64, y: i64, label: &str, stat: &CharacterStat) {
+    draw_text(image, x, y, label, 2, TEXT_MUTED);
+
+    let dot_count = stat.current.max(stat.species_max);
+    for i in 0..dot_count {
+        let color = if i >= stat.species_max {
+            DOT_LIMIT_BREAK
+        } else if i < stat.current {
+            DOT_FILLED
+        } else {
+            DOT_EMPTY
+        };
+        let center_x = x + 48 + DOT_RADIUS + i * DOT_SPACING;
+        fill_circle(image, center_x, y + GLYPH_HEIGHT, DOT_RADIUS, color);
+    }
+}
+
+fn draw_progress_bar(image: &mut RgbaImage, x: i64, y: i64, width: i64, height: i64, ratio: f32) {
+    fill_rect(image, x, y, width, height, PANEL);
+    fill_rect(
+        image,
+        x,
+        y,
+        (width as f32 * ratio) as i64,
+        height,
+        EXPERIENCE_BAR,
+    );
+}
+
+fn draw_rank_badge(image: &mut RgbaImage, x: i64, y: i64, rank: &MysteryDungeonRank) {
+    fill_circle(image, x, y, 14, rank_color(rank));
+    let initial = &rank.name_without_emoji()[..1];
+    draw_text(
+        image,
+        x - GLYPH_WIDTH + 1,
+        y - GLYPH_HEIGHT + 1,
+        initial,
+        2,
+        BACKGROUND,
+    );
+}
+
+/// Returns the width of the badge.
+fn draw_type_badge(image: &mut RgbaImage, x: i64, y: i64, pokemon_type: &PokemonType) -> i64 {
+    let name = format!("{:?}", pokemon_type);
+    let width = text_width(&name, 2) + 16;
+    fill_rect(image, x, y, width, 20, type_color(pokemon_type));
+    draw_text(image, x + 8, y + 3, &name, 2, TEXT);
+    width
+}
+
+fn put_pixel(image: &mut RgbaImage, x: i64, y: i64, color: Rgba<u8>) {
+    if x >= 0 && y >= 0 && x < image.width() as i64 && y < image.height() as i64 {
+        image.put_pixel(x as u32, y as u32, color);
+    }
+}
+
+fn fill_rect(image: &mut RgbaImage, x: i64, y: i64, width: i64, height: i64, color: Rgba<u8>) {
+    for dy in 0..height {
+        for dx in 0..width {
+            put_pixel(image, x + dx, y + dy, color);
+        }
+    }
+}
+
+fn fill_circle(image: &mut RgbaImage, center_x: i64, center_y: i64, radius: i64, color: Rgba<u8>) {
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            if dx * dx + dy * dy <= radius * radius {
+                put_pixel(image, center_x + dx, center_y + dy, color);
+            }
+        }
+    }
+}
+
+fn text_width(text: &str, scale: i64) -> i64 {
+    (text.chars().count() as i64 * GLYPH_ADVANCE - 1).max(0) * scale
+}
+
+fn draw_text(image: &mut RgbaImage, x: i64, y: i64, text: &str, scale: i64, color: Rgba<u8>) {
+    for (index, c) in text.chars().enumerate() {
+        let glyph = glyph(c.to_ascii_uppercase());
+        let glyph_x = x + index as i64 * GLYPH_ADVANCE * scale;
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) != 0 {
+                    fill_rect(
+                        image,
+                        glyph_x + column * scale,
+                        y + row as i64 * scale,
+                        scale,
+                        scale,
+                        color,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// A tiny built-in font, so cards can be rendered without any font files.
+#[rustfmt::skip]
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        '/' => [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000],
+        ':' => [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        '+' => [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        '\'' => [0b00100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000],
+        ' ' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000],
+        _ => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100],
+    }
+}
+
+fn rank_color(rank: &MysteryDungeonRank) -> Rgba<u8> {
+    match rank {
+        MysteryDungeonRank::Bronze => Rgba([205, 127, 50, 255]),
+        MysteryDungeonRank::Silver => Rgba([192, 192, 192, 255]),
+        MysteryDungeonRank::Gold => Rgba([255, 215, 0, 255]),
+        MysteryDungeonRank::Platinum => Rgba([160, 220, 220, 255]),
+        MysteryDungeonRank::Diamond => Rgba([120, 200, 255, 255]),
+        MysteryDungeonRank::Master => Rgba([180, 90, 230, 255]),
+    }
+}
+
+fn type_color(pokemon_type: &PokemonType) -> Rgba<u8> {
+    match pokemon_type {
+        PokemonType::Normal => Rgba([159, 161, 159, 255]),
+        PokemonType::Fighting => Rgba([255, 128, 0, 255]),
+        PokemonType::Flying => Rgba([129, 185, 239, 255]),
+        PokemonType::Poison => Rgba([145, 65, 203, 255]),
+        PokemonType::Ground => Rgba([145, 81, 33, 255]),
+        PokemonType::Rock => Rgba([175, 169, 129, 255]),
+        PokemonType::Bug => Rgba([145, 161, 25, 255]),
+        PokemonType::Ghost => Rgba([112, 65, 112, 255]),
+        PokemonType::Steel => Rgba([96, 161, 184, 255]),
+        PokemonType::Fire => Rgba([230, 40, 41, 255]),
+        PokemonType::Water => Rgba([41, 128, 239, 255]),
+        PokemonType::Grass => Rgba([63, 161, 41, 255]),
+        PokemonType::Electric => Rgba([250, 192, 0, 255]),
+        PokemonType::Psychic => Rgba([239, 65, 121, 255]),
+        PokemonType::Ice => Rgba([61, 206, 243, 255]),
+        PokemonType::Dragon => Rgba([80, 96, 225, 255]),
+        PokemonType::Dark => Rgba([98, 77, 78, 255]),
+        PokemonType::Fairy => Rgba([239, 113, 239, 255]),
+        PokemonType::Shadow => Rgba([60, 45, 80, 255]),
+        PokemonType::Virus => Rgba([40, 160, 110, 255]),
+    }
+}
+
+fn type_without_shadow_color(pokemon_type: &PokemonTypeWithoutShadow) -> Rgba<u8> {
+    type_color(&match pokemon_type {
+        PokemonTypeWithoutShadow::Normal => PokemonType::Normal,
+        PokemonTypeWithoutShadow::Fighting => PokemonType::Fighting,
+        PokemonTypeWithoutShadow::Flying => PokemonType::Flying,
+        PokemonTypeWithoutShadow::Poison => PokemonType::Poison,
+        PokemonTypeWithoutShadow::Ground => PokemonType::Ground,
+        PokemonTypeWithoutShadow::Rock => PokemonType::Rock,
+        PokemonTypeWithoutShadow::Bug => PokemonType::Bug,
+        PokemonTypeWithoutShadow::Ghost => PokemonType::Ghost,
+        PokemonTypeWithoutShadow::Steel => PokemonType::Steel,
+        PokemonTypeWithoutShadow::Fire => PokemonType::Fire,
+        PokemonTypeWithoutShadow::Water => PokemonType::Water,
+        PokemonTypeWithoutShadow::Grass => PokemonType::Grass,
+        PokemonTypeWithoutShadow::Electric => PokemonType::Electric,
+        PokemonTypeWithoutShadow::Psychic => PokemonType::Psychic,
+        PokemonTypeWithoutShadow::Ice => PokemonType::Ice,
+        PokemonTypeWithoutShadow::Dragon => PokemonType::Dragon,
+        PokemonTypeWithoutShadow::Dark => PokemonType::Dark,
+        PokemonTypeWithoutShadow::Fairy => PokemonType::Fairy,
+    })
+}
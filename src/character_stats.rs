@@ -240,16 +240,30 @@ Willpower: {}
 Defense: {}
 Special Defense: {}
 Active Move Limit: {}
-",
+{}",
                     (base_hp as i64 + self.vitality_or_beauty.current) * 2,
                     self.insight_or_clever.current + 2,
                     self.build_stat_block(),
                     (self.vitality_or_beauty.current as f32 * 0.5).ceil(),
                     (self.insight_or_clever.current as f32 * 0.5).ceil(),
                     self.insight_or_clever.current + 2,
+                    self.build_limit_break_row(),
                 )
             }
-            CharacterStatType::Social => self.build_stat_block(),
+            CharacterStatType::Social => {
+                format!(
+                    "{}{}",
+                    self.build_stat_block(),
+                    self.build_limit_break_row()
+                )
+            }
+        }
+    }
+
+    fn build_limit_break_row(&self) -> String {
+        match self.count_limit_breaks() {
+            0 => String::new(),
+            limit_breaks => format!("Limit Breaks: {}\n", limit_breaks),
         }
     }
 
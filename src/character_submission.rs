@@ -0,0 +1,210 @@
+use serenity::all::{ButtonStyle, CreateActionRow, UserId};
+use serenity::prelude::Mentionable;
+use sqlx::{Pool, Sqlite};
+
+use crate::enums::{CharacterSubmissionStatus, Gender};
+use crate::game_data::{GameData, PokemonApiId};
+use crate::{emoji, helpers, Error};
+
+pub const APPROVE: &str = "approve";
+pub const REJECT: &str = "reject";
+pub const REQUEST_CHANGES: &str = "changes";
+
+/// A character a player would like to create, waiting for a GM to review it.
+pub struct CharacterSubmission {
+    pub id: i64,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub name: String,
+    pub species_api_id: i64,
+    pub gender: Gender,
+    pub is_shiny: bool,
+    pub backstory: Option<String>,
+    pub status: CharacterSubmissionStatus,
+    pub channel_id: i64,
+    pub reviewer_id: Option<i64>,
+    pub feedback: Option<String>,
+}
+
+impl CharacterSubmission {
+    pub async fn load(database: &Pool<Sqlite>, id: i64) -> Result<Option<Self>, Error> {
+        let record = sqlx::query!(
+            "SELECT id, guild_id, user_id, name, species_api_id, phenotype, is_shiny, backstory, status, channel_id, reviewer_id, feedback \
+             FROM character_submission WHERE id = ?",
+            id
+        )
+        .fetch_optional(database)
+        .await?;
+
+        Ok(record.map(|x| CharacterSubmission {
+            id: x.id,
+            guild_id: x.guild_id,
+            user_id: x.user_id,
+            name: x.name,
+            species_api_id: x.species_api_id,
+            gender: Gender::from_phenotype(x.phenotype),
+            is_shiny: x.is_shiny,
+            backstory: x.backstory,
+            status: CharacterSubmissionStatus::from_repr(x.status)
+                .unwrap_or(CharacterSubmissionStatus::Pending),
+            channel_id: x.channel_id,
+            reviewer_id: x.reviewer_id,
+            feedback: x.feedback,
+        }))
+    }
+
+    pub fn build_review_message(&self, game_data: &GameData) -> String {
+        let species = match game_data
+            .pokemon_by_api_id
+            .get(&PokemonApiId(self.species_api_id as u16))
+        {
+            Some(pokemon) => pokemon.name.clone(),
+            None => format!("Unknown Pokemon #{}", self.species_api_id),
+        };
+        let shiny = if self.is_shiny { " ✨" } else { "" };
+
+        let mut result = format!(
+            "## 📝 Character Submission\n**Player:** {}\n**Name:** {}\n**Species:** {}{} ({:?})\n",
+            UserId::new(self.user_id as u64).mention(),
+            self.name,
+            species,
+            shiny,
+            self.gender
+        );
+        if let Some(backstory) = &self.backstory {
+            result.push_str(&format!("**Backstory:** {}\n", backstory));
+        }
+
+        let reviewer = self
+            .reviewer_id
+            .map(|x| UserId::new(x as u64).mention().to_string())
+            .unwrap_or_default();
+        match self.status {
+            CharacterSubmissionStatus::Pending => result.push_str("**Status:** ⏳ Pending"),
+            CharacterSubmissionStatus::Approved => result.push_str(&format!(
+                "**Status:** {} Approved by {}",
+                emoji::UNICODE_CHECK_MARK,
+                reviewer
+            )),
+            CharacterSubmissionStatus::Rejected => result.push_str(&format!(
+                "**Status:** {} Rejected by {}",
+                emoji::UNICODE_CROSS_MARK,
+                reviewer
+            )),
+            CharacterSubmissionStatus::ChangesRequested => {
+                result.push_str(&format!("**Status:** ✏️ Changes requested by {}", reviewer))
+            }
+        }
+        if let Some(feedback) = &self.feedback {
+            result.push_str(&format!("\n**Feedback:** {}", feedback));
+        }
+
+        result
+    }
+}
+
+pub fn create_review_buttons(submission_id: i64) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        helpers::create_styled_button(
+            "Approve",
+            &format!("character-submission_{}_{}", APPROVE, submission_id),
+            false,
+            ButtonStyle::Success,
+        ),
+        helpers::create_styled_button(
+            "Reject",
+            &format!("character-submission_{}_{}", REJECT, submission_id),
+            false,
+            ButtonStyle::Danger,
+        ),
+        helpers::create_styled_button(
+            "Request Changes",
+            &format!("character-submission_{}_{}", REQUEST_CHANGES, submission_id),
+            false,
+            ButtonStyle::Secondary,
+        ),
+    ])]
+}
+
+/// Marks a pending submission as reviewed.
+/// Returns false if someone else has already reviewed it in the meantime.
+pub async fn resolve(
+    database: &Pool<Sqlite>,
+    submission_id: i64,
+    status: CharacterSubmissionStatus,
+    reviewer_id: i64,
+    feedback: Option<&str>,
+) -> Result<bool, Error> {
+    let result = sqlx::query!(
+        "UPDATE character_submission SET status = ?, reviewer_id = ?, feedback = ? WHERE id = ? AND status = ?",
+        status,
+        reviewer_id,
+        feedback,
+        submission_id,
+        CharacterSubmissionStatus::Pending
+    )
+    .execute(database)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Puts a submission back into the review queue, e.g. because creating the character failed after approval.
+pub async fn reopen(database: &Pool<Sqlite>, submission_id: i64) -> Result<(), Error> {
+    sqlx::query!(
+        "UPDATE character_submission SET status = ?, reviewer_id = NULL, feedback = NULL WHERE id = ?",
+        CharacterSubmissionStatus::Pending,
+        submission_id
+    )
+    .execute(database)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Pool, Sqlite};
+
+    use crate::character_submission::CharacterSubmission;
+    use crate::enums::CharacterSubmissionStatus;
+    use crate::{character_submission, database_helpers, Error};
+
+    #[sqlx::test]
+    async fn submissions_should_only_be_reviewed_once(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        let submission_id = 300;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        sqlx::query!(
+            "INSERT INTO character_submission (id, guild_id, user_id, name, species_api_id, phenotype, is_shiny, channel_id, creation_timestamp) \
+             VALUES (?, ?, ?, 'Test', 1, 1, false, 0, 0)",
+            submission_id,
+            guild_id,
+            user_id
+        )
+        .execute(&db)
+        .await?;
+
+        let status = CharacterSubmissionStatus::ChangesRequested;
+        assert!(character_submission::resolve(&db, submission_id, status, 1, Some("Hmm")).await?);
+        let status = CharacterSubmissionStatus::Approved;
+        assert!(!character_submission::resolve(&db, submission_id, status, 2, None).await?);
+
+        let submission = CharacterSubmission::load(&db, submission_id)
+            .await?
+            .expect("Submission should exist");
+        assert_eq!(
+            CharacterSubmissionStatus::ChangesRequested,
+            submission.status
+        );
+        assert_eq!(Some(1), submission.reviewer_id);
+        assert_eq!(Some(String::from("Hmm")), submission.feedback);
+
+        character_submission::reopen(&db, submission_id).await?;
+        assert!(character_submission::resolve(&db, submission_id, status, 2, None).await?);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 use std::cmp::Ordering;
 
+use strum::IntoEnumIterator;
+
+use crate::commands::characters::ActionType;
 use crate::commands::Context;
 use crate::enums::PokemonTypeWithoutShadow;
+use crate::guild_treasury::GUILD_TREASURY_NAME;
 
 fn filter_and_sort<'a>(
     partial: &str,
@@ -75,12 +79,22 @@ pub async fn autocomplete_pokemon_type<'a>(_ctx: Context<'a>, partial: &'a str)
     filter_and_sort(partial, PokemonTypeWithoutShadow::get_names_vec().iter(), 0)
 }
 
+pub async fn autocomplete_action_type<'a>(_ctx: Context<'a>, partial: &'a str) -> Vec<String> {
+    let names: Vec<String> = ActionType::iter()
+        .filter(|x| x != &ActionType::DoNotLog)
+        .map(|x| String::from(<&'static str>::from(x)))
+        .collect();
+    filter_and_sort(partial, names.iter(), 0)
+}
+
 pub async fn autocomplete_zmove<'a>(ctx: Context<'a>, partial: &'a str) -> Vec<String> {
     filter_and_sort(partial, ctx.data().game.z_move_names.iter(), 0)
 }
 
 
 pub async fn autocomplete_character_name<'a>(ctx: Context<'a>, partial: &'a str) -> Vec<String> {
+    // Some commands using this also work in DMs, where there simply aren't any characters to suggest.
+    let guild_id = ctx.guild_id().map(|x| x.get());
     filter_and_sort(
         partial,
         ctx.data()
@@ -88,7 +102,7 @@ pub async fn autocomplete_character_name<'a>(ctx: Context<'a>, partial: &'a str)
             .get_characters()
             .await
             .iter()
-            .filter(|x| x.guild_id == ctx.guild_id().expect("Command should be guild_only!").get())
+            .filter(|x| Some(x.guild_id) == guild_id)
             .filter(|x| !x.is_retired)
             .map(|x| x.get_autocomplete_name()),
         0,
@@ -111,6 +125,70 @@ pub async fn autocomplete_wallet_name<'a>(ctx: Context<'a>, partial: &'a str) ->
     }
 }
 
+pub async fn autocomplete_wallet_name_or_guild_treasury<'a>(
+    ctx: Context<'a>,
+    partial: &'a str,
+) -> Vec<String> {
+    let guild_id = ctx.guild_id().expect("Command should be guild_only!").get() as i64;
+    let entries = sqlx::query!(
+        "SELECT name FROM wallet WHERE wallet.guild_id = ?",
+        guild_id
+    )
+    .fetch_all(&ctx.data().database)
+    .await;
+
+    let mut names = vec![String::from(GUILD_TREASURY_NAME)];
+    if let Ok(entries) = entries {
+        names.extend(entries.into_iter().map(|x| x.name));
+    }
+
+    filter_and_sort(partial, names.iter(), 0)
+}
+
+pub async fn autocomplete_shop_name<'a>(ctx: Context<'a>, partial: &'a str) -> Vec<String> {
+    let guild_id = ctx.guild_id().expect("Command should be guild_only!").get() as i64;
+    let entries = sqlx::query!("SELECT name FROM shop WHERE shop.guild_id = ?", guild_id)
+        .fetch_all(&ctx.data().database)
+        .await;
+
+    if let Ok(entries) = entries {
+        filter_and_sort(partial, entries.iter().map(|x| &x.name), 0)
+    } else {
+        Vec::new()
+    }
+}
+
+pub async fn autocomplete_team_name<'a>(ctx: Context<'a>, partial: &'a str) -> Vec<String> {
+    let guild_id = ctx.guild_id().expect("Command should be guild_only!").get() as i64;
+    let entries = sqlx::query!("SELECT name FROM team WHERE team.guild_id = ?", guild_id)
+        .fetch_all(&ctx.data().database)
+        .await;
+
+    if let Ok(entries) = entries {
+        filter_and_sort(partial, entries.iter().map(|x| &x.name), 0)
+    } else {
+        Vec::new()
+    }
+}
+
+pub async fn autocomplete_companion_name<'a>(ctx: Context<'a>, partial: &'a str) -> Vec<String> {
+    let guild_id = ctx.guild_id().expect("Command should be guild_only!").get() as i64;
+    let entries = sqlx::query!(
+        "SELECT DISTINCT companion.name FROM companion \
+         JOIN character ON character.id = companion.character_id \
+         WHERE character.guild_id = ?",
+        guild_id
+    )
+    .fetch_all(&ctx.data().database)
+    .await;
+
+    if let Ok(entries) = entries {
+        filter_and_sort(partial, entries.iter().map(|x| &x.name), 0)
+    } else {
+        Vec::new()
+    }
+}
+
 pub async fn autocomplete_owned_character_name<'a>(
     ctx: Context<'a>,
     partial: &'a str,
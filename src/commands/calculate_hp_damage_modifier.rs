@@ -1,11 +1,37 @@
-use crate::commands::{Context, Error};
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::health::get_character_health;
+use crate::commands::{find_character, Context, Error};
+use crate::errors::ValidationError;
 
 /// Use this to calculate your die count for reversal and other HP based moves
 #[poise::command(slash_command)]
 pub async fn calculate_hp_damage_modifier(
     ctx: Context<'_>,
-    #[description = "What's your max HP?"] max_hp: u8,
+    #[description = "Which character? Their max HP will be used."]
+    #[autocomplete = "autocomplete_character_name"]
+    character: Option<String>,
+    #[description = "What's your max HP?"] max_hp: Option<u8>,
 ) -> Result<(), Error> {
+    let max_hp = match (character, max_hp) {
+        (_, Some(max_hp)) => max_hp,
+        (Some(character), None) => {
+            let Some(guild_id) = ctx.guild_id() else {
+                return Err(Box::new(ValidationError::new(
+                    "Characters can only be selected within a server. Please enter your max HP instead.",
+                )));
+            };
+            let character = find_character(ctx.data(), guild_id.get(), &character).await?;
+            let health =
+                get_character_health(&ctx.data().database, &ctx.data().game, character.id).await?;
+            health.max_hp.clamp(0, u8::MAX as i64) as u8
+        }
+        (None, None) => {
+            return Err(Box::new(ValidationError::new(
+                "Please either select a character or enter your max HP.",
+            )))
+        }
+    };
+
     ctx.say(build_string(max_hp)).await?;
     Ok(())
 }
@@ -0,0 +1,121 @@
+use poise::CreateReply;
+use serenity::all::{ChannelId, CreateAttachment, EditAttachments, EditMessage, MessageId};
+
+use crate::character_card::CharacterCard;
+use crate::character_stats::GenericCharacterStats;
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::create_emojis::load_local_sprite;
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, send_ephemeral_reply, Context, Error,
+};
+use crate::enums::Gender;
+use crate::game_data::PokemonApiId;
+use crate::guild_settings::GuildSettings;
+use crate::{character_card, helpers, tera_charges};
+
+const FILE_NAME: &str = "character_card.png";
+
+/// Render a character's stats as image.
+#[poise::command(slash_command, guild_only)]
+pub async fn character_card(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Attach the card to the character post instead?"] attach_to_post: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let attach_to_post = attach_to_post.unwrap_or(false);
+    if attach_to_post {
+        ensure_user_owns_character_or_is_gm(&ctx, &character).await?;
+    }
+
+    let record = sqlx::query!("SELECT * FROM character WHERE id = ?", character.id)
+        .fetch_one(&ctx.data().database)
+        .await?;
+
+    let game_data = &ctx.data().game;
+    let pokemon = game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(record.species_api_id as u16))
+        .expect("DB IDs should always be mappable.");
+    let settings = GuildSettings::load(&ctx.data().database, record.guild_id).await;
+    let level = settings.calculate_level_from_experience(record.experience);
+    let form = helpers::get_active_form(pokemon, game_data, record.active_form_api_id);
+    let pokemon_for_stats = helpers::get_usual_evolution_stage_for_level(
+        level,
+        form,
+        game_data,
+        record.species_override_for_stats,
+    );
+    let combat_stats = GenericCharacterStats::from_combat(
+        pokemon_for_stats,
+        record.stat_strength,
+        record.stat_dexterity,
+        record.stat_vitality,
+        record.stat_special,
+        record.stat_insight,
+    );
+    let social_stats = GenericCharacterStats::from_social(
+        record.stat_tough,
+        record.stat_cool,
+        record.stat_beauty,
+        record.stat_cute,
+        record.stat_clever,
+    );
+
+    let gender = Gender::from_phenotype(record.phenotype);
+    let sprite = match load_local_sprite(pokemon, &gender, record.is_shiny) {
+        Ok(sprite) => Some(sprite),
+        Err(e) => {
+            log::warn!("Unable to load sprite for {}: {}", pokemon.name, e);
+            None
+        }
+    };
+
+    let tera_charges = tera_charges::get_tera_charges(&ctx.data().database, character.id).await;
+
+    let png = character_card::render(&CharacterCard {
+        name: &record.name,
+        sprite,
+        rank: settings.calculate_rank(level),
+        level,
+        experience: settings.calculate_current_experience(record.experience),
+        experience_per_level: settings.experience_per_level,
+        type1: form.type1,
+        type2: form.type2,
+        combat_stats: &combat_stats,
+        social_stats: &social_stats,
+        tera_charges,
+    })?;
+
+    if attach_to_post {
+        let mut message = ctx
+            .serenity_context()
+            .http
+            .get_message(
+                ChannelId::new(record.stat_channel_id as u64),
+                MessageId::new(record.stat_message_id as u64),
+            )
+            .await?;
+        message
+            .edit(
+                ctx,
+                EditMessage::new().attachments(
+                    EditAttachments::new().add(CreateAttachment::bytes(png, FILE_NAME)),
+                ),
+            )
+            .await?;
+        send_ephemeral_reply(
+            &ctx,
+            &format!("Attached a new card to {}'s post.", record.name),
+        )
+        .await?;
+    } else {
+        ctx.send(CreateReply::default().attachment(CreateAttachment::bytes(png, FILE_NAME)))
+            .await?;
+    }
+
+    Ok(())
+}
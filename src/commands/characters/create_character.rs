@@ -1,14 +1,17 @@
-use serenity::all::{CreateMessage, GetMessages};
+use serenity::all::Message;
 use serenity::model::user::User;
 
 use crate::commands::autocompletion::autocomplete_pokemon;
-use crate::commands::characters::{log_action, validate_user_input, ActionType};
+use crate::commands::characters::{
+    insert_character, log_action_with_target, post_placeholder_stat_message_in_channel,
+    validate_user_input, ActionLogTarget, ActionType, NewCharacter, DEFAULT_STARTING_MONEY,
+};
 use crate::commands::{
     create_emojis, ensure_guild_exists, ensure_user_exists, pokemon_from_autocomplete_string,
     send_ephemeral_reply, send_error, update_character_post, Context, Error,
 };
+use crate::emoji;
 use crate::enums::Gender;
-use crate::{emoji, helpers};
 
 /// Create a new character within the database.
 #[allow(clippy::too_many_arguments)]
@@ -103,26 +106,11 @@ async fn execute(
     let pokemon = pokemon_from_autocomplete_string(&ctx, &pokemon_species)?;
     let is_shiny = is_shiny.unwrap_or(false);
     let exp = exp.unwrap_or(0);
-    let money = money.unwrap_or(500);
-    let phenotype = gender as i64;
-
-    let message = ctx
-        .channel_id()
-        .send_message(
-            ctx,
-            CreateMessage::new().content(
-                "[Placeholder. This should get replaced or deleted within a couple seconds.]",
-            ),
-        )
-        .await?;
+    let money = money.unwrap_or(DEFAULT_STARTING_MONEY);
 
-    if let Ok(messages) = ctx.channel_id().messages(ctx, GetMessages::new()).await {
-        if messages.len() > 10 {
-            send_error(&ctx, "Seems like there's already been a looot of messages been sent in this channel! Cannot create a character here, that seems wrong.").await?;
-            message.delete(ctx).await?;
-            return Ok(());
-        }
-    }
+    let Some(message) = post_placeholder_stat_message(&ctx).await? else {
+        return Ok(());
+    };
 
     let user_id = player.id.get() as i64;
     let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
@@ -130,42 +118,30 @@ async fn execute(
     ensure_guild_exists(&ctx, guild_id).await;
     ensure_user_exists(&ctx, user_id, guild_id).await;
 
-    let stat_message_id = message.id.get() as i64;
-    let stat_channel_id = message.channel_id.get() as i64;
-    let creation_date = chrono::Utc::now().date_naive();
-
-    let level = helpers::calculate_level_from_experience(exp);
-    let mon = helpers::get_usual_evolution_stage_for_level(level, pokemon, &ctx.data().game, None);
-
-    let record = sqlx::query!(
-        "INSERT INTO character (user_id, guild_id, name, stat_message_id, stat_channel_id, creation_date, experience, money, species_api_id, is_shiny, phenotype,\
-                                stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight
-) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
-        user_id,
-        guild_id,
-        name,
-        stat_message_id,
-        stat_channel_id,
-        creation_date,
-        exp,
-        money,
-        pokemon.poke_api_id.0,
-        is_shiny,
-        phenotype,
-        mon.strength.min,
-        mon.dexterity.min,
-        mon.vitality.min,
-        mon.special.min,
-        mon.insight.min,
-    ).fetch_one(&ctx.data().database)
-        .await;
+    let record = insert_character(
+        &ctx.data().database,
+        &ctx.data().game,
+        NewCharacter {
+            user_id,
+            guild_id,
+            name: &name,
+            pokemon,
+            gender,
+            is_shiny,
+            experience: exp,
+            money,
+            stat_channel_id: message.channel_id.get() as i64,
+            stat_message_id: message.id.get() as i64,
+        },
+    )
+    .await;
 
     create_emojis::create_emojis_for_pokemon(&ctx, pokemon, &gender, is_shiny).await;
 
-    if let Ok(record) = record {
+    if let Ok(character_id) = record {
         send_ephemeral_reply(&ctx, "Character has been successfully created!").await?;
-        update_character_post(&ctx, record.id).await;
-        log_action(
+        update_character_post(&ctx, character_id).await;
+        log_action_with_target(
             &ActionType::Initialization,
             &ctx,
             &format!(
@@ -176,6 +152,7 @@ async fn execute(
                 emoji::POKE_COIN,
                 exp
             ),
+            ActionLogTarget::character(character_id, None),
         )
         .await?;
         ctx.data()
@@ -190,3 +167,16 @@ async fn execute(
 
     Ok(())
 }
+
+/// Posts the message which will later contain the character's stats.
+/// Returns None in case the channel doesn't look like a dedicated character channel.
+pub(super) async fn post_placeholder_stat_message<'a>(
+    ctx: &Context<'a>,
+) -> Result<Option<Message>, Error> {
+    let message = post_placeholder_stat_message_in_channel(ctx, ctx.channel_id()).await?;
+    if message.is_none() {
+        send_error(ctx, "Seems like there's already been a looot of messages been sent in this channel! Cannot create a character here, that seems wrong.").await?;
+    }
+
+    Ok(message)
+}
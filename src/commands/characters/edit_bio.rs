@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use poise::Modal;
+use serenity::all::{
+    CreateActionRow, CreateInputText, CreateInteractionResponse, CreateModal, InputTextStyle,
+    ModalInteractionData,
+};
+
+use crate::commands::autocompletion::autocomplete_owned_character_name;
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::{
+    ensure_user_owns_character, find_character, send_ephemeral_reply, update_character_post, Error,
+};
+use crate::data::Data;
+
+/// Bio texts are displayed on the character post, so they need to stay short enough to fit in there.
+/// The derive macro for modals only accepts literals, so modals with bio fields are implemented by hand.
+pub(super) const MAX_BIO_FIELD_LENGTH: u16 = 300;
+
+#[derive(Debug)]
+struct BioModal {
+    appearance: Option<String>,
+    backstory: Option<String>,
+}
+
+impl Modal for BioModal {
+    fn create(defaults: Option<Self>, custom_id: String) -> CreateInteractionResponse {
+        let (appearance, backstory) = match defaults {
+            Some(defaults) => (defaults.appearance, defaults.backstory),
+            None => (None, None),
+        };
+        CreateInteractionResponse::Modal(CreateModal::new(custom_id, "Character Bio").components(
+            vec![
+                create_bio_input(
+                    "Appearance",
+                    "appearance",
+                    "What does your character look like?",
+                    appearance,
+                ),
+                create_bio_input(
+                    "Backstory",
+                    "backstory",
+                    "Where does your character come from?",
+                    backstory,
+                ),
+            ],
+        ))
+    }
+
+    fn parse(mut data: ModalInteractionData) -> Result<Self, &'static str> {
+        Ok(BioModal {
+            appearance: poise::find_modal_text(&mut data, "appearance"),
+            backstory: poise::find_modal_text(&mut data, "backstory"),
+        })
+    }
+}
+
+/// An optional paragraph input limited to [MAX_BIO_FIELD_LENGTH].
+pub(super) fn create_bio_input(
+    label: &str,
+    custom_id: &str,
+    placeholder: &str,
+    value: Option<String>,
+) -> CreateActionRow {
+    let mut input = CreateInputText::new(InputTextStyle::Paragraph, label, custom_id)
+        .placeholder(placeholder)
+        .required(false)
+        .max_length(MAX_BIO_FIELD_LENGTH);
+    if let Some(value) = value {
+        input = input.value(value);
+    }
+
+    CreateActionRow::InputText(input)
+}
+
+/// Edit your character's appearance and backstory.
+#[poise::command(slash_command, guild_only)]
+pub async fn edit_bio(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+
+    let record = sqlx::query!(
+        "SELECT appearance, backstory FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let defaults = BioModal {
+        appearance: record.appearance,
+        backstory: record.backstory,
+    };
+    let Some(bio) =
+        poise::execute_modal(ctx, Some(defaults), Some(Duration::from_secs(600))).await?
+    else {
+        return Ok(());
+    };
+
+    let appearance = sanitize_bio_field(bio.appearance);
+    let backstory = sanitize_bio_field(bio.backstory);
+    sqlx::query!(
+        "UPDATE character SET appearance = ?, backstory = ? WHERE id = ?",
+        appearance,
+        backstory,
+        character.id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    let ctx = poise::Context::Application(ctx);
+    update_character_post(&ctx, character.id).await;
+    send_ephemeral_reply(&ctx, &format!("Updated {}'s bio.", character.name)).await?;
+    log_action_with_target(
+        &ActionType::CharacterEdit,
+        &ctx,
+        &format!("Updated {}'s bio.", character.name),
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
+
+pub(super) fn sanitize_bio_field(value: Option<String>) -> Option<String> {
+    value
+        .map(|x| {
+            x.trim()
+                .chars()
+                .take(MAX_BIO_FIELD_LENGTH as usize)
+                .collect::<String>()
+        })
+        .filter(|x| !x.is_empty())
+}
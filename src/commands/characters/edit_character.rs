@@ -1,7 +1,10 @@
 use crate::commands::autocompletion::autocomplete_character_name;
 use crate::commands::autocompletion::autocomplete_pokemon;
 use crate::commands::autocompletion::autocomplete_pokemon_type;
-use crate::commands::characters::{log_action, reset_character_stats, ActionType};
+use crate::commands::characters::{
+    log_action_with_target, reset_character_stats, transfer_character_ownership, ActionLogTarget,
+    ActionType,
+};
 use crate::commands::create_emojis::create_emojis_for_pokemon;
 use crate::commands::{
     ensure_user_exists, find_character, pokemon_from_autocomplete_string, send_ephemeral_reply,
@@ -10,6 +13,7 @@ use crate::commands::{
 use crate::enums::{Gender, PokemonTypeWithoutShadow};
 use crate::errors::ValidationError;
 use crate::game_data::PokemonApiId;
+use crate::tera_charges;
 use serenity::all::User;
 use serenity::prelude::Mentionable;
 
@@ -139,14 +143,14 @@ You can't (and probably also don't really want to) edit a character's species an
         )));
     }
 
-    let user_id = if let Some(new_owner) = new_owner {
+    let new_owner_id = if let Some(new_owner) = new_owner {
         action_log.push(format!("owner to {}", new_owner.mention()));
         invalidate_cache = true;
         let user_id = new_owner.id.get() as i64;
         ensure_user_exists(&ctx, user_id, guild_id as i64).await;
-        user_id
+        Some(user_id)
     } else {
-        record.user_id
+        None
     };
 
     if action_log.is_empty() {
@@ -159,26 +163,60 @@ You can't (and probably also don't really want to) edit a character's species an
         create_emojis_for_pokemon(&ctx, species, &gender, is_shiny).await;
     }
 
+    let mut transaction = ctx.data().database.begin().await?;
     sqlx::query!(
-        "UPDATE character SET name = ?, species_api_id = ?, species_override_for_stats = ?, is_shiny = ?, user_id = ? WHERE id = ?",
+        "UPDATE character SET name = ?, species_api_id = ?, species_override_for_stats = ?, is_shiny = ?, \
+            active_form_api_id = IIF(species_api_id = ?, active_form_api_id, NULL) WHERE id = ?",
         name,
         species.poke_api_id.0,
         species_override_for_stats,
         is_shiny,
-        user_id,
+        species.poke_api_id.0,
         character.id,
     )
-        .execute(&ctx.data().database)
+        .execute(&mut *transaction)
+        .await?;
+
+    // Registered forms belonged to the old species.
+    if species.poke_api_id.0 as i64 != record.species_api_id {
+        sqlx::query!(
+            "DELETE FROM character_form WHERE character_id = ?",
+            character.id
+        )
+        .execute(&mut *transaction)
         .await?;
+    }
+    transaction.commit().await?;
+
+    if let Some(new_owner_id) = new_owner_id {
+        if new_owner_id != record.user_id {
+            transfer_character_ownership(
+                &ctx.data().database,
+                character.id,
+                record.user_id,
+                new_owner_id,
+                true,
+            )
+            .await?;
+        }
+    }
 
     if let Some(tera_type) = tera_type {
         if let Some(tera_count) = tera_count {
-            let column = tera_type.get_tera_unlocked_column();
-            sqlx::query(&format!("UPDATE character SET {} = ? WHERE id = ?", column))
-                .bind(tera_count)
-                .bind(character.id)
-                .execute(&ctx.data().database)
-                .await?;
+            let used = tera_charges::get_tera_charges(&ctx.data().database, character.id)
+                .await
+                .iter()
+                .find(|x| x.tera_type == tera_type)
+                .map_or(0, |x| x.used.min(tera_count));
+            let mut connection = ctx.data().database.acquire().await?;
+            tera_charges::set_tera_charges(
+                &mut connection,
+                character.id,
+                tera_type,
+                tera_count,
+                used,
+            )
+            .await?;
         }
     }
 
@@ -197,10 +235,11 @@ You can't (and probably also don't really want to) edit a character's species an
     update_character_post(&ctx, character.id).await;
 
     let action_log = action_log.join(", ");
-    let _ = log_action(
+    let _ = log_action_with_target(
         &ActionType::CharacterEdit,
         &ctx,
         &format!("Set {}'s {}.", character.name, action_log),
+        ActionLogTarget::character(character.id, None),
     )
     .await;
     let _ = send_ephemeral_reply(
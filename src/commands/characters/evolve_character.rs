@@ -0,0 +1,207 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use poise::CreateReply;
+use serenity::all::{ButtonStyle, CreateActionRow, CreateInteractionResponse};
+
+use crate::commands::autocompletion::autocomplete_owned_character_name;
+use crate::commands::characters::{
+    change_species_or_form, log_action_with_target, ActionLogTarget, ActionType,
+};
+use crate::commands::create_emojis::create_emojis_for_pokemon;
+use crate::commands::{
+    ensure_user_owns_character, find_character, send_error, update_character_post, Context, Error,
+};
+use crate::enums::Gender;
+use crate::game_data::pokemon::Pokemon;
+use crate::game_data::{GameData, PokemonApiId};
+use crate::guild_settings::GuildSettings;
+use crate::helpers;
+
+const EVOLVE_PREFIX: &str = "evolve_character_";
+const ABORT: &str = "evolve_character_abort";
+const BUTTONS_PER_ROW: usize = 5;
+const MAX_EVOLUTION_ROWS: usize = 4;
+
+/// Evolve your character once they've reached the required level.
+#[poise::command(slash_command, guild_only)]
+pub async fn evolve_character(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+
+    let record = sqlx::query!(
+        "SELECT species_api_id, experience FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let species = ctx
+        .data()
+        .game
+        .pokemon_by_api_id
+        .get(&PokemonApiId(record.species_api_id as u16))
+        .expect("DB IDs should always be mappable.");
+    let settings = GuildSettings::load(&ctx.data().database, guild_id as i64).await;
+    let level = settings.calculate_level_from_experience(record.experience);
+    let evolutions = get_valid_evolutions(&ctx.data().game, species, level);
+
+    if evolutions.is_empty() {
+        return send_error(
+            &ctx,
+            &format!(
+                "**{} is unable to evolve right now.**\n*Either {} has no further evolutions, or they need to reach a higher level first.*",
+                character.name, species.name
+            ),
+        )
+        .await;
+    }
+
+    let mut components: Vec<CreateActionRow> = evolutions
+        .chunks(BUTTONS_PER_ROW)
+        .take(MAX_EVOLUTION_ROWS)
+        .map(|chunk| {
+            CreateActionRow::Buttons(
+                chunk
+                    .iter()
+                    .map(|x| {
+                        helpers::create_styled_button(
+                            &x.name,
+                            &format!("{}{}", EVOLVE_PREFIX, x.poke_api_id.0),
+                            false,
+                            ButtonStyle::Success,
+                        )
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+    components.push(CreateActionRow::Buttons(vec![
+        helpers::create_styled_button("Nope!", ABORT, false, ButtonStyle::Danger),
+    ]));
+
+    let original_message = format!(
+        "**{} is ready to evolve!** What should they evolve into?",
+        character.name
+    );
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .content(&original_message)
+                .components(components),
+        )
+        .await?;
+    let message = reply.message().await?;
+
+    let interaction = message
+        .await_component_interaction(ctx)
+        .author_id(ctx.author().id)
+        .timeout(Duration::from_secs(60))
+        .await;
+
+    let result_message = match interaction {
+        Some(interaction) => {
+            interaction
+                .create_response(ctx, CreateInteractionResponse::Acknowledge)
+                .await?;
+
+            let target = interaction
+                .data
+                .custom_id
+                .strip_prefix(EVOLVE_PREFIX)
+                .and_then(|x| x.parse::<u16>().ok())
+                .and_then(|id| evolutions.iter().find(|x| x.poke_api_id.0 == id));
+
+            match target {
+                Some(target) => {
+                    match execute_evolution(&ctx, character.id, species, target).await {
+                        Ok(_) => {
+                            update_character_post(&ctx, character.id).await;
+                            log_action_with_target(
+                                &ActionType::CharacterEvolution,
+                                &ctx,
+                                format!(
+                                    "{} evolved from {} into {}!",
+                                    character.name, species.name, target.name
+                                ),
+                                ActionLogTarget::character(character.id, None),
+                            )
+                            .await?;
+                            format!("**{} evolved into {}!**", character.name, target.name)
+                        }
+                        Err(e) => format!("{}\n\n{}", original_message, e),
+                    }
+                }
+                None => format!("{}\n\n**Request was cancelled.**", original_message),
+            }
+        }
+        None => format!(
+            "{}\n\n**Request timed out. Use the command again if needed.**",
+            original_message
+        ),
+    };
+
+    reply
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content(result_message)
+                .components(Vec::new()),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Returns all species which evolve from the given species and are usually reached at the given level.
+fn get_valid_evolutions<'a>(
+    game_data: &'a Arc<GameData>,
+    species: &Pokemon,
+    level: i64,
+) -> Vec<&'a Pokemon> {
+    let mut result: Vec<&Pokemon> = game_data
+        .pokemon_by_api_id
+        .values()
+        .filter(|x| x.evolves_from == Some(species.poke_api_id))
+        .filter(|x| {
+            helpers::get_usual_evolution_stage_for_level(level, x, game_data, None).poke_api_id
+                == x.poke_api_id
+        })
+        .collect();
+
+    result.sort_by(|a, b| a.name.cmp(&b.name));
+    result
+}
+
+async fn execute_evolution<'a>(
+    ctx: &Context<'a>,
+    character_id: i64,
+    species: &Pokemon,
+    target: &Pokemon,
+) -> Result<(), Error> {
+    change_species_or_form(
+        &ctx.data().database,
+        &ctx.data().game,
+        character_id,
+        species,
+        target,
+        None,
+    )
+    .await?;
+
+    let record = sqlx::query!(
+        "SELECT phenotype, is_shiny FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let gender = Gender::from_phenotype(record.phenotype);
+    create_emojis_for_pokemon(ctx, target, &gender, record.is_shiny).await;
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use poise::CreateReply;
+use serde::{Deserialize, Serialize};
+use serenity::all::CreateAttachment;
+use sqlx::{Pool, Sqlite};
+
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::characters::get_registered_forms;
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, health, moves, Context, Error,
+};
+use crate::tera_charges;
+
+/// Increase this whenever the format changes in a way older versions of the bot can't read.
+pub const CHARACTER_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedTeraCharge {
+    pub tera_type: String,
+    pub unlocked: i64,
+    pub used: i64,
+}
+
+/// Everything needed to recreate a character on another server.
+/// Server-specific things like the stat post are left out and will be created anew on import.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CharacterExport {
+    pub version: u32,
+    pub name: String,
+    pub species_api_id: i64,
+    pub species_override_for_stats: Option<i64>,
+    pub active_form_api_id: Option<i64>,
+    pub is_shiny: bool,
+    pub phenotype: i64,
+    pub creation_date: String,
+    pub experience: i64,
+    pub money: i64,
+    pub battle_points: i64,
+    pub backpack_upgrade_count: i64,
+    pub total_spar_count: i64,
+    pub weekly_spar_count: i64,
+    pub total_new_player_tour_count: i64,
+    pub total_new_player_combat_tutorial_count: i64,
+    pub is_retired: bool,
+    pub is_hidden_ability_unlocked: bool,
+    pub stat_strength: i64,
+    pub stat_dexterity: i64,
+    pub stat_vitality: i64,
+    pub stat_special: i64,
+    pub stat_insight: i64,
+    pub stat_tough: i64,
+    pub stat_cool: i64,
+    pub stat_beauty: i64,
+    pub stat_cute: i64,
+    pub stat_clever: i64,
+    pub nature: Option<String>,
+    pub held_item: Option<String>,
+    pub appearance: Option<String>,
+    pub backstory: Option<String>,
+    pub current_hp: Option<i64>,
+    pub current_will: Option<i64>,
+    #[serde(default)]
+    pub tera_charges: Vec<ExportedTeraCharge>,
+    #[serde(default)]
+    pub moves: Vec<String>,
+    #[serde(default)]
+    pub status_effects: Vec<String>,
+    /// Channel IDs of the completed quests. These can only be restored on the same server.
+    #[serde(default)]
+    pub quest_completions: Vec<i64>,
+    /// Wallets are matched by name on import.
+    #[serde(default)]
+    pub wallets: Vec<String>,
+    /// API IDs of all forms the character can switch into, besides their default one.
+    #[serde(default)]
+    pub forms: Vec<i64>,
+}
+
+pub async fn build_character_export(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+) -> Result<CharacterExport, Error> {
+    let record = sqlx::query!("SELECT * FROM character WHERE id = ?", character_id)
+        .fetch_one(database)
+        .await?;
+
+    let tera_charges = tera_charges::get_tera_charges(database, character_id)
+        .await
+        .into_iter()
+        .map(|x| ExportedTeraCharge {
+            tera_type: format!("{:?}", x.tera_type),
+            unlocked: x.unlocked,
+            used: x.used,
+        })
+        .collect();
+
+    let quest_completions = sqlx::query!(
+        "SELECT quest_id FROM quest_completion WHERE character_id = ? ORDER BY quest_id",
+        character_id
+    )
+    .fetch_all(database)
+    .await?
+    .into_iter()
+    .map(|x| x.quest_id)
+    .collect();
+
+    let wallets = sqlx::query!(
+        "SELECT wallet.name FROM wallet_owner \
+         INNER JOIN wallet ON wallet.id = wallet_owner.wallet_id \
+         WHERE wallet_owner.character_id = ? ORDER BY wallet.name",
+        character_id
+    )
+    .fetch_all(database)
+    .await?
+    .into_iter()
+    .map(|x| x.name)
+    .collect();
+
+    Ok(CharacterExport {
+        version: CHARACTER_EXPORT_VERSION,
+        name: record.name,
+        species_api_id: record.species_api_id,
+        species_override_for_stats: record.species_override_for_stats,
+        active_form_api_id: record.active_form_api_id,
+        is_shiny: record.is_shiny,
+        phenotype: record.phenotype,
+        creation_date: record.creation_date.to_string(),
+        experience: record.experience,
+        money: record.money,
+        battle_points: record.battle_points,
+        backpack_upgrade_count: record.backpack_upgrade_count,
+        total_spar_count: record.total_spar_count,
+        weekly_spar_count: record.weekly_spar_count,
+        total_new_player_tour_count: record.total_new_player_tour_count,
+        total_new_player_combat_tutorial_count: record.total_new_player_combat_tutorial_count,
+        is_retired: record.is_retired,
+        is_hidden_ability_unlocked: record.is_hidden_ability_unlocked,
+        stat_strength: record.stat_strength,
+        stat_dexterity: record.stat_dexterity,
+        stat_vitality: record.stat_vitality,
+        stat_special: record.stat_special,
+        stat_insight: record.stat_insight,
+        stat_tough: record.stat_tough,
+        stat_cool: record.stat_cool,
+        stat_beauty: record.stat_beauty,
+        stat_cute: record.stat_cute,
+        stat_clever: record.stat_clever,
+        nature: record.nature,
+        held_item: record.held_item,
+        appearance: record.appearance,
+        backstory: record.backstory,
+        current_hp: record.current_hp,
+        current_will: record.current_will,
+        tera_charges,
+        moves: moves::get_known_moves(database, character_id).await,
+        status_effects: health::get_status_effects(database, character_id).await,
+        quest_completions,
+        wallets,
+        forms: get_registered_forms(database, character_id).await,
+    })
+}
+
+/// Export a character as JSON file, which can be imported on other servers.
+#[poise::command(slash_command, guild_only)]
+pub async fn export_character(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(&ctx, &character).await?;
+
+    let export = build_character_export(&ctx.data().database, character.id).await?;
+    let json = serde_json::to_vec_pretty(&export)?;
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!("Here's the export for {}!", character.name))
+            .attachment(CreateAttachment::bytes(
+                json,
+                format!("{}.json", character.name),
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -3,7 +3,8 @@ use crate::commands::autocompletion::autocomplete_character_name;
 use crate::commands::autocompletion::autocomplete_owned_character_name;
 use crate::commands::characters::{change_character_stat_after_validation, ActionType};
 use crate::commands::{
-    ensure_character_has_money, ensure_user_owns_character, find_character, Context, Error,
+    ensure_character_has_money, ensure_user_owns_character, find_character, reply_with_undo_button,
+    Context, Error,
 };
 use crate::emoji;
 use crate::errors::ValidationError;
@@ -45,13 +46,16 @@ async fn transfer_money_between_characters<'a>(
         )
         .await
         {
-            ctx.say(format!(
-                "***{}** gave {} {} to **{}***!",
-                giver.name,
-                amount,
-                emoji::POKE_COIN,
-                receiver.name
-            ))
+            reply_with_undo_button(
+                ctx,
+                format!(
+                    "***{}** gave {} {} to **{}***!",
+                    giver.name,
+                    amount,
+                    emoji::POKE_COIN,
+                    receiver.name
+                ),
+            )
             .await?;
         } else {
             // TODO: The undo might fail.
@@ -75,7 +79,6 @@ pub async fn give_money(
     #[autocomplete = "autocomplete_character_name"]
     receiver: String,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let giver = find_character(ctx.data(), guild_id, &giver).await?;
     let receiver = find_character(ctx.data(), guild_id, &receiver).await?;
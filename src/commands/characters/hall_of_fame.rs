@@ -0,0 +1,150 @@
+use sqlx::{Pool, Sqlite};
+
+use crate::commands::{send_ephemeral_reply, Context, Error};
+use crate::game_data::{GameData, PokemonApiId};
+use crate::helpers;
+
+struct HallOfFameEntry {
+    name: String,
+    species_api_id: i64,
+    reason: Option<String>,
+    epilogue: Option<String>,
+    level: Option<i64>,
+    completed_quests: Option<i64>,
+    achievements: Option<String>,
+    retirement_timestamp: Option<i64>,
+}
+
+/// List all retired characters and what they've achieved.
+#[poise::command(slash_command, guild_only)]
+pub async fn hall_of_fame(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let entries = load_hall_of_fame(&ctx.data().database, guild_id).await?;
+    if entries.is_empty() {
+        send_ephemeral_reply(&ctx, "Nobody has retired yet!").await?;
+        return Ok(());
+    }
+
+    let mut text = String::from("## 🏛️ Hall of Fame\n");
+    for entry in entries {
+        text.push_str(&build_entry_string(&ctx.data().game, &entry));
+    }
+
+    for message in helpers::split_long_messages(text) {
+        ctx.say(message).await?;
+    }
+
+    Ok(())
+}
+
+/// Returns all currently retired characters, with the stats they had at their latest retirement.
+/// Characters which were retired before these records were kept have no stats.
+async fn load_hall_of_fame(
+    database: &Pool<Sqlite>,
+    guild_id: i64,
+) -> Result<Vec<HallOfFameEntry>, Error> {
+    let entries = sqlx::query_as!(
+        HallOfFameEntry,
+        "SELECT character.name, character.species_api_id, \
+                retirement.reason, retirement.epilogue, \
+                retirement.level as \"level?: i64\", \
+                retirement.completed_quests as \"completed_quests?: i64\", \
+                retirement.achievements as \"achievements?: String\", \
+                retirement.retirement_timestamp as \"retirement_timestamp?: i64\" \
+         FROM character \
+         LEFT JOIN character_retirement retirement \
+            ON retirement.character_id = character.id AND retirement.unretirement_timestamp IS NULL \
+         WHERE character.guild_id = ? AND character.is_retired = true \
+         ORDER BY retirement.retirement_timestamp DESC NULLS LAST, character.name",
+        guild_id
+    )
+    .fetch_all(database)
+    .await?;
+
+    Ok(entries)
+}
+
+fn build_entry_string(game_data: &GameData, entry: &HallOfFameEntry) -> String {
+    let species = match game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(entry.species_api_id as u16))
+    {
+        Some(pokemon) => pokemon.name.clone(),
+        None => String::from("Unknown Pokemon"),
+    };
+
+    let (Some(level), Some(completed_quests), Some(timestamp)) = (
+        entry.level,
+        entry.completed_quests,
+        entry.retirement_timestamp,
+    ) else {
+        return format!(
+            "**{}** ({}) – *retired before records were kept*\n",
+            entry.name, species
+        );
+    };
+
+    let mut result = format!(
+        "**{}** ({}) – Lv. {}, {} quests – retired <t:{}:D>\n",
+        entry.name, species, level, completed_quests, timestamp
+    );
+    if let Some(achievements) = entry.achievements.as_ref().filter(|x| !x.is_empty()) {
+        result.push_str(&format!("🏅 {}\n", achievements));
+    }
+    if let Some(reason) = &entry.reason {
+        result.push_str(&format!("*{}*\n", reason));
+    }
+    if let Some(epilogue) = &entry.epilogue {
+        result.push_str(&format!("> {}\n", epilogue.replace('\n', "\n> ")));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Pool, Sqlite};
+
+    use crate::commands::characters::hall_of_fame::load_hall_of_fame;
+    use crate::{database_helpers, Error};
+
+    #[sqlx::test]
+    async fn hall_of_fame_should_only_show_latest_retirement(
+        db: Pool<Sqlite>,
+    ) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        for (character_id, name) in [(1, "Veteran"), (2, "Legacy"), (3, "Active")] {
+            database_helpers::create_mock::character_in_database(
+                &db,
+                guild_id,
+                user_id,
+                character_id,
+                name,
+                0,
+            )
+            .await;
+        }
+        sqlx::query!("UPDATE character SET is_retired = true WHERE id IN (1, 2)")
+            .execute(&db)
+            .await?;
+        sqlx::query!(
+            "INSERT INTO character_retirement (character_id, reason, level, experience, completed_quests, achievements, retirement_timestamp, unretirement_timestamp) \
+             VALUES (1, 'Tired', 2, 100, 3, '', 10, 20), (1, 'Done', 5, 400, 12, '🎫 Tour Guide', 30, NULL), (3, NULL, 1, 0, 0, '', 5, 6)"
+        )
+        .execute(&db)
+        .await?;
+
+        let entries = load_hall_of_fame(&db, guild_id).await?;
+        assert_eq!(2, entries.len());
+        assert_eq!("Veteran", entries[0].name);
+        assert_eq!(Some(String::from("Done")), entries[0].reason);
+        assert_eq!(Some(12), entries[0].completed_quests);
+        assert_eq!("Legacy", entries[1].name);
+        assert_eq!(None, entries[1].level);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,613 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serenity::all::{Attachment, User};
+use sqlx::{Pool, Sqlite};
+
+use crate::character_stats::GenericCharacterStats;
+use crate::commands::characters::create_character::post_placeholder_stat_message;
+use crate::commands::characters::edit_bio::MAX_BIO_FIELD_LENGTH;
+use crate::commands::characters::export_character::{CharacterExport, CHARACTER_EXPORT_VERSION};
+use crate::commands::characters::{
+    log_action_with_target, validate_user_input, ActionLogTarget, ActionType,
+};
+use crate::commands::inventory::find_item;
+use crate::commands::moves::{calculate_move_limit, find_move, validate_move_is_learnable};
+use crate::commands::{
+    create_emojis, ensure_guild_exists, ensure_user_exists, send_ephemeral_reply, send_error,
+    update_character_post, Context, Error,
+};
+use crate::enums::{Gender, PokemonTypeWithoutShadow};
+use crate::errors::{ParseError, ValidationError};
+use crate::game_data::pokemon::Pokemon;
+use crate::game_data::{GameData, PokemonApiId};
+use crate::guild_settings::GuildSettings;
+use crate::{helpers, tera_charges};
+
+const MAX_FILE_SIZE: u32 = 64 * 1024;
+
+/// Import a character from a file created with /export_character.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn import_character(
+    ctx: Context<'_>,
+    #[description = "Who owns the character?"] player: User,
+    #[description = "The exported JSON file."] file: Attachment,
+) -> Result<(), Error> {
+    if file.size > MAX_FILE_SIZE {
+        return send_error(&ctx, "That file is way too big to be a character export!").await;
+    }
+
+    let content = file.download().await?;
+    let export: CharacterExport = match serde_json::from_slice(&content) {
+        Ok(export) => export,
+        Err(e) => {
+            return Err(Box::new(ParseError::new(format!(
+                "**Unable to read that file.**\n*{}*",
+                e
+            ))))
+        }
+    };
+
+    let user_id = player.id.get() as i64;
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let settings = GuildSettings::load(&ctx.data().database, guild_id).await;
+    let pokemon = validate_character_export(&ctx.data().game, &settings, &export)?;
+
+    let Some(message) = post_placeholder_stat_message(&ctx).await? else {
+        return Ok(());
+    };
+
+    ensure_guild_exists(&ctx, guild_id).await;
+    ensure_user_exists(&ctx, user_id, guild_id).await;
+
+    let result = insert_imported_character(
+        &ctx.data().database,
+        &export,
+        guild_id,
+        user_id,
+        message.id.get() as i64,
+        message.channel_id.get() as i64,
+    )
+    .await;
+
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            log::warn!("Failed to import character {}: {}", export.name, e);
+            send_error(&ctx, "Something went wrong! Does a character with this name already exist for this specific player?").await?;
+            message.delete(ctx).await?;
+            return Ok(());
+        }
+    };
+
+    let gender = Gender::from_phenotype(export.phenotype);
+    create_emojis::create_emojis_for_pokemon(&ctx, pokemon, &gender, export.is_shiny).await;
+
+    let mut reply = format!("{} has been successfully imported!", export.name);
+    let skipped_quests = export.quest_completions.len() - result.restored_quest_completions;
+    if skipped_quests > 0 {
+        reply.push_str(&format!(
+            "\n*{} quest completions belong to quests from another server and were skipped.*",
+            skipped_quests
+        ));
+    }
+    let skipped_wallets = export.wallets.len() - result.restored_wallets;
+    if skipped_wallets > 0 {
+        reply.push_str(&format!(
+            "\n*{} wallets don't exist on this server and were skipped.*",
+            skipped_wallets
+        ));
+    }
+    send_ephemeral_reply(&ctx, &reply).await?;
+
+    update_character_post(&ctx, result.character_id).await;
+    log_action_with_target(
+        &ActionType::Initialization,
+        &ctx,
+        &format!("Imported character {} ({}).", export.name, pokemon.name),
+        ActionLogTarget::character(result.character_id, None),
+    )
+    .await?;
+    ctx.data()
+        .cache
+        .update_character_names(&ctx.data().database)
+        .await;
+
+    Ok(())
+}
+
+/// Makes sure the export contains nothing which couldn't also have been created through regular commands.
+fn validate_character_export<'a>(
+    game_data: &'a Arc<GameData>,
+    settings: &GuildSettings,
+    export: &CharacterExport,
+) -> Result<&'a Pokemon, ValidationError> {
+    if export.version > CHARACTER_EXPORT_VERSION {
+        return Err(ValidationError::new(format!(
+            "**This file was exported with a newer version of the bot (v{}).**\n*Only v{} and below can be imported.*",
+            export.version, CHARACTER_EXPORT_VERSION
+        )));
+    }
+
+    if let Err(e) = validate_user_input(&export.name) {
+        return Err(ValidationError::new(format!(
+            "**Invalid name {}.**\n*{}*",
+            export.name, e
+        )));
+    }
+
+    let Some(pokemon) = u16::try_from(export.species_api_id)
+        .ok()
+        .and_then(|id| game_data.pokemon_by_api_id.get(&PokemonApiId(id)))
+    else {
+        return Err(ValidationError::new(format!(
+            "**Unknown species API ID {}.**",
+            export.species_api_id
+        )));
+    };
+
+    if let Some(species_override) = export.species_override_for_stats {
+        if u16::try_from(species_override)
+            .ok()
+            .and_then(|id| game_data.pokemon_by_api_id.get(&PokemonApiId(id)))
+            .is_none()
+        {
+            return Err(ValidationError::new(format!(
+                "**Unknown species API ID {} used as stat override.**",
+                species_override
+            )));
+        }
+    }
+
+    for form in &export.forms {
+        if *form == export.species_api_id
+            || u16::try_from(*form)
+                .ok()
+                .and_then(|id| game_data.pokemon_by_api_id.get(&PokemonApiId(id)))
+                .is_none()
+        {
+            return Err(ValidationError::new(format!(
+                "**Invalid form API ID {}.**",
+                form
+            )));
+        }
+    }
+    if let Some(active_form_api_id) = export.active_form_api_id {
+        if !export.forms.contains(&active_form_api_id) {
+            return Err(ValidationError::new(format!(
+                "**The active form {} isn't one of the registered forms.**",
+                active_form_api_id
+            )));
+        }
+    }
+
+    let counters = [
+        export.experience,
+        export.money,
+        export.battle_points,
+        export.backpack_upgrade_count,
+        export.total_spar_count,
+        export.weekly_spar_count,
+        export.total_new_player_tour_count,
+        export.total_new_player_combat_tutorial_count,
+    ];
+    if counters.iter().any(|x| *x < 0) {
+        return Err(ValidationError::new(
+            "**Experience, money, battle points, backpack upgrades and session counts can't be negative.**",
+        ));
+    }
+
+    if let Some(nature) = &export.nature {
+        if !game_data.natures.contains_key(&nature.to_lowercase()) {
+            return Err(ValidationError::new(format!(
+                "**Unknown nature {}.**",
+                nature
+            )));
+        }
+    }
+    if let Some(held_item) = &export.held_item {
+        find_item(game_data, held_item)?;
+    }
+    for bio_field in [&export.appearance, &export.backstory]
+        .into_iter()
+        .flatten()
+    {
+        if bio_field.chars().count() > MAX_BIO_FIELD_LENGTH as usize {
+            return Err(ValidationError::new(format!(
+                "**Appearance and backstory can't be longer than {} characters.**",
+                MAX_BIO_FIELD_LENGTH
+            )));
+        }
+    }
+
+    let level = settings.calculate_level_from_experience(export.experience);
+    let rank = settings.calculate_rank(level);
+    let form = helpers::get_active_form(pokemon, game_data, export.active_form_api_id);
+    let pokemon_for_stats = helpers::get_usual_evolution_stage_for_level(
+        level,
+        form,
+        game_data,
+        export.species_override_for_stats,
+    );
+
+    let combat_stats = [
+        (export.stat_strength, &pokemon_for_stats.strength),
+        (export.stat_dexterity, &pokemon_for_stats.dexterity),
+        (export.stat_vitality, &pokemon_for_stats.vitality),
+        (export.stat_special, &pokemon_for_stats.special),
+        (export.stat_insight, &pokemon_for_stats.insight),
+    ];
+    if combat_stats.iter().any(|(x, stat)| *x < stat.min as i64) {
+        return Err(ValidationError::new(format!(
+            "**Combat stats are below the minimum for {}.**",
+            pokemon_for_stats.name
+        )));
+    }
+
+    let social_stats = [
+        export.stat_tough,
+        export.stat_cool,
+        export.stat_beauty,
+        export.stat_cute,
+        export.stat_clever,
+    ];
+    if social_stats.iter().any(|x| *x < 1) {
+        return Err(ValidationError::new(
+            "**Social stats need to be at least 1.**",
+        ));
+    }
+
+    let invested_combat_points = GenericCharacterStats::from_combat(
+        pokemon_for_stats,
+        export.stat_strength,
+        export.stat_dexterity,
+        export.stat_vitality,
+        export.stat_special,
+        export.stat_insight,
+    )
+    .calculate_invested_stat_points();
+    if invested_combat_points > settings.calculate_available_combat_points(level) {
+        return Err(ValidationError::new(format!(
+            "**Too many combat stat points.**\n*{} points are invested, but only {} are available at level {}.*",
+            invested_combat_points,
+            settings.calculate_available_combat_points(level),
+            level
+        )));
+    }
+
+    let invested_social_points = GenericCharacterStats::from_social(
+        export.stat_tough,
+        export.stat_cool,
+        export.stat_beauty,
+        export.stat_cute,
+        export.stat_clever,
+    )
+    .calculate_invested_stat_points();
+    if invested_social_points > settings.calculate_available_social_points(&rank) {
+        return Err(ValidationError::new(format!(
+            "**Too many social stat points.**\n*{} points are invested, but only {} are available at {}.*",
+            invested_social_points,
+            settings.calculate_available_social_points(&rank),
+            rank
+        )));
+    }
+
+    let max_hp = helpers::calculate_max_hp(pokemon_for_stats.base_hp, export.stat_vitality);
+    let max_will = helpers::calculate_max_will(export.stat_insight);
+    if export
+        .current_hp
+        .is_some_and(|x| !(0..=max_hp).contains(&x))
+        || export
+            .current_will
+            .is_some_and(|x| !(0..=max_will).contains(&x))
+    {
+        return Err(ValidationError::new(format!(
+            "**Current HP and Will need to be between 0 and their maximum of {} HP and {} Will.**",
+            max_hp, max_will
+        )));
+    }
+
+    for tera_charge in &export.tera_charges {
+        if PokemonTypeWithoutShadow::from_str(&tera_charge.tera_type).is_err() {
+            return Err(ValidationError::new(format!(
+                "**Unknown terastallization type {}.**",
+                tera_charge.tera_type
+            )));
+        }
+        if tera_charge.unlocked < 0 || tera_charge.used < 0 {
+            return Err(ValidationError::new(format!(
+                "**Terastallization charges for {} can't be negative.**",
+                tera_charge.tera_type
+            )));
+        }
+        if tera_charge.used > tera_charge.unlocked {
+            return Err(ValidationError::new(format!(
+                "**More {} terastallization charges have been used than unlocked.**\n*{} are used, but only {} are unlocked.*",
+                tera_charge.tera_type, tera_charge.used, tera_charge.unlocked
+            )));
+        }
+    }
+
+    for name in &export.moves {
+        let m = find_move(game_data, name)?;
+        validate_move_is_learnable(form, rank, m)?;
+    }
+    let move_limit = calculate_move_limit(export.stat_insight, settings.move_limit_bonus);
+    if export.moves.len() as i64 > move_limit {
+        return Err(ValidationError::new(format!(
+            "**Too many moves.**\n*{} moves are known, but only {} can be learned.*",
+            export.moves.len(),
+            move_limit
+        )));
+    }
+
+    for status_effect in &export.status_effects {
+        if !game_data
+            .status_effects
+            .contains_key(&status_effect.to_lowercase())
+        {
+            return Err(ValidationError::new(format!(
+                "**Unknown status effect {}.**",
+                status_effect
+            )));
+        }
+    }
+
+    Ok(pokemon)
+}
+
+struct ImportResult {
+    character_id: i64,
+    restored_quest_completions: usize,
+    restored_wallets: usize,
+}
+
+/// Creates the character and everything attached to it within a single transaction.
+/// Quests and wallets are only linked if they exist within the target guild.
+async fn insert_imported_character(
+    database: &Pool<Sqlite>,
+    export: &CharacterExport,
+    guild_id: i64,
+    user_id: i64,
+    stat_message_id: i64,
+    stat_channel_id: i64,
+) -> Result<ImportResult, Error> {
+    let mut transaction = database.begin().await?;
+
+    let character_id = sqlx::query!(
+        "INSERT INTO character (user_id, guild_id, name, stat_message_id, stat_channel_id, creation_date, experience, money, \
+            species_api_id, species_override_for_stats, active_form_api_id, is_shiny, phenotype, battle_points, backpack_upgrade_count, \
+            total_spar_count, weekly_spar_count, total_new_player_tour_count, total_new_player_combat_tutorial_count, \
+            is_retired, is_hidden_ability_unlocked, \
+            stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+            stat_edit_strength, stat_edit_dexterity, stat_edit_vitality, stat_edit_special, stat_edit_insight, \
+            stat_tough, stat_cool, stat_beauty, stat_cute, stat_clever, \
+            stat_edit_tough, stat_edit_cool, stat_edit_beauty, stat_edit_cute, stat_edit_clever, \
+            nature, held_item, appearance, backstory, current_hp, current_will) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+         RETURNING id",
+        user_id,
+        guild_id,
+        export.name,
+        stat_message_id,
+        stat_channel_id,
+        export.creation_date,
+        export.experience,
+        export.money,
+        export.species_api_id,
+        export.species_override_for_stats,
+        export.active_form_api_id,
+        export.is_shiny,
+        export.phenotype,
+        export.battle_points,
+        export.backpack_upgrade_count,
+        export.total_spar_count,
+        export.weekly_spar_count,
+        export.total_new_player_tour_count,
+        export.total_new_player_combat_tutorial_count,
+        export.is_retired,
+        export.is_hidden_ability_unlocked,
+        export.stat_strength,
+        export.stat_dexterity,
+        export.stat_vitality,
+        export.stat_special,
+        export.stat_insight,
+        export.stat_strength,
+        export.stat_dexterity,
+        export.stat_vitality,
+        export.stat_special,
+        export.stat_insight,
+        export.stat_tough,
+        export.stat_cool,
+        export.stat_beauty,
+        export.stat_cute,
+        export.stat_clever,
+        export.stat_tough,
+        export.stat_cool,
+        export.stat_beauty,
+        export.stat_cute,
+        export.stat_clever,
+        export.nature,
+        export.held_item,
+        export.appearance,
+        export.backstory,
+        export.current_hp,
+        export.current_will,
+    )
+    .fetch_one(&mut *transaction)
+    .await?
+    .id;
+
+    for tera_charge in &export.tera_charges {
+        let tera_type = PokemonTypeWithoutShadow::from_str(&tera_charge.tera_type)?;
+        tera_charges::set_tera_charges(
+            &mut transaction,
+            character_id,
+            tera_type,
+            tera_charge.unlocked,
+            tera_charge.used,
+        )
+        .await?;
+    }
+
+    for form in &export.forms {
+        sqlx::query!(
+            "INSERT OR IGNORE INTO character_form (character_id, species_api_id) VALUES (?, ?)",
+            character_id,
+            form
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    for m in &export.moves {
+        sqlx::query!(
+            "INSERT INTO character_move (character_id, move_name) VALUES (?, ?)",
+            character_id,
+            m
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    for status_effect in &export.status_effects {
+        sqlx::query!(
+            "INSERT INTO character_status_effect (character_id, status_effect_name) VALUES (?, ?)",
+            character_id,
+            status_effect
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    let mut restored_quest_completions = 0;
+    for quest_id in &export.quest_completions {
+        restored_quest_completions += sqlx::query!(
+            "INSERT INTO quest_completion (quest_id, character_id) \
+             SELECT channel_id, ? FROM quest WHERE channel_id = ? AND guild_id = ?",
+            character_id,
+            quest_id,
+            guild_id
+        )
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected() as usize;
+    }
+
+    let mut restored_wallets = 0;
+    for wallet in &export.wallets {
+        restored_wallets += sqlx::query!(
+            "INSERT INTO wallet_owner (wallet_id, character_id) \
+             SELECT id, ? FROM wallet WHERE name = ? AND guild_id = ?",
+            character_id,
+            wallet,
+            guild_id
+        )
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected() as usize;
+    }
+
+    transaction.commit().await?;
+    Ok(ImportResult {
+        character_id,
+        restored_quest_completions,
+        restored_wallets,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::characters::export_character::build_character_export;
+    use crate::commands::characters::import_character::insert_imported_character;
+    use crate::{database_helpers, Error};
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn imported_characters_should_keep_their_data(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_helpers::create_mock::data(db).await;
+        let origin_guild_id = 100;
+        let target_guild_id = 101;
+        let user_id = 200;
+        let character_id = 300;
+        let wallet_id = 400;
+        let wallet_name = String::from("Team Wallet");
+
+        database_helpers::create_mock::guild(&data.database, origin_guild_id).await;
+        database_helpers::create_mock::guild(&data.database, target_guild_id).await;
+        database_helpers::create_mock::user(&data.database, user_id).await;
+        database_helpers::create_mock::character(
+            &data,
+            origin_guild_id,
+            user_id,
+            character_id,
+            &String::from("Test"),
+        )
+        .await;
+        database_helpers::create_mock::wallet(
+            &data.database,
+            target_guild_id,
+            wallet_id,
+            &wallet_name,
+        )
+        .await;
+
+        sqlx::query!(
+            "UPDATE character SET experience = 250 WHERE id = ?",
+            character_id
+        )
+        .execute(&data.database)
+        .await?;
+        sqlx::query!(
+            "INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used) VALUES (?, 'Fire', 2, 1)",
+            character_id
+        )
+        .execute(&data.database)
+        .await?;
+        sqlx::query!(
+            "INSERT INTO character_move (character_id, move_name) VALUES (?, 'Tackle')",
+            character_id
+        )
+        .execute(&data.database)
+        .await?;
+        sqlx::query!(
+            "INSERT INTO character_form (character_id, species_api_id) VALUES (?, 479)",
+            character_id
+        )
+        .execute(&data.database)
+        .await?;
+        sqlx::query!(
+            "UPDATE character SET active_form_api_id = 479 WHERE id = ?",
+            character_id
+        )
+        .execute(&data.database)
+        .await?;
+
+        let mut export = build_character_export(&data.database, character_id).await?;
+        export.wallets.push(wallet_name);
+        export.quest_completions.push(12345);
+
+        let result =
+            insert_imported_character(&data.database, &export, target_guild_id, user_id, 0, 0)
+                .await?;
+        assert_eq!(0, result.restored_quest_completions);
+        assert_eq!(1, result.restored_wallets);
+
+        let imported = build_character_export(&data.database, result.character_id).await?;
+        assert_eq!("Test", imported.name);
+        assert_eq!(250, imported.experience);
+        assert_eq!(vec![String::from("Tackle")], imported.moves);
+        assert_eq!(1, imported.tera_charges.len());
+        assert_eq!(2, imported.tera_charges[0].unlocked);
+        assert_eq!(1, imported.tera_charges[0].used);
+        assert_eq!(vec![String::from("Team Wallet")], imported.wallets);
+        assert_eq!(vec![479], imported.forms);
+        assert_eq!(Some(479), imported.active_form_api_id);
+
+        Ok(())
+    }
+}
@@ -5,27 +5,43 @@ use std::sync::Arc;
 use poise::Command;
 use regex::Regex;
 use serenity::all::{
-    ButtonStyle, CreateActionRow, CreateAllowedMentions, CreateButton, CreateMessage, GetMessages,
+    ButtonStyle, CacheHttp, CreateActionRow, CreateAllowedMentions, CreateButton, CreateMessage,
+    GetMessages, Message, User,
 };
 use serenity::model::id::ChannelId;
 use sqlx::{Pool, Sqlite};
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
 
 use crate::cache::CharacterCacheItem;
 use crate::character_stats::GenericCharacterStats;
 use crate::commands::{
-    parse_character_names, send_error, update_character_post, BuildUpdatedStatMessageStringResult,
-    Context,
+    companions, health, inventory, moves, parse_character_names, send_error, update_character_post,
+    BuildUpdatedStatMessageStringResult, Context,
 };
 use crate::data::Data;
-use crate::enums::{Gender, MysteryDungeonRank, PokemonTypeWithoutShadow};
+use crate::enums::Gender;
+use crate::errors::ValidationError;
+use crate::game_data::pokemon::Pokemon;
 use crate::game_data::{GameData, PokemonApiId};
-use crate::{emoji, helpers, Error};
+use crate::guild_settings::GuildSettings;
+use crate::ledger::{LedgerEntry, LedgerTarget};
+use crate::tera_charges::TeraCharge;
+use crate::{achievements, emoji, helpers, ledger, tera_charges, Error};
 
+mod character_card;
 mod character_sheet;
 mod create_character;
 mod create_character_post;
+mod edit_bio;
 mod edit_character;
+mod evolve_character;
+mod export_character;
 mod give_money;
+mod hall_of_fame;
+mod import_character;
+mod offer_adoption;
+mod offer_trade;
+mod register_form;
 mod reset_character_stats;
 mod retire_character;
 mod reward_battle_points;
@@ -35,21 +51,36 @@ mod reward_giving_tour;
 mod reward_money;
 mod reward_rp;
 mod reward_terastallization;
+mod set_nature;
+mod submit_character;
+mod switch_form;
 mod unlock_hidden_ability;
 mod unretire_character;
 mod upgrade_backpack;
 mod use_terastallization;
 
-const DEFAULT_BACKPACK_SLOTS: i64 = 6;
+pub const DEFAULT_BACKPACK_SLOTS: i64 = 6;
 
 pub fn get_all_commands() -> Vec<Command<Data, Error>> {
     vec![
         character_sheet::character_sheet(),
+        character_card::character_card(),
         edit_character::edit_character(),
+        evolve_character::evolve_character(),
+        register_form::register_form(),
+        register_form::unregister_form(),
+        switch_form::switch_form(),
+        set_nature::set_nature(),
+        edit_bio::edit_bio(),
         give_money::give_money(),
+        offer_adoption::offer_adoption(),
+        offer_trade::offer_trade(),
         create_character::create_character(),
         create_character::initialize_character(),
+        submit_character::submit_character(),
         create_character_post::create_character_post(),
+        export_character::export_character(),
+        import_character::import_character(),
         reward_experience::reward_experience(),
         reward_money::reward_money(),
         upgrade_backpack::upgrade_backpack(),
@@ -61,6 +92,7 @@ pub fn get_all_commands() -> Vec<Command<Data, Error>> {
         reset_character_stats::reset_character_stats(),
         retire_character::retire_character(),
         unretire_character::unretire_character(),
+        hall_of_fame::hall_of_fame(),
         use_terastallization::use_terastallization(),
         reward_terastallization::reward_terastallization(),
     ]
@@ -120,22 +152,330 @@ async fn count_completed_quests<'a>(database: &Pool<Sqlite>, character_id: i64)
     }
 }
 
-pub fn append_tera_charges(
-    string: &mut String,
-    pokemon_type: PokemonTypeWithoutShadow,
-    unlocked: i64,
-    used: i64,
-) {
-    if unlocked > 0 {
+/// Returns the API IDs of all forms a character can switch into, besides their default one.
+pub async fn get_registered_forms(database: &Pool<Sqlite>, character_id: i64) -> Vec<i64> {
+    match sqlx::query!(
+        "SELECT species_api_id FROM character_form WHERE character_id = ? ORDER BY species_api_id",
+        character_id
+    )
+    .fetch_all(database)
+    .await
+    {
+        Ok(records) => records.into_iter().map(|x| x.species_api_id).collect(),
+        Err(e) => {
+            log::error!(
+                "Was unable to load forms for character {}: {}",
+                character_id,
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Moves a character into another species or form, and rebases their combat stats onto its limits.
+/// Changing the species drops all registered forms, since those belonged to the old one.
+pub async fn change_species_or_form(
+    database: &Pool<Sqlite>,
+    game_data: &Arc<GameData>,
+    character_id: i64,
+    old_species: &Pokemon,
+    new_species: &Pokemon,
+    new_active_form_api_id: Option<i64>,
+) -> Result<(), Error> {
+    let record = sqlx::query!(
+        "SELECT guild_id, experience, species_override_for_stats, active_form_api_id, \
+            stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight \
+         FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_one(database)
+    .await?;
+
+    let settings = GuildSettings::load(database, record.guild_id).await;
+    let level = settings.calculate_level_from_experience(record.experience);
+    let old = helpers::get_usual_evolution_stage_for_level(
+        level,
+        helpers::get_active_form(old_species, game_data, record.active_form_api_id),
+        game_data,
+        record.species_override_for_stats,
+    );
+    let new = helpers::get_usual_evolution_stage_for_level(
+        level,
+        helpers::get_active_form(new_species, game_data, new_active_form_api_id),
+        game_data,
+        record.species_override_for_stats,
+    );
+
+    let strength = helpers::rebase_stat(record.stat_strength, &old.strength, &new.strength);
+    let dexterity = helpers::rebase_stat(record.stat_dexterity, &old.dexterity, &new.dexterity);
+    let vitality = helpers::rebase_stat(record.stat_vitality, &old.vitality, &new.vitality);
+    let special = helpers::rebase_stat(record.stat_special, &old.special, &new.special);
+    let insight = helpers::rebase_stat(record.stat_insight, &old.insight, &new.insight);
+
+    let old_species_api_id = old_species.poke_api_id.0;
+    let new_species_api_id = new_species.poke_api_id.0;
+    let mut transaction = database.begin().await?;
+    let result = sqlx::query!(
+        "UPDATE character SET species_api_id = ?, active_form_api_id = ?, \
+            stat_strength = ?, stat_dexterity = ?, stat_vitality = ?, stat_special = ?, stat_insight = ?, \
+            stat_edit_strength = ?, stat_edit_dexterity = ?, stat_edit_vitality = ?, stat_edit_special = ?, stat_edit_insight = ? \
+         WHERE id = ? AND species_api_id = ? AND active_form_api_id IS ? \
+            AND stat_strength = ? AND stat_dexterity = ? AND stat_vitality = ? AND stat_special = ? AND stat_insight = ?",
+        new_species_api_id,
+        new_active_form_api_id,
+        strength,
+        dexterity,
+        vitality,
+        special,
+        insight,
+        strength,
+        dexterity,
+        vitality,
+        special,
+        insight,
+        character_id,
+        old_species_api_id,
+        record.active_form_api_id,
+        record.stat_strength,
+        record.stat_dexterity,
+        record.stat_vitality,
+        record.stat_special,
+        record.stat_insight
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    if result.rows_affected() != 1 {
+        return Err(Box::new(ValidationError::new(
+            "**Something went wrong.**\n*Did this character change in the meantime?*",
+        )));
+    }
+
+    if old_species_api_id != new_species_api_id {
+        sqlx::query!(
+            "DELETE FROM character_form WHERE character_id = ?",
+            character_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+pub const DEFAULT_STARTING_MONEY: i64 = 500;
+const MAX_MESSAGES_IN_CHARACTER_CHANNEL: usize = 10;
+
+/// Character posts live in their own channels, so busy channels are most likely a mistake.
+pub async fn is_character_channel(cache_http: impl CacheHttp, channel_id: ChannelId) -> bool {
+    match channel_id.messages(cache_http, GetMessages::new()).await {
+        Ok(messages) => messages.len() <= MAX_MESSAGES_IN_CHARACTER_CHANNEL,
+        Err(_) => true,
+    }
+}
+
+/// Posts the message which will later contain the character's stats.
+/// Returns None in case the channel doesn't look like a dedicated character channel.
+pub async fn post_placeholder_stat_message_in_channel(
+    cache_http: impl CacheHttp,
+    channel_id: ChannelId,
+) -> Result<Option<Message>, Error> {
+    let message = channel_id
+        .send_message(
+            &cache_http,
+            CreateMessage::new().content(
+                "[Placeholder. This should get replaced or deleted within a couple seconds.]",
+            ),
+        )
+        .await?;
+
+    if !is_character_channel(&cache_http, channel_id).await {
+        message.delete(&cache_http).await?;
+        return Ok(None);
+    }
+
+    Ok(Some(message))
+}
+
+/// Everything needed to insert a freshly created character into the database.
+pub struct NewCharacter<'a> {
+    pub user_id: i64,
+    pub guild_id: i64,
+    pub name: &'a str,
+    pub pokemon: &'a Pokemon,
+    pub gender: Gender,
+    pub is_shiny: bool,
+    pub experience: i64,
+    pub money: i64,
+    pub stat_channel_id: i64,
+    pub stat_message_id: i64,
+}
+
+/// Inserts a new character with the minimum stats of its usual evolution stage and returns its ID.
+pub async fn insert_character(
+    database: &Pool<Sqlite>,
+    game_data: &Arc<GameData>,
+    character: NewCharacter<'_>,
+) -> Result<i64, sqlx::Error> {
+    let creation_date = chrono::Utc::now().date_naive();
+    let phenotype = character.gender as i64;
+    let settings = GuildSettings::load(database, character.guild_id).await;
+    let level = settings.calculate_level_from_experience(character.experience);
+    let mon =
+        helpers::get_usual_evolution_stage_for_level(level, character.pokemon, game_data, None);
+
+    let record = sqlx::query!(
+        "INSERT INTO character (user_id, guild_id, name, stat_message_id, stat_channel_id, creation_date, experience, money, species_api_id, is_shiny, phenotype,\
+                                stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+        character.user_id,
+        character.guild_id,
+        character.name,
+        character.stat_message_id,
+        character.stat_channel_id,
+        creation_date,
+        character.experience,
+        character.money,
+        character.pokemon.poke_api_id.0,
+        character.is_shiny,
+        phenotype,
+        mon.strength.min,
+        mon.dexterity.min,
+        mon.vitality.min,
+        mon.special.min,
+        mon.insight.min,
+    )
+    .fetch_one(database)
+    .await?;
+
+    Ok(record.id)
+}
+
+/// Moves a character over to another player and records the change within their ownership history.
+/// Unless they should keep their wallet access, the character also gets removed from all wallets they co-own.
+/// Returns the IDs of the wallets the character was removed from.
+pub async fn transfer_character_ownership(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+    previous_user_id: i64,
+    new_user_id: i64,
+    keep_wallets: bool,
+) -> Result<Vec<i64>, Error> {
+    let mut transaction = database.begin().await?;
+
+    let result = sqlx::query!(
+        "UPDATE character SET user_id = ? WHERE id = ? AND user_id = ?",
+        new_user_id,
+        character_id,
+        previous_user_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    if result.rows_affected() != 1 {
+        return Err(Box::new(ValidationError::new(
+            "**Something went wrong.**\n*Did this character change owners in the meantime?*",
+        )));
+    }
+
+    let removed_wallets = if keep_wallets {
+        Vec::new()
+    } else {
+        sqlx::query!(
+            "DELETE FROM wallet_owner WHERE character_id = ? RETURNING wallet_id",
+            character_id
+        )
+        .fetch_all(&mut *transaction)
+        .await?
+        .into_iter()
+        .map(|x| x.wallet_id)
+        .collect()
+    };
+
+    let timestamp = chrono::Utc::now().timestamp();
+    sqlx::query!(
+        "INSERT INTO character_ownership_change (character_id, previous_user_id, new_user_id, timestamp) VALUES (?, ?, ?, ?)",
+        character_id,
+        previous_user_id,
+        new_user_id,
+        timestamp
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+    Ok(removed_wallets)
+}
+
+async fn get_previous_owners(database: &Pool<Sqlite>, character_id: i64) -> Vec<i64> {
+    match sqlx::query!(
+        "SELECT previous_user_id FROM character_ownership_change WHERE character_id = ? ORDER BY timestamp",
+        character_id
+    )
+    .fetch_all(database)
+    .await
+    {
+        Ok(records) => {
+            let mut result: Vec<i64> = Vec::new();
+            for record in records {
+                if !result.contains(&record.previous_user_id) {
+                    result.push(record.previous_user_id);
+                }
+            }
+            result
+        }
+        Err(e) => {
+            log::error!(
+                "Was unable to load previous owners for character {}: {}",
+                character_id,
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
+pub fn append_tera_charges(string: &mut String, charge: &TeraCharge) {
+    if charge.unlocked > 0 {
         string.push_str(&format!(
             "- `{}/{}` {}\n",
-            unlocked - used,
-            unlocked,
-            pokemon_type
+            charge.remaining(),
+            charge.unlocked,
+            charge.tera_type
         ));
     }
 }
 
+/// Discord rejects messages longer than this many characters.
+const MAX_CHARACTER_POST_LENGTH: usize = 2000;
+const TRUNCATION_SUFFIX: &str = "…\n";
+
+/// The bio is the only part of a character post with a lot of free text, so it gets cut short first
+/// whenever the post would grow too long. Should that not suffice, the end of the post gets cut off.
+fn assemble_character_post(mut message: String, bio: &str, statistics: &str) -> String {
+    let suffix_length = TRUNCATION_SUFFIX.chars().count();
+    let bio_budget = MAX_CHARACTER_POST_LENGTH
+        .saturating_sub(message.chars().count() + statistics.chars().count());
+    if bio.chars().count() <= bio_budget {
+        message.push_str(bio);
+    } else if bio_budget > suffix_length {
+        message.extend(bio.chars().take(bio_budget - suffix_length));
+        message.push_str(TRUNCATION_SUFFIX);
+    }
+    message.push_str(statistics);
+
+    if message.chars().count() > MAX_CHARACTER_POST_LENGTH {
+        message = message
+            .chars()
+            .take(MAX_CHARACTER_POST_LENGTH - suffix_length)
+            .collect();
+        message.push_str(TRUNCATION_SUFFIX);
+    }
+
+    message
+}
+
 pub async fn build_character_string(
     database: &Pool<Sqlite>,
     game_data: &Arc<GameData>,
@@ -153,9 +493,10 @@ pub async fn build_character_string(
     let completed_quest_count = count_completed_quests(database, character_id).await;
     match entry {
         Ok(record) => {
-            let level = helpers::calculate_level_from_experience(record.experience);
-            let experience = helpers::calculate_current_experience(record.experience);
-            let rank = MysteryDungeonRank::from_level(level as u8);
+            let settings = GuildSettings::load(database, record.guild_id).await;
+            let level = settings.calculate_level_from_experience(record.experience);
+            let experience = settings.calculate_current_experience(record.experience);
+            let rank = settings.calculate_rank(level);
             let pokemon = game_data
                 .pokemon_by_api_id
                 .get(&PokemonApiId(
@@ -175,6 +516,12 @@ pub async fn build_character_string(
             )
             .await
             .unwrap_or(format!("[{}]", pokemon.name));
+            let form = helpers::get_active_form(pokemon, game_data, record.active_form_api_id);
+            let active_form = if form.poke_api_id != pokemon.poke_api_id {
+                format!(" | Form: {}", form.name)
+            } else {
+                String::new()
+            };
             let species_override_for_stats =
                 if let Some(species_override_for_stats) = record.species_override_for_stats {
                     let species_override_for_stats = game_data
@@ -190,19 +537,19 @@ pub async fn build_character_string(
                     String::new()
                 };
 
-            let type_emojis = if let Some(type2) = pokemon.type2 {
+            let type_emojis = if let Some(type2) = form.type2 {
                 format!(
                     "{}/{}",
-                    emoji::type_to_emoji(&pokemon.type1),
+                    emoji::type_to_emoji(&form.type1),
                     emoji::type_to_emoji(&type2)
                 )
             } else {
-                emoji::type_to_emoji(&pokemon.type1).to_string()
+                emoji::type_to_emoji(&form.type1).to_string()
             };
 
             let pokemon_evolution_form_for_stats = helpers::get_usual_evolution_stage_for_level(
                 level,
-                pokemon,
+                form,
                 game_data,
                 record.species_override_for_stats,
             );
@@ -215,6 +562,21 @@ pub async fn build_character_string(
                 record.stat_insight,
             );
 
+            let health = health::CharacterHealth::new(
+                helpers::calculate_max_hp(
+                    pokemon_evolution_form_for_stats.base_hp,
+                    record.stat_vitality,
+                ),
+                record.current_hp,
+                helpers::calculate_max_will(record.stat_insight),
+                record.current_will,
+            );
+            let mut health = health.build_string();
+            let status_effects = health::get_status_effects(database, character_id).await;
+            if !status_effects.is_empty() {
+                health.push_str(&format!("\n**Status**: {}", status_effects.join(", ")));
+            }
+
             let social_stats = GenericCharacterStats::from_social(
                 record.stat_tough,
                 record.stat_cool,
@@ -224,7 +586,7 @@ pub async fn build_character_string(
             );
 
             let ability_list =
-                pokemon.build_simple_ability_list(record.is_hidden_ability_unlocked, false);
+                form.build_simple_ability_list(record.is_hidden_ability_unlocked, false);
 
             let retired_or_not = if record.is_retired { "[RETIRED]" } else { "" };
 
@@ -235,63 +597,121 @@ pub async fn build_character_string(
             };
 
             let mut tera_charges = String::new();
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Bug, record.tera_unlocked_bug, record.tera_used_bug);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Dark, record.tera_unlocked_dark, record.tera_used_dark);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Dragon, record.tera_unlocked_dragon, record.tera_used_dragon);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Electric, record.tera_unlocked_electric, record.tera_used_electric);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Fairy, record.tera_unlocked_fairy, record.tera_used_fairy);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Fire, record.tera_unlocked_fire, record.tera_used_fire);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Fighting, record.tera_unlocked_fighting, record.tera_used_fighting);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Flying, record.tera_unlocked_flying, record.tera_used_flying);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Ghost, record.tera_unlocked_ghost, record.tera_used_ghost);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Grass, record.tera_unlocked_grass, record.tera_used_grass);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Ground, record.tera_unlocked_ground, record.tera_used_ground);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Ice, record.tera_unlocked_ice, record.tera_used_ice);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Normal, record.tera_unlocked_normal, record.tera_used_normal);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Poison, record.tera_unlocked_poison, record.tera_used_poison);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Psychic, record.tera_unlocked_psychic, record.tera_used_psychic);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Rock, record.tera_unlocked_rock, record.tera_used_rock);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Steel, record.tera_unlocked_steel, record.tera_used_steel);
-            #[rustfmt::skip] append_tera_charges(&mut tera_charges, PokemonTypeWithoutShadow::Water, record.tera_unlocked_water, record.tera_used_water);
+            for charge in tera_charges::get_tera_charges(database, character_id).await {
+                append_tera_charges(&mut tera_charges, &charge);
+            }
 
             if !tera_charges.is_empty() {
                 tera_charges.insert_str(0, "### Terastallization Charges\n");
             }
 
-            let mut message = format!(
+            let companions = companions::get_companions(database, character_id)
+                .await
+                .unwrap_or_default();
+            let mut companion_list =
+                companions::build_companion_list(game_data, &settings, &companions);
+            if !companion_list.is_empty() {
+                companion_list.insert_str(0, "### Companions\n");
+            }
+
+            let mut details = Vec::new();
+            if let Some(nature) = &record.nature {
+                details.push(format!("**Nature**: {}", nature));
+            }
+            if let Some(held_item) = &record.held_item {
+                details.push(format!("**Held Item**: {}", held_item));
+            }
+            let registered_forms = get_registered_forms(database, character_id).await;
+            if !registered_forms.is_empty() {
+                let forms =
+                    std::iter::once(pokemon)
+                        .chain(registered_forms.iter().filter_map(|x| {
+                            game_data.pokemon_by_api_id.get(&PokemonApiId(*x as u16))
+                        }))
+                        .map(|x| x.name.clone())
+                        .collect::<Vec<String>>();
+                details.push(format!("**Forms**: {}", forms.join(", ")));
+            }
+            let details = if details.is_empty() {
+                String::new()
+            } else {
+                format!("{}\n", details.join(" | "))
+            };
+
+            let mut bio = String::new();
+            if let Some(appearance) = &record.appearance {
+                bio.push_str(&format!("**Appearance**: {}\n", appearance));
+            }
+            if let Some(backstory) = &record.backstory {
+                bio.push_str(&format!("**Backstory**: {}\n", backstory));
+            }
+            if !bio.is_empty() {
+                bio.insert_str(0, "### Bio\n");
+            }
+
+            let known_moves = moves::get_known_moves(database, character_id).await;
+            let mut move_list = moves::build_move_list(game_data, form, &known_moves);
+            if !move_list.is_empty() {
+                let move_limit =
+                    moves::calculate_move_limit(record.stat_insight, settings.move_limit_bonus);
+                move_list.insert_str(
+                    0,
+                    &format!("### Moves ({}/{})\n", known_moves.len(), move_limit),
+                );
+            }
+
+            let inventory = inventory::get_inventory(database, character_id).await;
+            let used_backpack_slots = inventory::count_used_slots(game_data, &inventory);
+            let mut inventory_list = inventory::build_inventory_list(&inventory);
+            if !inventory_list.is_empty() {
+                inventory_list.insert_str(0, "### Inventory\n");
+            }
+
+            let message = format!(
                 "\
 ## {} {} {} {}
-**Level {}** `({} / 100)`
-{} {} {}
-### Stats {}{}
+**Level {}** `({} / {})`
+{}{} {} {}
+{}
+### Stats {}{}{}
 ```
 {}
 {}
 ```
 ### Abilities 
-{}{}### Statistics
-{} Backpack Slots: {}\n\n",
+{}{}{}{}{}",
                 rank.emoji_string(),
                 record.name,
                 emoji,
                 retired_or_not,
                 level,
                 experience,
+                settings.experience_per_level,
+                details,
                 record.money,
                 emoji::POKE_COIN,
                 battle_point,
+                health,
                 type_emojis,
+                active_form,
                 species_override_for_stats,
                 combat_stats.build_string(),
                 social_stats.build_string(),
                 ability_list,
+                move_list,
                 tera_charges,
-                emoji::BACKPACK,
-                record.backpack_upgrade_count + DEFAULT_BACKPACK_SLOTS,
+                companion_list,
+                inventory_list,
             );
 
+            let mut statistics = format!(
+                "### Statistics\n{} Backpack Slots: {}/{}\n\n",
+                emoji::BACKPACK,
+                used_backpack_slots,
+                inventory::calculate_backpack_slots(record.backpack_upgrade_count),
+            );
             if completed_quest_count > 0 {
-                message.push_str(&format!(
+                statistics.push_str(&format!(
                     "{} Completed Quests: {}\n",
                     emoji::TROPHY,
                     completed_quest_count
@@ -299,7 +719,7 @@ pub async fn build_character_string(
             }
 
             if record.total_spar_count > 0 {
-                message.push_str(&format!(
+                statistics.push_str(&format!(
                     "{} Total RP Sessions: {}\n",
                     emoji::FENCING,
                     record.total_spar_count
@@ -307,7 +727,7 @@ pub async fn build_character_string(
             }
 
             if record.total_new_player_tour_count > 0 {
-                message.push_str(&format!(
+                statistics.push_str(&format!(
                     "{} Given tours: {}\n",
                     emoji::TICKET,
                     record.total_new_player_tour_count
@@ -315,16 +735,42 @@ pub async fn build_character_string(
             }
 
             if record.total_new_player_combat_tutorial_count > 0 {
-                message.push_str(&format!(
+                statistics.push_str(&format!(
                     "{} Given combat tutorials: {}\n",
                     emoji::CROSSED_SWORDS,
                     record.total_new_player_combat_tutorial_count
                 ));
             }
 
-            let remaining_combat_points = helpers::calculate_available_combat_points(level)
+            let badges =
+                achievements::get_earned_badges(database, &game_data.achievements, character_id)
+                    .await;
+            if !badges.is_empty() {
+                statistics.push_str(&format!(
+                    "{} Badges: {}\n",
+                    emoji::SPORTS_MEDAL,
+                    badges.join(", ")
+                ));
+            }
+
+            let previous_owners = get_previous_owners(database, character_id).await;
+            if !previous_owners.is_empty() {
+                statistics.push_str(&format!(
+                    "{} Previous Owners: {}\n",
+                    emoji::BUSTS_IN_SILHOUETTE,
+                    previous_owners
+                        .iter()
+                        .map(|x| format!("<@{}>", x))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ));
+            }
+
+            let message = assemble_character_post(message, &bio, &statistics);
+
+            let remaining_combat_points = settings.calculate_available_combat_points(level)
                 - combat_stats.calculate_invested_stat_points();
-            let remaining_social_points = helpers::calculate_available_social_points(&rank) as i64
+            let remaining_social_points = settings.calculate_available_social_points(&rank)
                 - social_stats.calculate_invested_stat_points();
 
             let mut components = Vec::new();
@@ -372,7 +818,8 @@ pub async fn build_character_string(
     }
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, IntoStaticStr, EnumString, EnumIter)]
+#[strum(ascii_case_insensitive)]
 pub enum ActionType {
     Initialization,
     Reward,
@@ -396,6 +843,25 @@ pub enum ActionType {
     TerastallizationUnlock,
     StoreGMExperience,
     UseGMExperience,
+    ItemReward,
+    ItemDrop,
+    ItemUse,
+    ItemPurchase,
+    ItemSale,
+    CharacterEvolution,
+    MoveLearned,
+    MoveForgotten,
+    HeldItemChange,
+    CharacterAdoption,
+    SettingsChange,
+    TeamChange,
+    AchievementUnlocked,
+    CharacterSubmission,
+    CompanionChange,
+    RecurringPayout,
+    Auction,
+    HealthChange,
+    StatusEffectChange,
     DoNotLog,
 }
 
@@ -424,15 +890,79 @@ impl fmt::Display for ActionType {
             ActionType::TerastallizationUnlock => "💎 [Terastallization Unlock]",
             ActionType::StoreGMExperience => "🏦⬅️ [GM Experience]",
             ActionType::UseGMExperience => "🏦➡️ [GM Experience]",
+            ActionType::ItemReward => "🎁 [Item]",
+            ActionType::ItemDrop => "🗑️ [Item]",
+            ActionType::ItemUse => "🧪 [Item]",
+            ActionType::ItemPurchase => "🛒 [Shop]",
+            ActionType::ItemSale => "💱 [Shop]",
+            ActionType::CharacterEvolution => "🧬 [Evolution]",
+            ActionType::MoveLearned => "📖 [Move]",
+            ActionType::MoveForgotten => "💨 [Move]",
+            ActionType::HeldItemChange => "✋ [Item]",
+            ActionType::CharacterAdoption => "🤝 [Adoption]",
+            ActionType::SettingsChange => "⚙️ [Settings]",
+            ActionType::TeamChange => "🏕️ [Team]",
+            ActionType::AchievementUnlocked => "🏅 [Achievement]",
+            ActionType::CharacterSubmission => "📝 [Submission]",
+            ActionType::CompanionChange => "🐾 [Companion]",
+            ActionType::RecurringPayout => "🔁 [Payout]",
+            ActionType::Auction => "🔨 [Auction]",
+            ActionType::HealthChange => "🩹 [Health]",
+            ActionType::StatusEffectChange => "🌀 [Status]",
             ActionType::DoNotLog => "",
         })
     }
 }
 
+/// Which character or wallet an action affected, used to make the action history searchable.
+#[derive(Default)]
+pub struct ActionLogTarget {
+    pub character_id: Option<i64>,
+    pub wallet_id: Option<i64>,
+    pub amount: Option<i64>,
+}
+
+impl ActionLogTarget {
+    pub fn character(character_id: i64, amount: Option<i64>) -> Self {
+        ActionLogTarget {
+            character_id: Some(character_id),
+            wallet_id: None,
+            amount,
+        }
+    }
+
+    pub fn wallet(wallet_id: i64, amount: Option<i64>) -> Self {
+        ActionLogTarget {
+            character_id: None,
+            wallet_id: Some(wallet_id),
+            amount,
+        }
+    }
+}
+
+pub struct ActionLogEntry<'a> {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub actor: &'a User,
+    pub origin: String,
+    pub action_type: &'a ActionType,
+    pub message: &'a str,
+    pub target: ActionLogTarget,
+}
+
 pub async fn log_action<'a>(
     action_type: &ActionType,
     ctx: &Context<'a>,
     message: impl Into<String>,
+) -> Result<(), Error> {
+    log_action_with_target(action_type, ctx, message, ActionLogTarget::default()).await
+}
+
+pub async fn log_action_with_target<'a>(
+    action_type: &ActionType,
+    ctx: &Context<'a>,
+    message: impl Into<String>,
+    target: ActionLogTarget,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id();
     if guild_id.is_none() {
@@ -440,13 +970,6 @@ pub async fn log_action<'a>(
     }
 
     let guild_id = guild_id.expect("should only be called in guild_only").get() as i64;
-    let record = sqlx::query!(
-        "SELECT action_log_channel_id FROM guild WHERE id = ?",
-        guild_id
-    )
-    .fetch_one(&ctx.data().database)
-    .await;
-
     let origin = match ctx
         .channel_id()
         .messages(ctx, GetMessages::new().limit(1))
@@ -459,19 +982,51 @@ pub async fn log_action<'a>(
         Err(_) => String::new(),
     };
 
+    send_action_log(
+        ctx,
+        &ctx.data().database,
+        ActionLogEntry {
+            guild_id,
+            channel_id: ctx.channel_id().get() as i64,
+            actor: ctx.author(),
+            origin,
+            action_type,
+            message: &message.into(),
+            target,
+        },
+    )
+    .await
+}
+
+/// Persists the action and posts it to the guild's action log channel.
+/// Use [log_action] when a poise context is available.
+pub async fn send_action_log(
+    cache_http: impl CacheHttp,
+    database: &Pool<Sqlite>,
+    entry: ActionLogEntry<'_>,
+) -> Result<(), Error> {
+    persist_action_log(database, &entry).await;
+
+    let record = sqlx::query!(
+        "SELECT action_log_channel_id FROM guild WHERE id = ?",
+        entry.guild_id
+    )
+    .fetch_one(database)
+    .await;
+
     if let Ok(record) = record {
         if let Some(action_log_channel_id) = record.action_log_channel_id {
             let channel_id = ChannelId::from(action_log_channel_id as u64);
             channel_id
                 .send_message(
-                    ctx,
+                    cache_http,
                     CreateMessage::new()
                         .content(std::format!(
                             "{} {} (triggered by {}{})",
-                            action_type,
-                            message.into(),
-                            ctx.author(),
-                            origin
+                            entry.action_type,
+                            entry.message,
+                            entry.actor,
+                            entry.origin
                         ))
                         .allowed_mentions(CreateAllowedMentions::new().empty_users()),
                 )
@@ -482,6 +1037,30 @@ pub async fn log_action<'a>(
     Ok(())
 }
 
+async fn persist_action_log(database: &Pool<Sqlite>, entry: &ActionLogEntry<'_>) {
+    let actor_id = entry.actor.id.get() as i64;
+    let action_type: &'static str = entry.action_type.into();
+    let timestamp = chrono::Utc::now().timestamp();
+    if let Err(e) = sqlx::query!(
+        "INSERT INTO action_log (guild_id, actor_id, character_id, wallet_id, action_type, amount, message, channel_id, timestamp) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        entry.guild_id,
+        actor_id,
+        entry.target.character_id,
+        entry.target.wallet_id,
+        action_type,
+        entry.target.amount,
+        entry.message,
+        entry.channel_id,
+        timestamp
+    )
+    .execute(database)
+    .await
+    {
+        log::error!("Was unable to persist action log entry: {}", e);
+    }
+}
+
 #[derive(sqlx::FromRow)]
 pub struct EntityWithNameAndNumericValue {
     pub id: i64,
@@ -489,6 +1068,18 @@ pub struct EntityWithNameAndNumericValue {
     pub value: i64,
 }
 
+pub fn get_stat_display_name(database_column: &str) -> String {
+    if let Some(tera_type) = tera_charges::parse_ledger_stat(database_column) {
+        return tera_type.to_string();
+    }
+
+    match database_column {
+        "money" => String::from(emoji::POKE_COIN),
+        "battle_points" => String::from(emoji::BATTLE_POINT),
+        _ => String::from(database_column),
+    }
+}
+
 pub async fn change_character_stat<'a>(
     ctx: &Context<'a>,
     database_column: &str,
@@ -544,41 +1135,31 @@ pub async fn change_character_stat_after_validation<'a>(
     match record {
         Ok(record) => {
             let new_value = record.value + amount;
+            let mut transaction = ctx.data().database.begin().await?;
             let result = sqlx::query(
                 format!("UPDATE character SET {} = ? WHERE id = ? AND {} = ?", database_column, database_column).as_str())
                 .bind(new_value)
                 .bind(record.id)
                 .bind(record.value)
-                .execute(&ctx.data().database).await;
+                .execute(&mut *transaction).await;
 
             if result.is_err() || result.unwrap().rows_affected() != 1 {
                 return send_stale_data_error(ctx).await;
             }
 
+            ledger::record(&mut transaction, LedgerEntry {
+                guild_id: character.guild_id as i64,
+                interaction_id: ctx.id() as i64,
+                actor_id: ctx.author().id.get() as i64,
+                target: LedgerTarget::Character(record.id),
+                stat: database_column,
+                amount,
+                action_type,
+            }).await?;
+            transaction.commit().await?;
+
             update_character_post(ctx, record.id).await;
-            let action = match database_column {
-                "money" => String::from(emoji::POKE_COIN),
-                "battle_points" => String::from(emoji::BATTLE_POINT),
-                "tera_unlocked_normal" => PokemonTypeWithoutShadow::Normal.to_string(),
-                "tera_unlocked_fighting" => PokemonTypeWithoutShadow::Fighting.to_string(),
-                "tera_unlocked_flying" => PokemonTypeWithoutShadow::Flying.to_string(),
-                "tera_unlocked_poison" => PokemonTypeWithoutShadow::Poison.to_string(),
-                "tera_unlocked_ground" => PokemonTypeWithoutShadow::Ground.to_string(),
-                "tera_unlocked_rock" => PokemonTypeWithoutShadow::Rock.to_string(),
-                "tera_unlocked_bug" => PokemonTypeWithoutShadow::Bug.to_string(),
-                "tera_unlocked_ghost" => PokemonTypeWithoutShadow::Ghost.to_string(),
-                "tera_unlocked_steel" => PokemonTypeWithoutShadow::Steel.to_string(),
-                "tera_unlocked_fire" => PokemonTypeWithoutShadow::Fire.to_string(),
-                "tera_unlocked_water" => PokemonTypeWithoutShadow::Water.to_string(),
-                "tera_unlocked_grass" => PokemonTypeWithoutShadow::Grass.to_string(),
-                "tera_unlocked_electric" => PokemonTypeWithoutShadow::Electric.to_string(),
-                "tera_unlocked_psychic" => PokemonTypeWithoutShadow::Psychic.to_string(),
-                "tera_unlocked_ice" => PokemonTypeWithoutShadow::Ice.to_string(),
-                "tera_unlocked_dragon" => PokemonTypeWithoutShadow::Dragon.to_string(),
-                "tera_unlocked_dark" => PokemonTypeWithoutShadow::Dark.to_string(),
-                "tera_unlocked_fairy" => PokemonTypeWithoutShadow::Fairy.to_string(),
-                _ => String::from(database_column)
-            };
+            let action = get_stat_display_name(database_column);
             let added_or_removed: &str;
             let to_or_from: &str;
             if amount > 0 {
@@ -586,11 +1167,12 @@ pub async fn change_character_stat_after_validation<'a>(
                 to_or_from = "to";
 
                 if database_column == "experience" {
-                    let old_level = helpers::calculate_level_from_experience(record.value);
-                    let new_level = helpers::calculate_level_from_experience(record.value + amount);
+                    let settings = GuildSettings::load(&ctx.data().database, character.guild_id as i64).await;
+                    let old_level = settings.calculate_level_from_experience(record.value);
+                    let new_level = settings.calculate_level_from_experience(record.value + amount);
                     if new_level > old_level {
-                        let old_rank = MysteryDungeonRank::from_level(old_level as u8);
-                        let new_rank = MysteryDungeonRank::from_level(new_level as u8);
+                        let old_rank = settings.calculate_rank(old_level);
+                        let new_rank = settings.calculate_rank(new_level);
 
                         let rank_notification = if new_rank > old_rank {
                             format!(" They are now {}!", new_rank)
@@ -600,6 +1182,12 @@ pub async fn change_character_stat_after_validation<'a>(
 
                         let _ = ctx.say(format!("### {} Level Up! {}\n**{}** just reached level {}!{}", emoji::PARTY_POPPER, emoji::PARTYING_FACE, record.name, new_level, rank_notification)).await;
                     }
+
+                    if action_type == &ActionType::Reward {
+                        award_companion_experience(ctx, &settings, record.id, amount).await;
+                    }
+                } else if database_column == "battle_points" {
+                    award_achievements(ctx, record.id, &record.name).await;
                 }
             } else {
                 added_or_removed = "Removed";
@@ -607,7 +1195,7 @@ pub async fn change_character_stat_after_validation<'a>(
             }
 
             if action_type != &ActionType::DoNotLog {
-                log_action(action_type, ctx, format!("{} {} {} {} {}", added_or_removed, amount.abs(), action, to_or_from, record.name).as_str()).await
+                log_action_with_target(action_type, ctx, format!("{} {} {} {} {}", added_or_removed, amount.abs(), action, to_or_from, record.name).as_str(), ActionLogTarget::character(record.id, Some(amount))).await
             } else {
                 Ok(())
             }
@@ -618,6 +1206,94 @@ pub async fn change_character_stat_after_validation<'a>(
     }
 }
 
+/// Companions level up alongside their partner, but only through experience rewards.
+/// Removing experience, undoing a reward or editing a character won't touch their companions,
+/// since the stat points rolled on level ups can't be taken back.
+pub async fn award_companion_experience<'a>(
+    ctx: &Context<'a>,
+    settings: &GuildSettings,
+    character_id: i64,
+    amount: i64,
+) {
+    let level_ups = match companions::grant_experience(
+        &ctx.data().database,
+        &ctx.data().game.pokemon_by_api_id,
+        settings,
+        character_id,
+        amount,
+    )
+    .await
+    {
+        Ok(level_ups) => level_ups,
+        Err(e) => {
+            log::error!(
+                "Was unable to grant experience to the companions of character {}: {}",
+                character_id,
+                e
+            );
+            return;
+        }
+    };
+
+    if level_ups.is_empty() {
+        return;
+    }
+
+    for level_up in level_ups {
+        let _ = ctx
+            .say(format!("### {} Level Up!\n{}", emoji::PAW_PRINTS, level_up))
+            .await;
+    }
+
+    update_character_post(ctx, character_id).await;
+}
+
+/// Should be called whenever one of the counters used by achievements has increased.
+pub async fn award_achievements<'a>(ctx: &Context<'a>, character_id: i64, character_name: &str) {
+    let awarded = match achievements::award_new_achievements(
+        &ctx.data().database,
+        &ctx.data().game.achievements,
+        character_id,
+    )
+    .await
+    {
+        Ok(awarded) => awarded,
+        Err(e) => {
+            log::error!(
+                "Was unable to award achievements for character {}: {}",
+                character_id,
+                e
+            );
+            return;
+        }
+    };
+
+    if awarded.is_empty() {
+        return;
+    }
+
+    for achievement in &awarded {
+        let _ = ctx
+            .say(format!(
+                "### {} Achievement Unlocked!\n**{}** earned **{}**!\n*{}*",
+                emoji::SPORTS_MEDAL,
+                character_name,
+                achievement.badge(),
+                achievement.description
+            ))
+            .await;
+        let _ = log_action_with_target(
+            &ActionType::AchievementUnlocked,
+            ctx,
+            &format!("{} earned {}.", character_name, achievement.name),
+            ActionLogTarget::character(character_id, None),
+        )
+        .await;
+    }
+
+    update_character_post(ctx, character_id).await;
+}
+
 pub fn validate_user_input<'a>(text: &str) -> Result<(), &'a str> {
     if text.len() > 30 {
         return Err("Query string too long!");
@@ -639,3 +1315,40 @@ pub fn build_character_list(characters: &[CharacterCacheItem]) -> String {
         .collect::<Vec<&str>>()
         .join(", ")
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::characters::edit_bio::MAX_BIO_FIELD_LENGTH;
+    use crate::commands::characters::{assemble_character_post, MAX_CHARACTER_POST_LENGTH};
+
+    fn build_bio(appearance: &str, backstory: &str) -> String {
+        format!(
+            "### Bio\n**Appearance**: {}\n**Backstory**: {}\n",
+            appearance, backstory
+        )
+    }
+
+    #[test]
+    fn character_posts_should_fit_into_a_single_message() {
+        let name = "N".repeat(30);
+        let appearance = "é".repeat(MAX_BIO_FIELD_LENGTH as usize);
+        let backstory = "🐾".repeat(MAX_BIO_FIELD_LENGTH as usize);
+        let bio = build_bio(&appearance, &backstory);
+        let statistics = "### Statistics\n🎒 Backpack Slots: 6/6\n\n";
+
+        let short_head = format!("## {}\n", name);
+        let post = assemble_character_post(short_head.clone(), &bio, statistics);
+        assert_eq!(format!("{}{}{}", short_head, bio, statistics), post);
+
+        let long_head = format!("## {}\n{}", name, "- Some Move\n".repeat(120));
+        let post = assemble_character_post(long_head.clone(), &bio, statistics);
+        assert!(post.chars().count() <= MAX_CHARACTER_POST_LENGTH);
+        assert!(post.starts_with(&long_head));
+        assert!(post.ends_with(statistics));
+        assert!(post.contains("### Bio\n**Appearance**: é"));
+
+        let oversized_head = "- Some Item\n".repeat(200);
+        let post = assemble_character_post(oversized_head, &bio, statistics);
+        assert!(post.chars().count() <= MAX_CHARACTER_POST_LENGTH);
+    }
+}
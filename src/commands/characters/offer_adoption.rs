@@ -0,0 +1,280 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use poise::{CreateReply, ReplyHandle};
+use serenity::all::{ButtonStyle, CreateActionRow, CreateInteractionResponse, User, UserId};
+use serenity::prelude::Mentionable;
+
+use crate::commands::autocompletion::autocomplete_owned_character_name;
+use crate::commands::characters::{
+    log_action_with_target, transfer_character_ownership, ActionLogTarget, ActionType,
+};
+use crate::commands::wallets::update_wallet_post;
+use crate::commands::{
+    ensure_user_exists, ensure_user_owns_character, find_character, is_user_admin_or_gm,
+    update_character_post, Context, Error,
+};
+use crate::errors::ValidationError;
+use crate::helpers;
+
+const CONFIRM: &str = "offer_adoption_confirm";
+const ABORT: &str = "offer_adoption_abort";
+const OWNER_TIMEOUT: Duration = Duration::from_secs(60);
+const NEW_OWNER_TIMEOUT: Duration = Duration::from_secs(60 * 10);
+const GM_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Who needs to press the buttons for a step of the adoption to continue.
+enum Approver {
+    User(UserId),
+    GameMaster,
+}
+
+/// Hand one of your characters over to another player. They'll have to accept first.
+#[poise::command(slash_command, guild_only)]
+pub async fn offer_adoption(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Who should adopt them?"] new_owner: User,
+    #[description = "Should they stay an owner of their wallets? Defaults to false."]
+    keep_wallets: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+
+    if new_owner.id == ctx.author().id || new_owner.bot {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} can't adopt {}.**",
+            new_owner.name, character.name
+        ))));
+    }
+
+    let keep_wallets = keep_wallets.unwrap_or(false);
+    let guild_id = guild_id as i64;
+    let requires_gm_approval = sqlx::query!(
+        "SELECT adoption_requires_gm_approval FROM guild WHERE id = ?",
+        guild_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?
+    .map(|x| x.adoption_requires_gm_approval)
+    .unwrap_or(false);
+
+    let wallets = sqlx::query!(
+        "SELECT wallet.name FROM wallet_owner \
+         INNER JOIN wallet ON wallet.id = wallet_owner.wallet_id \
+         WHERE wallet_owner.character_id = ? ORDER BY wallet.name",
+        character.id
+    )
+    .fetch_all(&ctx.data().database)
+    .await?;
+
+    let mut original_message = format!(
+        "**{} wants {} to adopt {}!**",
+        ctx.author().mention(),
+        new_owner.mention(),
+        character.name
+    );
+    if !wallets.is_empty() {
+        original_message.push_str(&format!(
+            "\n*{} co-owns these wallets: {}. {}*",
+            character.name,
+            wallets
+                .iter()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
+            if keep_wallets {
+                "They will keep their access."
+            } else {
+                "They will be removed from them."
+            }
+        ));
+    }
+    if requires_gm_approval {
+        original_message.push_str("\n*A GM will need to approve this adoption.*");
+    }
+
+    let reply = ctx
+        .send(CreateReply::default().content(&original_message))
+        .await?;
+
+    let steps = [
+        (
+            Approver::User(ctx.author().id),
+            format!("{}, please confirm.", ctx.author().mention()),
+            OWNER_TIMEOUT,
+        ),
+        (
+            Approver::User(new_owner.id),
+            format!(
+                "{}, do you want to adopt {}?",
+                new_owner.mention(),
+                character.name
+            ),
+            NEW_OWNER_TIMEOUT,
+        ),
+        (
+            Approver::GameMaster,
+            String::from("Waiting for a GM to approve..."),
+            GM_TIMEOUT,
+        ),
+    ];
+
+    for (approver, prompt, timeout) in steps {
+        if matches!(approver, Approver::GameMaster) && !requires_gm_approval {
+            continue;
+        }
+
+        let content = format!("{}\n\n{}", original_message, prompt);
+        match await_decision(&ctx, &reply, &content, approver, timeout).await? {
+            Some(true) => {}
+            Some(false) => {
+                return edit_message_and_delete_buttons(
+                    ctx,
+                    &reply,
+                    format!("{}\n\n**Adoption was cancelled.**", original_message),
+                )
+                .await;
+            }
+            None => {
+                return edit_message_and_delete_buttons(
+                    ctx,
+                    &reply,
+                    format!(
+                        "{}\n\n**Request timed out. Use the command again if needed.**",
+                        original_message
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
+    let new_user_id = new_owner.id.get() as i64;
+    ensure_user_exists(&ctx, new_user_id, guild_id).await;
+    let removed_wallets = match transfer_character_ownership(
+        &ctx.data().database,
+        character.id,
+        character.user_id as i64,
+        new_user_id,
+        keep_wallets,
+    )
+    .await
+    {
+        Ok(removed_wallets) => removed_wallets,
+        Err(e) => {
+            return edit_message_and_delete_buttons(
+                ctx,
+                &reply,
+                format!("{}\n\n{}", original_message, e),
+            )
+            .await;
+        }
+    };
+
+    ctx.data()
+        .cache
+        .update_character_names(&ctx.data().database)
+        .await;
+    update_character_post(&ctx, character.id).await;
+    for wallet_id in removed_wallets {
+        update_wallet_post(&ctx, wallet_id).await;
+    }
+
+    log_action_with_target(
+        &ActionType::CharacterAdoption,
+        &ctx,
+        &format!(
+            "{} has been adopted by {}!",
+            character.name,
+            new_owner.mention()
+        ),
+        ActionLogTarget::character(character.id, None),
+    )
+    .await?;
+
+    edit_message_and_delete_buttons(
+        ctx,
+        &reply,
+        format!(
+            "{}\n\n**{} has been adopted by {}!**",
+            original_message,
+            character.name,
+            new_owner.mention()
+        ),
+    )
+    .await
+}
+
+/// Shows confirmation buttons and waits for the approver to press one of them.
+/// Returns None if nobody reacted in time.
+async fn await_decision<'a>(
+    ctx: &Context<'a>,
+    reply: &ReplyHandle<'a>,
+    content: &str,
+    approver: Approver,
+    timeout: Duration,
+) -> Result<Option<bool>, Error> {
+    reply
+        .edit(
+            *ctx,
+            CreateReply::default()
+                .content(content)
+                .components(vec![CreateActionRow::Buttons(vec![
+                    helpers::create_styled_button("Confirm", CONFIRM, false, ButtonStyle::Success),
+                    helpers::create_styled_button("Cancel", ABORT, false, ButtonStyle::Danger),
+                ])]),
+        )
+        .await?;
+    let message = reply.message().await?;
+
+    let interaction = match approver {
+        Approver::User(user_id) => {
+            message
+                .await_component_interaction(ctx)
+                .author_id(user_id)
+                .timeout(timeout)
+                .await
+        }
+        Approver::GameMaster => {
+            message
+                .await_component_interaction(ctx)
+                .filter(|x| {
+                    x.member
+                        .as_ref()
+                        .is_some_and(|member| is_user_admin_or_gm(Cow::Borrowed(member)))
+                })
+                .timeout(timeout)
+                .await
+        }
+    };
+
+    match interaction {
+        Some(interaction) => {
+            interaction
+                .create_response(ctx, CreateInteractionResponse::Acknowledge)
+                .await?;
+            Ok(Some(interaction.data.custom_id == CONFIRM))
+        }
+        None => Ok(None),
+    }
+}
+
+async fn edit_message_and_delete_buttons<'a>(
+    ctx: Context<'a>,
+    reply: &ReplyHandle<'a>,
+    message: String,
+) -> Result<(), Error> {
+    reply
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content(message)
+                .components(Vec::new()),
+        )
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use std::time::{Duration, Instant};
+
+use poise::{CreateReply, ReplyHandle};
+use serenity::all::{ButtonStyle, CreateActionRow, CreateInteractionResponse};
+
+use crate::cache::CharacterCacheItem;
+use crate::commands::autocompletion::{
+    autocomplete_character_name, autocomplete_item, autocomplete_owned_character_name,
+};
+use crate::commands::characters::{
+    award_achievements, log_action_with_target, ActionLogTarget, ActionType,
+};
+use crate::commands::inventory::find_item;
+use crate::commands::{
+    ensure_character_has_money, ensure_user_owns_character, find_character, update_character_post,
+    Context, Error,
+};
+use crate::data::Data;
+use crate::errors::ValidationError;
+use crate::trades::TradeSide;
+use crate::{helpers, trades};
+
+const CONFIRM: &str = "offer_trade_confirm";
+const ABORT: &str = "offer_trade_abort";
+const TRADE_TIMEOUT: Duration = Duration::from_secs(60 * 15);
+
+/// Offer a trade to another character. Both sides need to confirm before anything changes hands.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command, guild_only)]
+pub async fn offer_trade(
+    ctx: Context<'_>,
+    #[description = "Which of your characters is trading?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "With whom?"]
+    #[autocomplete = "autocomplete_character_name"]
+    partner: String,
+    #[min = 1_u32]
+    #[description = "How much money are you offering?"]
+    offered_money: Option<u32>,
+    #[min = 1_u32]
+    #[description = "How many battle points are you offering?"]
+    offered_battle_points: Option<u32>,
+    #[description = "Which item are you offering?"]
+    #[autocomplete = "autocomplete_item"]
+    offered_item: Option<String>,
+    #[min = 1_u32]
+    #[description = "How many of that item? Defaults to 1."]
+    offered_item_amount: Option<u32>,
+    #[min = 1_u32]
+    #[description = "How much money do you want in return?"]
+    requested_money: Option<u32>,
+    #[min = 1_u32]
+    #[description = "How many battle points do you want in return?"]
+    requested_battle_points: Option<u32>,
+    #[description = "Which item do you want in return?"]
+    #[autocomplete = "autocomplete_item"]
+    requested_item: Option<String>,
+    #[min = 1_u32]
+    #[description = "How many of that item? Defaults to 1."]
+    requested_item_amount: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+    let partner = find_character(ctx.data(), guild_id, &partner).await?;
+    if character.id == partner.id {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} can't trade with themselves.**",
+            character.name
+        ))));
+    }
+
+    let offer = build_trade_side(
+        ctx.data(),
+        &character,
+        offered_money,
+        offered_battle_points,
+        offered_item,
+        offered_item_amount,
+    )?;
+    let request = build_trade_side(
+        ctx.data(),
+        &partner,
+        requested_money,
+        requested_battle_points,
+        requested_item,
+        requested_item_amount,
+    )?;
+    if offer.is_empty() && request.is_empty() {
+        return Err(Box::new(ValidationError::new(
+            "**Please specify what should be traded.**",
+        )));
+    }
+    ensure_character_has_money(ctx.data(), &character, offer.money, "trade").await?;
+
+    let original_message = format!(
+        "## 🤝 Trade Offer\n**{}** gives: {}\n**{}** gives: {}",
+        character.name,
+        offer.describe(),
+        partner.name,
+        request.describe(),
+    );
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .content(build_status(
+                    &original_message,
+                    &[&character, &partner],
+                    &[],
+                ))
+                .components(create_buttons()),
+        )
+        .await?;
+
+    if !await_confirmations(&ctx, &reply, &original_message, &[&character, &partner]).await? {
+        return Ok(());
+    }
+
+    if let Err(e) = trades::execute(&ctx.data().database, &ctx.data().game, &offer, &request).await
+    {
+        return edit_message_and_delete_buttons(
+            ctx,
+            &reply,
+            format!(
+                "{}\n\n{}\n**The trade was cancelled.**",
+                original_message, e
+            ),
+        )
+        .await;
+    }
+
+    update_character_post(&ctx, character.id).await;
+    update_character_post(&ctx, partner.id).await;
+    let log_message = format!(
+        "{} traded {} to {} for {}.",
+        character.name,
+        offer.describe(),
+        partner.name,
+        request.describe()
+    );
+    log_action_with_target(
+        &ActionType::TradeOutgoing,
+        &ctx,
+        &log_message,
+        ActionLogTarget::character(character.id, Some(request.money - offer.money)),
+    )
+    .await?;
+    log_action_with_target(
+        &ActionType::TradeIncoming,
+        &ctx,
+        &log_message,
+        ActionLogTarget::character(partner.id, Some(offer.money - request.money)),
+    )
+    .await?;
+
+    // Battle points count towards achievements, so receiving them might unlock some.
+    if offer.battle_points > 0 {
+        award_achievements(&ctx, partner.id, &partner.name).await;
+    }
+    if request.battle_points > 0 {
+        award_achievements(&ctx, character.id, &character.name).await;
+    }
+
+    edit_message_and_delete_buttons(
+        ctx,
+        &reply,
+        format!("{}\n\n**The trade is complete!**", original_message),
+    )
+    .await
+}
+
+fn build_trade_side<'a>(
+    data: &'a Data,
+    character: &'a CharacterCacheItem,
+    money: Option<u32>,
+    battle_points: Option<u32>,
+    item: Option<String>,
+    item_amount: Option<u32>,
+) -> Result<TradeSide<'a>, Error> {
+    let item = match (item, item_amount) {
+        (Some(item), amount) => Some((find_item(&data.game, &item)?, amount.unwrap_or(1) as i64)),
+        (None, Some(_)) => {
+            return Err(Box::new(ValidationError::new(
+                "**Please specify which item should be traded.**",
+            )))
+        }
+        (None, None) => None,
+    };
+
+    Ok(TradeSide {
+        character,
+        money: money.unwrap_or(0) as i64,
+        battle_points: battle_points.unwrap_or(0) as i64,
+        item,
+    })
+}
+
+fn create_buttons() -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        helpers::create_styled_button("Confirm", CONFIRM, false, ButtonStyle::Success),
+        helpers::create_styled_button("Cancel", ABORT, false, ButtonStyle::Danger),
+    ])]
+}
+
+fn build_status(
+    original_message: &str,
+    characters: &[&CharacterCacheItem],
+    confirmed_user_ids: &[u64],
+) -> String {
+    let mut result = format!("{}\n", original_message);
+    for character in characters {
+        if confirmed_user_ids.contains(&character.user_id) {
+            result.push_str(&format!("\n✅ {} has confirmed.", character.name));
+        } else {
+            result.push_str(&format!(
+                "\n⏳ Waiting for <@{}> to confirm for {}...",
+                character.user_id, character.name
+            ));
+        }
+    }
+
+    result
+}
+
+/// Waits until the owners of all characters have pressed confirm.
+/// Returns false if anyone cancelled or the trade timed out.
+async fn await_confirmations<'a>(
+    ctx: &Context<'a>,
+    reply: &ReplyHandle<'a>,
+    original_message: &str,
+    characters: &[&CharacterCacheItem],
+) -> Result<bool, Error> {
+    let message = reply.message().await?;
+    let deadline = Instant::now() + TRADE_TIMEOUT;
+    let approvers: Vec<u64> = characters.iter().map(|x| x.user_id).collect();
+    let mut confirmed_user_ids = Vec::new();
+
+    while approvers.iter().any(|x| !confirmed_user_ids.contains(x)) {
+        let approvers_in_filter = approvers.clone();
+        let interaction = message
+            .await_component_interaction(ctx)
+            .filter(move |x| approvers_in_filter.contains(&x.user.id.get()))
+            .timeout(deadline.saturating_duration_since(Instant::now()))
+            .await;
+
+        let Some(interaction) = interaction else {
+            edit_message_and_delete_buttons(
+                *ctx,
+                reply,
+                format!(
+                    "{}\n\n**Trade offer timed out. Use the command again if needed.**",
+                    original_message
+                ),
+            )
+            .await?;
+            return Ok(false);
+        };
+
+        interaction
+            .create_response(ctx, CreateInteractionResponse::Acknowledge)
+            .await?;
+        if interaction.data.custom_id != CONFIRM {
+            edit_message_and_delete_buttons(
+                *ctx,
+                reply,
+                format!(
+                    "{}\n\n**The trade was cancelled by <@{}>.**",
+                    original_message, interaction.user.id
+                ),
+            )
+            .await?;
+            return Ok(false);
+        }
+
+        let user_id = interaction.user.id.get();
+        if !confirmed_user_ids.contains(&user_id) {
+            confirmed_user_ids.push(user_id);
+        }
+        reply
+            .edit(
+                *ctx,
+                CreateReply::default()
+                    .content(build_status(
+                        original_message,
+                        characters,
+                        &confirmed_user_ids,
+                    ))
+                    .components(create_buttons()),
+            )
+            .await?;
+    }
+
+    Ok(true)
+}
+
+async fn edit_message_and_delete_buttons<'a>(
+    ctx: Context<'a>,
+    reply: &ReplyHandle<'a>,
+    message: String,
+) -> Result<(), Error> {
+    reply
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content(message)
+                .components(Vec::new()),
+        )
+        .await?;
+    Ok(())
+}
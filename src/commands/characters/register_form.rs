@@ -0,0 +1,140 @@
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_pokemon};
+use crate::commands::characters::{
+    change_species_or_form, log_action_with_target, ActionLogTarget, ActionType,
+};
+use crate::commands::{
+    find_character, pokemon_from_autocomplete_string, update_character_post, Context, Error,
+};
+use crate::errors::ValidationError;
+use crate::game_data::PokemonApiId;
+
+/// Allow a character to switch into another form, e.g. a Rotom appliance.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn register_form(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which form?"]
+    #[autocomplete = "autocomplete_pokemon"]
+    form: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let form = pokemon_from_autocomplete_string(&ctx, &form)?;
+
+    let record = sqlx::query!(
+        "SELECT species_api_id FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+    if record.species_api_id == form.poke_api_id.0 as i64 {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} already is a {}. That's their default form!",
+            character.name, form.name
+        ))));
+    }
+
+    let result = sqlx::query!(
+        "INSERT OR IGNORE INTO character_form (character_id, species_api_id) VALUES (?, ?)",
+        character.id,
+        form.poke_api_id.0
+    )
+    .execute(&ctx.data().database)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} can already switch into {}.",
+            character.name, form.name
+        ))));
+    }
+
+    let message = format!("{} can now switch into {}.", character.name, form.name);
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::CharacterEdit,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
+
+/// Remove a form a character was able to switch into.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn unregister_form(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which form?"]
+    #[autocomplete = "autocomplete_pokemon"]
+    form: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let form = pokemon_from_autocomplete_string(&ctx, &form)?;
+    let form_api_id = form.poke_api_id.0 as i64;
+
+    let result = sqlx::query!(
+        "DELETE FROM character_form WHERE character_id = ? AND species_api_id = ?",
+        character.id,
+        form_api_id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} has no {} form registered.",
+            character.name, form.name
+        ))));
+    }
+
+    let record = sqlx::query!(
+        "SELECT species_api_id, active_form_api_id FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+    if record.active_form_api_id == Some(form_api_id) {
+        let species = ctx
+            .data()
+            .game
+            .pokemon_by_api_id
+            .get(&PokemonApiId(record.species_api_id as u16))
+            .expect("DB IDs should always be mappable.");
+        change_species_or_form(
+            &ctx.data().database,
+            &ctx.data().game,
+            character.id,
+            species,
+            species,
+            None,
+        )
+        .await?;
+    }
+
+    let message = format!(
+        "{} can no longer switch into {}.",
+        character.name, form.name
+    );
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::CharacterEdit,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
@@ -1,8 +1,10 @@
 use crate::cache::CharacterCacheItem;
+use crate::character_stats::GenericCharacterStats;
 use crate::commands::autocompletion::autocomplete_character_name;
-use crate::commands::characters::{log_action, ActionType};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
 use crate::commands::{find_character, update_character_post, Context, Error};
 use crate::game_data::PokemonApiId;
+use crate::guild_settings::GuildSettings;
 use crate::helpers;
 
 /// Resets a characters stats to its default values.
@@ -21,31 +23,49 @@ pub async fn reset_character_stats(
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let character = find_character(ctx.data(), guild_id, &character).await?;
 
-    reset_db_stats(&ctx, &character).await?;
+    let refund = reset_db_stats(&ctx, &character).await?;
     update_character_post(&ctx, character.id).await;
 
+    let limit_break_substring = if refund.limit_breaks > 0 {
+        format!(", including {} limit breaks", refund.limit_breaks)
+    } else {
+        String::new()
+    };
     let _ = ctx
-        .reply(&format!("{}'s stats have been reset.", character.name))
+        .reply(&format!(
+            "{}'s stats have been reset. Refunded {} combat and {} social stat points{}.",
+            character.name, refund.combat_points, refund.social_points, limit_break_substring
+        ))
         .await;
-    let _ = log_action(
+    let _ = log_action_with_target(
         &ActionType::CharacterStatReset,
         &ctx,
         &format!("Reset {}'s stats", character.name),
+        ActionLogTarget::character(character.id, None),
     )
     .await;
     Ok(())
 }
 
+pub struct StatResetRefund {
+    pub combat_points: i64,
+    pub social_points: i64,
+    pub limit_breaks: i64,
+}
+
 pub async fn reset_db_stats(
     ctx: &Context<'_>,
     character: &CharacterCacheItem,
-) -> Result<(), Error> {
+) -> Result<StatResetRefund, Error> {
     let record = sqlx::query!(
-        "SELECT name, species_api_id, experience, species_override_for_stats FROM character WHERE id = ?",
+        "SELECT name, species_api_id, experience, species_override_for_stats, active_form_api_id, \
+                stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+                stat_tough, stat_cool, stat_beauty, stat_cute, stat_clever \
+         FROM character WHERE id = ?",
         character.id
     )
-        .fetch_one(&ctx.data().database)
-        .await?;
+    .fetch_one(&ctx.data().database)
+    .await?;
 
     let species_id = PokemonApiId(record.species_api_id as u16);
     let used_poke_species = ctx
@@ -55,15 +75,42 @@ pub async fn reset_db_stats(
         .get(&species_id)
         .expect("DB IDs should always be mappable.");
 
-    let level = helpers::calculate_level_from_experience(record.experience);
+    let settings = GuildSettings::load(&ctx.data().database, character.guild_id as i64).await;
+    let level = settings.calculate_level_from_experience(record.experience);
 
     let pokemon_evolution_form_for_stats = helpers::get_usual_evolution_stage_for_level(
         level,
-        used_poke_species,
+        helpers::get_active_form(
+            used_poke_species,
+            &ctx.data().game,
+            record.active_form_api_id,
+        ),
         &ctx.data().game,
         record.species_override_for_stats,
     );
 
+    // Limit breaks are included in the invested points, so resetting everything to the minimum refunds them as well.
+    let combat_stats = GenericCharacterStats::from_combat(
+        pokemon_evolution_form_for_stats,
+        record.stat_strength,
+        record.stat_dexterity,
+        record.stat_vitality,
+        record.stat_special,
+        record.stat_insight,
+    );
+    let social_stats = GenericCharacterStats::from_social(
+        record.stat_tough,
+        record.stat_cool,
+        record.stat_beauty,
+        record.stat_cute,
+        record.stat_clever,
+    );
+    let refund = StatResetRefund {
+        combat_points: combat_stats.calculate_invested_stat_points(),
+        social_points: social_stats.calculate_invested_stat_points(),
+        limit_breaks: combat_stats.count_limit_breaks() + social_stats.count_limit_breaks(),
+    };
+
     let _ = sqlx::query!(
         "UPDATE character SET 
 stat_strength = ?,
@@ -101,5 +148,5 @@ stat_edit_clever = 1
     )
     .execute(&ctx.data().database)
     .await;
-    Ok(())
+    Ok(refund)
 }
@@ -1,7 +1,11 @@
+use crate::achievements;
 use crate::commands::autocompletion::autocomplete_character_name;
-use crate::commands::characters::{log_action, ActionType};
+use crate::commands::characters::{
+    count_completed_quests, log_action_with_target, ActionLogTarget, ActionType,
+};
 use crate::commands::{find_character, update_character_post, Context, Error};
-use crate::errors::CommandInvocationError;
+use crate::errors::{CommandInvocationError, ValidationError};
+use crate::guild_settings::GuildSettings;
 use serenity::all::{ChannelId, EditThread};
 use tokio::join;
 
@@ -17,21 +21,35 @@ pub async fn retire_character(
     #[description = "Which character?"]
     #[autocomplete = "autocomplete_character_name"]
     character: String,
+    #[description = "Optional. Why is the character retiring?"]
+    #[max_length = 100]
+    reason: Option<String>,
+    #[description = "Optional. A few closing words for the hall of fame."]
+    #[max_length = 1000]
+    epilogue: Option<String>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let character = find_character(ctx.data(), guild_id, &character).await?;
+    if character.is_retired {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} is already retired.",
+            character.name
+        ))));
+    }
 
-    match sqlx::query!(
-        "UPDATE character SET is_retired = true WHERE id = ?",
-        character.id
-    )
-    .execute(&ctx.data().database)
-    .await
-    {
+    match retire(&ctx, character.id, reason.as_deref(), epilogue.as_deref()).await {
         Ok(_) => {
-            let message = format!("{} has been retired.", character.name);
+            let message = match &reason {
+                Some(reason) => format!("{} has been retired. Reason: {}", character.name, reason),
+                None => format!("{} has been retired.", character.name),
+            };
             let a = ctx.reply(&message);
-            let b = log_action(&ActionType::CharacterRetirement, &ctx, &message);
+            let b = log_action_with_target(
+                &ActionType::CharacterRetirement,
+                &ctx,
+                &message,
+                ActionLogTarget::character(character.id, None),
+            );
             let c = ctx.data().cache.reset(&ctx.data().database);
             let d = update_character_post(&ctx, character.id);
 
@@ -53,6 +71,56 @@ pub async fn retire_character(
     Ok(())
 }
 
+/// Retires the character and records a snapshot of their final stats for the hall of fame.
+async fn retire(
+    ctx: &Context<'_>,
+    character_id: i64,
+    reason: Option<&str>,
+    epilogue: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let database = &ctx.data().database;
+    let record = sqlx::query!(
+        "SELECT guild_id, experience FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_one(database)
+    .await?;
+
+    let settings = GuildSettings::load(database, record.guild_id).await;
+    let level = settings.calculate_level_from_experience(record.experience);
+    let completed_quests = count_completed_quests(database, character_id).await;
+    let achievements =
+        achievements::get_earned_badges(database, &ctx.data().game.achievements, character_id)
+            .await
+            .join(", ");
+    let timestamp = chrono::Utc::now().timestamp();
+    let reason = reason.map(str::trim).filter(|x| !x.is_empty());
+    let epilogue = epilogue.map(str::trim).filter(|x| !x.is_empty());
+
+    let mut transaction = database.begin().await?;
+    sqlx::query!(
+        "UPDATE character SET is_retired = true WHERE id = ?",
+        character_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query!(
+        "INSERT INTO character_retirement (character_id, reason, epilogue, level, experience, completed_quests, achievements, retirement_timestamp) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        character_id,
+        reason,
+        epilogue,
+        level,
+        record.experience,
+        completed_quests,
+        achievements,
+        timestamp
+    )
+    .execute(&mut *transaction)
+    .await?;
+    transaction.commit().await
+}
+
 async fn archive_character_post(ctx: &Context<'_>, character_id: i64) {
     if let Ok(result) = sqlx::query!(
         "SELECT stat_channel_id FROM character WHERE id = ?",
@@ -1,6 +1,6 @@
 use crate::commands::autocompletion::autocomplete_character_name;
 use crate::commands::characters::{build_character_list, change_character_stat, ActionType};
-use crate::commands::{parse_variadic_args, send_error, Context, Error};
+use crate::commands::{parse_variadic_args, reply_with_undo_button, send_error, Context, Error};
 
 /// Reward players with experience points.
 #[allow(clippy::too_many_arguments)]
@@ -24,7 +24,6 @@ pub async fn reward_experience(
     #[autocomplete = "autocomplete_character_name"] character8: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character9: Option<String>,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let args = parse_variadic_args(
         character1, character2, character3, character4, character5, character6, character7,
         character8, character9,
@@ -33,11 +32,14 @@ pub async fn reward_experience(
     match change_character_stat(&ctx, "experience", &args, amount as i64, ActionType::Reward).await
     {
         Ok(characters) => {
-            ctx.say(format!(
-                "{} received {} experience points!",
-                build_character_list(&characters),
-                amount
-            ))
+            reply_with_undo_button(
+                &ctx,
+                format!(
+                    "{} received {} experience points!",
+                    build_character_list(&characters),
+                    amount
+                ),
+            )
             .await?;
         }
         Err(err) => {
@@ -1,6 +1,8 @@
 use crate::cache::CharacterCacheItem;
 use crate::commands::autocompletion::autocomplete_character_name;
-use crate::commands::characters::{change_character_stat_after_validation, log_action, ActionType};
+use crate::commands::characters::{
+    award_achievements, change_character_stat_after_validation, log_action, ActionType,
+};
 use crate::commands::{parse_user_input_to_character, send_error, Context, Error};
 
 /// Reward players for giving a combat tutorial to new players.
@@ -111,6 +113,7 @@ async fn track_combat_tutorial_for_character<'a>(
         &format!("{} gave a combat tutorial!", character.name),
     )
     .await;
+    award_achievements(ctx, character.id, &character.name).await;
 
     let _ = change_character_stat_after_validation(
         ctx,
@@ -1,6 +1,6 @@
 use crate::commands::autocompletion::autocomplete_character_name;
 use crate::commands::characters::{build_character_list, change_character_stat, ActionType};
-use crate::commands::{parse_variadic_args, send_error, Context, Error};
+use crate::commands::{parse_variadic_args, reply_with_undo_button, send_error, Context, Error};
 use crate::emoji;
 
 /// Reward players with cash.
@@ -25,7 +25,6 @@ pub async fn reward_money(
     #[autocomplete = "autocomplete_character_name"] character8: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character9: Option<String>,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let args = parse_variadic_args(
         character1, character2, character3, character4, character5, character6, character7,
         character8, character9,
@@ -33,12 +32,15 @@ pub async fn reward_money(
 
     match change_character_stat(&ctx, "money", &args, amount as i64, ActionType::Reward).await {
         Ok(characters) => {
-            ctx.say(format!(
-                "{} received {} {}!",
-                build_character_list(&characters),
-                amount,
-                emoji::POKE_COIN
-            ))
+            reply_with_undo_button(
+                &ctx,
+                format!(
+                    "{} received {} {}!",
+                    build_character_list(&characters),
+                    amount,
+                    emoji::POKE_COIN
+                ),
+            )
             .await?;
         }
         Err(err) => {
@@ -1,7 +1,8 @@
 use crate::cache::CharacterCacheItem;
 use crate::commands::autocompletion::autocomplete_character_name;
 use crate::commands::characters::{
-    build_character_list, change_character_stat_after_validation, log_action, ActionType,
+    award_achievements, build_character_list, change_character_stat_after_validation, log_action,
+    ActionType,
 };
 use crate::commands::{
     parse_character_names, parse_variadic_args, send_error, update_character_post, Context, Error,
@@ -159,6 +160,7 @@ async fn track_spar_for_character<'a>(
         &format!("Tracked an rp session for {}!", character.name),
     )
     .await;
+    award_achievements(ctx, character.id, &character.name).await;
 
     if new_weekly_spar_count <= guild_settings.weekly_spar_limit {
         result.participants_who_gained_exp.push(character.clone());
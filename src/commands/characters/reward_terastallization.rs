@@ -1,9 +1,16 @@
 use crate::cache::CharacterCacheItem;
 use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_pokemon_type};
-use crate::commands::characters::{build_character_list, change_character_stat, ActionType};
-use crate::commands::{parse_variadic_args, Context, Error};
+use crate::commands::characters::{
+    build_character_list, log_action_with_target, ActionLogTarget, ActionType,
+};
+use crate::commands::{
+    parse_character_names, parse_variadic_args, reply_with_undo_button, update_character_post,
+    Context, Error,
+};
 use crate::enums::PokemonTypeWithoutShadow;
 use crate::errors::CommandInvocationError;
+use crate::ledger::{LedgerEntry, LedgerTarget};
+use crate::{ledger, tera_charges};
 
 /// Reward players with a Terastallization charge.
 #[allow(clippy::too_many_arguments)]
@@ -29,62 +36,66 @@ pub async fn reward_terastallization(
     #[autocomplete = "autocomplete_character_name"] character8: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character9: Option<String>,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let args = parse_variadic_args(
         character1, character2, character3, character4, character5, character6, character7,
         character8, character9,
     );
 
-    match handle_unlock(&ctx, tera_type, &args).await {
-        Ok(characters) => {
-            ctx.say(format!(
-                "Unlocked a {} Terastallization Charge for {}!",
-                tera_type,
-                build_character_list(&characters)
-            ))
-            .await?;
-            Ok(())
-        }
-        Err(err) => Err(Box::new(err)),
-    }
+    let characters = handle_unlock(&ctx, tera_type, &args).await?;
+    reply_with_undo_button(
+        &ctx,
+        format!(
+            "Unlocked a {} Terastallization Charge for {}!",
+            tera_type,
+            build_character_list(&characters)
+        ),
+    )
+    .await?;
+    Ok(())
 }
 
 async fn handle_unlock<'a>(
     ctx: &Context<'a>,
     tera_type: PokemonTypeWithoutShadow,
     names: &Vec<String>,
-) -> Result<Vec<CharacterCacheItem>, CommandInvocationError> {
-    let tera_unlocked_column = match tera_type {
-        PokemonTypeWithoutShadow::Normal => "tera_unlocked_normal",
-        PokemonTypeWithoutShadow::Fighting => "tera_unlocked_fighting",
-        PokemonTypeWithoutShadow::Flying => "tera_unlocked_flying",
-        PokemonTypeWithoutShadow::Poison => "tera_unlocked_poison",
-        PokemonTypeWithoutShadow::Ground => "tera_unlocked_ground",
-        PokemonTypeWithoutShadow::Rock => "tera_unlocked_rock",
-        PokemonTypeWithoutShadow::Bug => "tera_unlocked_bug",
-        PokemonTypeWithoutShadow::Ghost => "tera_unlocked_ghost",
-        PokemonTypeWithoutShadow::Steel => "tera_unlocked_steel",
-        PokemonTypeWithoutShadow::Fire => "tera_unlocked_fire",
-        PokemonTypeWithoutShadow::Water => "tera_unlocked_water",
-        PokemonTypeWithoutShadow::Grass => "tera_unlocked_grass",
-        PokemonTypeWithoutShadow::Electric => "tera_unlocked_electric",
-        PokemonTypeWithoutShadow::Psychic => "tera_unlocked_psychic",
-        PokemonTypeWithoutShadow::Ice => "tera_unlocked_ice",
-        PokemonTypeWithoutShadow::Dragon => "tera_unlocked_dragon",
-        PokemonTypeWithoutShadow::Dark => "tera_unlocked_dark",
-        PokemonTypeWithoutShadow::Fairy => "tera_unlocked_fairy",
+) -> Result<Vec<CharacterCacheItem>, Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let characters = match parse_character_names(ctx, guild_id, names).await {
+        Ok(characters) => characters,
+        Err(err) => return Err(Box::new(CommandInvocationError::new(&err))),
     };
+    ctx.defer().await?;
 
-    match change_character_stat(
-        ctx,
-        tera_unlocked_column,
-        names,
-        1,
-        ActionType::TerastallizationUnlock,
-    )
-    .await
-    {
-        Ok(characters) => Ok(characters),
-        Err(err) => Err(CommandInvocationError::new(&err)),
+    let stat = tera_charges::get_ledger_stat(tera_type);
+    let mut transaction = ctx.data().database.begin().await?;
+    for character in &characters {
+        tera_charges::add_unlocked_charges(&mut transaction, character.id, tera_type, 1).await?;
+        ledger::record(
+            &mut transaction,
+            LedgerEntry {
+                guild_id: character.guild_id as i64,
+                interaction_id: ctx.id() as i64,
+                actor_id: ctx.author().id.get() as i64,
+                target: LedgerTarget::Character(character.id),
+                stat: &stat,
+                amount: 1,
+                action_type: &ActionType::TerastallizationUnlock,
+            },
+        )
+        .await?;
     }
+    transaction.commit().await?;
+
+    for character in &characters {
+        update_character_post(ctx, character.id).await;
+        log_action_with_target(
+            &ActionType::TerastallizationUnlock,
+            ctx,
+            format!("Added 1 {} to {}", tera_type, character.name),
+            ActionLogTarget::character(character.id, Some(1)),
+        )
+        .await?;
+    }
+
+    Ok(characters)
 }
@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+
+use crate::commands::autocompletion::{autocomplete_nature, autocomplete_owned_character_name};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::{
+    ensure_user_owns_character, find_character, is_user_admin_or_gm, update_character_post,
+    Context, Error,
+};
+use crate::errors::{ParseError, ValidationError};
+
+/// Set your character's nature. Once set, only GMs can change it.
+#[poise::command(slash_command, guild_only)]
+pub async fn set_nature(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Which nature?"]
+    #[autocomplete = "autocomplete_nature"]
+    nature: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let Some(nature) = ctx.data().game.natures.get(&nature.to_lowercase()) else {
+        return Err(Box::new(ParseError::new(format!(
+            "Unable to find a nature named **{}**, sorry!",
+            nature
+        ))));
+    };
+
+    let member = ctx
+        .author_member()
+        .await
+        .expect("author_member should be set within guild context.");
+    let is_admin_or_gm = is_user_admin_or_gm(Cow::Borrowed(&member));
+    if !is_admin_or_gm {
+        ensure_user_owns_character(ctx.author(), &character)?;
+    }
+
+    let record = sqlx::query!("SELECT nature FROM character WHERE id = ?", character.id)
+        .fetch_one(&ctx.data().database)
+        .await?;
+    if record.nature.is_some() && !is_admin_or_gm {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} already has a nature.**\n*Please ask a GM if you want to change it.*",
+            character.name
+        ))));
+    }
+
+    sqlx::query!(
+        "UPDATE character SET nature = ? WHERE id = ?",
+        nature.name,
+        character.id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    let message = format!("Set {}'s nature to {}.", character.name, nature.name);
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::CharacterEdit,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
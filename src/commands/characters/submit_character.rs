@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use poise::Modal;
+use serenity::all::{
+    ChannelId, CreateActionRow, CreateInputText, CreateInteractionResponse, CreateMessage,
+    CreateModal, InputTextStyle, ModalInteractionData,
+};
+
+use crate::character_submission::{create_review_buttons, CharacterSubmission};
+use crate::commands::autocompletion::autocomplete_pokemon;
+use crate::commands::characters::{
+    edit_bio, is_character_channel, log_action, validate_user_input, ActionType,
+};
+use crate::commands::{
+    ensure_guild_exists, ensure_user_exists, pokemon_from_autocomplete_string,
+    send_ephemeral_reply, Error,
+};
+use crate::data::Data;
+use crate::enums::{CharacterSubmissionStatus, Gender};
+use crate::errors::ValidationError;
+
+#[derive(Debug)]
+struct SubmissionModal {
+    name: String,
+    backstory: Option<String>,
+}
+
+impl Modal for SubmissionModal {
+    fn create(_defaults: Option<Self>, custom_id: String) -> CreateInteractionResponse {
+        CreateInteractionResponse::Modal(
+            CreateModal::new(custom_id, "Character Submission").components(vec![
+                CreateActionRow::InputText(
+                    CreateInputText::new(InputTextStyle::Short, "Name", "name")
+                        .placeholder("What's your character's name?")
+                        .required(true)
+                        .min_length(1)
+                        .max_length(30),
+                ),
+                edit_bio::create_bio_input(
+                    "Backstory",
+                    "backstory",
+                    "Where does your character come from?",
+                    None,
+                ),
+            ]),
+        )
+    }
+
+    fn parse(mut data: ModalInteractionData) -> Result<Self, &'static str> {
+        Ok(SubmissionModal {
+            name: poise::find_modal_text(&mut data, "name").ok_or("missing name")?,
+            backstory: poise::find_modal_text(&mut data, "backstory"),
+        })
+    }
+}
+
+/// Submit a new character. A GM will have to approve it before it gets created.
+#[poise::command(slash_command, guild_only)]
+pub async fn submit_character(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[autocomplete = "autocomplete_pokemon"]
+    #[description = "What kind of pokemon are you?"]
+    pokemon_species: String,
+    #[description = "Which phenotype?"] gender: Gender,
+    #[description = "Optional. Does it glow in the dark? Defaults to false."] is_shiny: Option<
+        bool,
+    >,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let user_id = ctx.author().id.get() as i64;
+    let pokemon =
+        pokemon_from_autocomplete_string(&poise::Context::Application(ctx), &pokemon_species)?;
+    let is_shiny = is_shiny.unwrap_or(false);
+
+    let review_channel_id = sqlx::query!(
+        "SELECT character_review_channel_id FROM guild WHERE id = ?",
+        guild_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?
+    .and_then(|x| x.character_review_channel_id);
+    let Some(review_channel_id) = review_channel_id else {
+        return Err(Box::new(ValidationError::new(
+            "This server hasn't set up a channel for character reviews yet. Ask an admin to do so with `/setup_guild`!",
+        )));
+    };
+
+    if !is_character_channel(ctx.serenity_context(), ctx.interaction.channel_id).await {
+        return Err(Box::new(ValidationError::new(
+            "Seems like there's already been a looot of messages been sent in this channel! Please submit your character from within a new channel, so their character post can go there.",
+        )));
+    }
+
+    let Some(submission) =
+        poise::execute_modal::<_, _, SubmissionModal>(ctx, None, Some(Duration::from_secs(600)))
+            .await?
+    else {
+        return Ok(());
+    };
+
+    let name = submission.name.trim().to_string();
+    validate_user_input(&name)?;
+    let backstory = edit_bio::sanitize_bio_field(submission.backstory);
+
+    let duplicate = sqlx::query!(
+        "SELECT COUNT(*) as count FROM character WHERE guild_id = ? AND user_id = ? AND name = ?",
+        guild_id,
+        user_id,
+        name
+    )
+    .fetch_one(&ctx.data().database)
+    .await?
+    .count
+        + sqlx::query!(
+            "SELECT COUNT(*) as count FROM character_submission WHERE guild_id = ? AND user_id = ? AND name = ? AND status = ?",
+            guild_id,
+            user_id,
+            name,
+            CharacterSubmissionStatus::Pending
+        )
+        .fetch_one(&ctx.data().database)
+        .await?
+        .count;
+    if duplicate > 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "You already have a character or pending submission named **{}**.",
+            name
+        ))));
+    }
+
+    let ctx = poise::Context::Application(ctx);
+    ensure_guild_exists(&ctx, guild_id).await;
+    ensure_user_exists(&ctx, user_id, guild_id).await;
+
+    let phenotype = gender as i64;
+    let channel_id = ctx.channel_id().get() as i64;
+    let timestamp = chrono::Utc::now().timestamp();
+    let record = sqlx::query!(
+        "INSERT INTO character_submission (guild_id, user_id, name, species_api_id, phenotype, is_shiny, backstory, channel_id, creation_timestamp) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+        guild_id,
+        user_id,
+        name,
+        pokemon.poke_api_id.0,
+        phenotype,
+        is_shiny,
+        backstory,
+        channel_id,
+        timestamp
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let submission = CharacterSubmission::load(&ctx.data().database, record.id)
+        .await?
+        .expect("Submission was just created");
+    let review_message = ChannelId::new(review_channel_id as u64)
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .content(submission.build_review_message(&ctx.data().game))
+                .components(create_review_buttons(submission.id)),
+        )
+        .await;
+    let review_message = match review_message {
+        Ok(message) => message,
+        Err(e) => {
+            sqlx::query!(
+                "DELETE FROM character_submission WHERE id = ?",
+                submission.id
+            )
+            .execute(&ctx.data().database)
+            .await?;
+            return Err(Box::new(ValidationError::new(format!(
+                "Was unable to post your submission into the review channel: {}",
+                e
+            ))));
+        }
+    };
+
+    let review_message_id = review_message.id.get() as i64;
+    sqlx::query!(
+        "UPDATE character_submission SET review_message_id = ? WHERE id = ?",
+        review_message_id,
+        submission.id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    send_ephemeral_reply(
+        &ctx,
+        &format!(
+            "**{}** has been submitted for review! You'll be notified once a GM has taken a look.",
+            name
+        ),
+    )
+    .await?;
+    log_action(
+        &ActionType::CharacterSubmission,
+        &ctx,
+        format!("Submitted {} ({}) for review.", name, pokemon.name),
+    )
+    .await
+}
@@ -0,0 +1,95 @@
+use crate::commands::autocompletion::{autocomplete_owned_character_name, autocomplete_pokemon};
+use crate::commands::characters::{
+    change_species_or_form, get_registered_forms, log_action_with_target, ActionLogTarget,
+    ActionType,
+};
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, pokemon_from_autocomplete_string,
+    update_character_post, Context, Error,
+};
+use crate::errors::ValidationError;
+use crate::game_data::PokemonApiId;
+
+/// Switch your character into one of their registered forms, or back into their default one.
+#[poise::command(slash_command, guild_only)]
+pub async fn switch_form(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Which form?"]
+    #[autocomplete = "autocomplete_pokemon"]
+    form: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(&ctx, &character).await?;
+    let form = pokemon_from_autocomplete_string(&ctx, &form)?;
+
+    let record = sqlx::query!(
+        "SELECT species_api_id FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let species = ctx
+        .data()
+        .game
+        .pokemon_by_api_id
+        .get(&PokemonApiId(record.species_api_id as u16))
+        .expect("DB IDs should always be mappable.");
+    let form_api_id = form.poke_api_id.0 as i64;
+    let active_form_api_id = if form_api_id == record.species_api_id {
+        None
+    } else {
+        let registered_forms = get_registered_forms(&ctx.data().database, character.id).await;
+        if !registered_forms.contains(&form_api_id) {
+            let available = registered_forms
+                .iter()
+                .filter_map(|x| {
+                    ctx.data()
+                        .game
+                        .pokemon_by_api_id
+                        .get(&PokemonApiId(*x as u16))
+                })
+                .map(|x| x.name.clone())
+                .collect::<Vec<String>>();
+            return Err(Box::new(ValidationError::new(if available.is_empty() {
+                format!(
+                    "{} has no other forms registered. Ask a GM if that should change!",
+                    character.name
+                )
+            } else {
+                format!(
+                    "{} can't switch into {}. Available forms: {}",
+                    character.name,
+                    form.name,
+                    available.join(", ")
+                )
+            })));
+        }
+        Some(form_api_id)
+    };
+
+    change_species_or_form(
+        &ctx.data().database,
+        &ctx.data().game,
+        character.id,
+        species,
+        species,
+        active_form_api_id,
+    )
+    .await?;
+
+    let message = format!("{} switched into their {} form.", character.name, form.name);
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::CharacterEdit,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
@@ -4,7 +4,7 @@ use poise::{CreateReply, ReplyHandle};
 use serenity::all::{ButtonStyle, CreateActionRow};
 
 use crate::commands::autocompletion::autocomplete_owned_character_name;
-use crate::commands::characters::ActionType;
+use crate::commands::characters::{ActionLogTarget, ActionType};
 use crate::commands::{characters, find_character, update_character_post, Context, Error};
 use crate::errors::ValidationError;
 use crate::{emoji, helpers};
@@ -95,7 +95,7 @@ pub async fn unlock_hidden_ability(
                 .await;
 
             if query_result.is_ok() && query_result.unwrap().rows_affected() == 1 {
-                characters::log_action(
+                characters::log_action_with_target(
                     &ActionType::Payment,
                     &ctx,
                     format!(
@@ -105,12 +105,14 @@ pub async fn unlock_hidden_ability(
                         character.name,
                     )
                     .as_str(),
+                    ActionLogTarget::character(character.id, Some(-PRICE)),
                 )
                 .await?;
-                characters::log_action(
+                characters::log_action_with_target(
                     &ActionType::HiddenAbilityUnlock,
                     &ctx,
                     format!("Unlocked {}'s hidden ability!", character.name).as_str(),
+                    ActionLogTarget::character(character.id, None),
                 )
                 .await?;
 
@@ -1,7 +1,8 @@
 use crate::commands::autocompletion::autocomplete_retired_character_name;
-use crate::commands::characters::{log_action, ActionType};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
 use crate::commands::{find_character, update_character_post, Context, Error};
 use crate::helpers;
+use sqlx::{Pool, Sqlite};
 
 /// Unretire a character.
 #[allow(clippy::too_many_arguments)]
@@ -19,13 +20,7 @@ pub async fn unretire_character(
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let character = find_character(ctx.data(), guild_id, &character).await?;
 
-    match sqlx::query!(
-        "UPDATE character SET is_retired = false WHERE id = ?",
-        character.id
-    )
-    .execute(&ctx.data().database)
-    .await
-    {
+    match unretire(&ctx.data().database, character.id).await {
         Ok(_) => {
             let _ = ctx
                 .reply(&format!(
@@ -34,10 +29,11 @@ pub async fn unretire_character(
                 ))
                 .await;
 
-            let _ = log_action(
+            let _ = log_action_with_target(
                 &ActionType::CharacterUnRetirement,
                 &ctx,
                 &format!("{} has returned from their retirement.", character.name),
+                ActionLogTarget::character(character.id, None),
             )
             .await;
 
@@ -58,3 +54,23 @@ pub async fn unretire_character(
 
     Ok(())
 }
+
+/// Retirement records are kept for the hall of fame, they just get marked as concluded.
+async fn unretire(database: &Pool<Sqlite>, character_id: i64) -> Result<(), sqlx::Error> {
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut transaction = database.begin().await?;
+    sqlx::query!(
+        "UPDATE character SET is_retired = false WHERE id = ?",
+        character_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    sqlx::query!(
+        "UPDATE character_retirement SET unretirement_timestamp = ? WHERE character_id = ? AND unretirement_timestamp IS NULL",
+        timestamp,
+        character_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+    transaction.commit().await
+}
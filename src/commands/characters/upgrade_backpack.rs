@@ -4,16 +4,15 @@ use poise::{CreateReply, ReplyHandle};
 use serenity::all::{ButtonStyle, CreateActionRow};
 
 use crate::commands::autocompletion::autocomplete_owned_character_name;
-use crate::commands::characters::{ActionType, DEFAULT_BACKPACK_SLOTS};
+use crate::commands::characters::{ActionLogTarget, ActionType, DEFAULT_BACKPACK_SLOTS};
 use crate::commands::{
     characters, find_character, send_error, update_character_post, Context, Error,
 };
-use crate::{emoji, helpers};
+use crate::guild_settings::GuildSettings;
+use crate::{emoji, guild_treasury, helpers};
 
 const CONFIRM: &str = "upgrade_backpack_proceed";
 const ABORT: &str = "upgrade_backpack_abort";
-const BASE_PRICE: i64 = 500;
-const MONEY_PER_LEVEL: i64 = 500;
 
 /// See what it takes to upgrade your backpack!
 #[allow(clippy::too_many_arguments)]
@@ -33,7 +32,9 @@ pub async fn upgrade_backpack(
     .fetch_one(&ctx.data().database)
     .await?;
 
-    let required_money = BASE_PRICE + MONEY_PER_LEVEL * character_record.backpack_upgrade_count;
+    let settings = GuildSettings::load(&ctx.data().database, guild_id as i64).await;
+    let required_money =
+        settings.calculate_backpack_upgrade_price(character_record.backpack_upgrade_count);
     let target_slots = DEFAULT_BACKPACK_SLOTS + character_record.backpack_upgrade_count + 1;
     if character_record.money < required_money {
         return send_error(
@@ -88,7 +89,12 @@ pub async fn upgrade_backpack(
         if interaction.data.custom_id == CONFIRM {
             let updated_money = character_record.money - required_money;
             let updated_backpack_upgrade_count = character_record.backpack_upgrade_count + 1;
+            let fee = guild_treasury::calculate_tax(
+                required_money,
+                settings.backpack_upgrade_fee_percentage,
+            );
 
+            let mut transaction = ctx.data().database.begin().await?;
             let query_result = sqlx::query!(
                         "UPDATE character SET money = ?, backpack_upgrade_count = ? WHERE id = ? AND money = ? and backpack_upgrade_count = ?",
                         updated_money,
@@ -97,26 +103,46 @@ pub async fn upgrade_backpack(
                         character_record.money,
                         character_record.backpack_upgrade_count,
                     )
-                .execute(&ctx.data().database)
+                .execute(&mut *transaction)
                 .await;
 
             if query_result.is_ok() && query_result.unwrap().rows_affected() == 1 {
-                characters::log_action(
+                let mut payment_message = format!(
+                    "Removed {} {} from {}",
+                    required_money,
+                    emoji::POKE_COIN,
+                    character.name,
+                );
+                if fee > 0 {
+                    guild_treasury::deposit_in_transaction(
+                        &mut transaction,
+                        guild_id as i64,
+                        fee,
+                        Some(character.id),
+                        "Backpack upgrade fee",
+                    )
+                    .await?;
+                    payment_message.push_str(&format!(
+                        " ({} {} went to the {})",
+                        fee,
+                        emoji::POKE_COIN,
+                        guild_treasury::GUILD_TREASURY_NAME
+                    ));
+                }
+                transaction.commit().await?;
+
+                characters::log_action_with_target(
                     &ActionType::Payment,
                     &ctx,
-                    format!(
-                        "Removed {} {} from {}",
-                        required_money,
-                        emoji::POKE_COIN,
-                        character.name,
-                    )
-                    .as_str(),
+                    payment_message.as_str(),
+                    ActionLogTarget::character(character.id, Some(-required_money)),
                 )
                 .await?;
-                characters::log_action(
+                characters::log_action_with_target(
                     &ActionType::BackpackUpgrade,
                     &ctx,
                     format!("Increased {}'s backpack size by 1", character.name).as_str(),
+                    ActionLogTarget::character(character.id, Some(1)),
                 )
                 .await?;
 
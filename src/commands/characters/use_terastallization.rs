@@ -6,12 +6,7 @@ use crate::commands::{
 };
 use crate::enums::PokemonTypeWithoutShadow;
 use crate::errors::ValidationError;
-
-#[derive(sqlx::FromRow)]
-struct TeraCharge {
-    unlocked: i64,
-    used: i64,
-}
+use crate::tera_charges;
 
 /// Use a Terastallization charge.
 #[allow(clippy::too_many_arguments)]
@@ -29,36 +24,13 @@ pub async fn use_terastallization(
     let character = find_character(ctx.data(), guild_id, &character).await?;
     ensure_user_owns_character(ctx.author(), &character)?;
 
-    let tera_used_column = tera_type.get_tera_used_column();
-    let tera_unlocked_column = tera_type.get_tera_unlocked_column();
-
-    let record = sqlx::query_as::<_, TeraCharge>(
-        format!(
-            "SELECT {} as unlocked, {} as used FROM character WHERE id = ?",
-            tera_unlocked_column, tera_used_column
-        )
-        .as_str(),
-    )
-    .bind(character.id)
-    .fetch_one(&ctx.data().database)
-    .await?;
-
-    if record.used >= record.unlocked {
+    if !tera_charges::use_charge(&ctx.data().database, character.id, tera_type).await? {
         return Err(Box::new(ValidationError::new(&format!(
             "{} doesn't seem to have any {} Terastallization charges left!",
             character.name, tera_type
         ))));
     }
 
-    sqlx::query(&format!(
-        "UPDATE character SET {} = ? WHERE id = ?",
-        tera_used_column
-    ))
-    .bind(record.used + 1)
-    .bind(character.id)
-    .execute(&ctx.data().database)
-    .await?;
-
     ctx.say(format!(
         "{} used a {} Terastallization!",
         character.name, tera_type
@@ -0,0 +1,121 @@
+use chrono::Utc;
+
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_pokemon};
+use crate::commands::characters::{
+    log_action_with_target, validate_user_input, ActionLogTarget, ActionType,
+};
+use crate::commands::encounter::EncounterMon;
+use crate::commands::{
+    find_character, pokemon_from_autocomplete_string, send_ephemeral_reply, send_error,
+    update_character_post, Context, Error,
+};
+use crate::errors::ValidationError;
+use crate::guild_settings::GuildSettings;
+
+/// Give a character a companion pokemon. Its stats will be distributed randomly.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn add_companion(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which pokemon?"]
+    #[autocomplete = "autocomplete_pokemon"]
+    species: String,
+    #[min = 1_u8]
+    #[max = 100_u8]
+    #[description = "Of which level?"]
+    level: u8,
+    #[description = "What's its name?"] nickname: String,
+    #[description = "Optional. An emoji to display next to its name."]
+    #[max_length = 64]
+    emoji: Option<String>,
+) -> Result<(), Error> {
+    let nickname = nickname.trim().to_string();
+    if let Err(e) = validate_user_input(&nickname) {
+        return send_error(&ctx, e).await;
+    }
+
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let pokemon = pokemon_from_autocomplete_string(&ctx, &species)?;
+    let emoji = emoji
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty());
+
+    let settings = GuildSettings::load(&ctx.data().database, guild_id as i64).await;
+    let mon = EncounterMon::from_pokemon(pokemon, level, &settings);
+    let experience = (level as i64 - 1) * settings.experience_per_level;
+    let phenotype = mon.gender as i64;
+    let timestamp = Utc::now().timestamp();
+    let (strength, dexterity, vitality, special, insight) = (
+        mon.strength,
+        mon.dexterity,
+        mon.vitality,
+        mon.special,
+        mon.insight,
+    );
+    let (tough, cool, beauty, clever, cute) =
+        (mon.tough, mon.cool, mon.beauty, mon.clever, mon.cute);
+    let result = sqlx::query!(
+        "INSERT INTO companion (character_id, name, species_api_id, emoji, phenotype, ability, experience, \
+            stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+            stat_tough, stat_cool, stat_beauty, stat_clever, stat_cute, creation_timestamp) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        character.id,
+        nickname,
+        pokemon.poke_api_id.0,
+        emoji,
+        phenotype,
+        mon.ability,
+        experience,
+        strength,
+        dexterity,
+        vitality,
+        special,
+        insight,
+        tough,
+        cool,
+        beauty,
+        clever,
+        cute,
+        timestamp
+    )
+    .execute(&ctx.data().database)
+    .await;
+
+    match result {
+        Ok(_) => {}
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return Err(Box::new(ValidationError::new(format!(
+                "{} already has a companion named {}.",
+                character.name, nickname
+            ))));
+        }
+        Err(e) => return Err(Box::new(e)),
+    }
+
+    update_character_post(&ctx, character.id).await;
+    send_ephemeral_reply(
+        &ctx,
+        &format!(
+            "{} has a new companion: **{}** ({})!",
+            character.name, nickname, pokemon.name
+        ),
+    )
+    .await?;
+    log_action_with_target(
+        &ActionType::CompanionChange,
+        &ctx,
+        format!(
+            "Added {} ({}, Lv. {}) as a companion for {}.",
+            nickname, pokemon.name, level, character.name
+        ),
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
@@ -0,0 +1,42 @@
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_companion_name};
+use crate::commands::companions::find_companion;
+use crate::commands::{find_character, Context, Error};
+use crate::errors::ValidationError;
+use crate::game_data::PokemonApiId;
+use crate::guild_settings::GuildSettings;
+use crate::helpers;
+
+/// Display the stats of a character's companion.
+#[poise::command(slash_command, guild_only)]
+pub async fn companion(
+    ctx: Context<'_>,
+    #[description = "Whose companion?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which companion?"]
+    #[autocomplete = "autocomplete_companion_name"]
+    companion: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let companion = find_companion(&ctx.data().database, character.id, &companion).await?;
+    let Some(pokemon) = ctx
+        .data()
+        .game
+        .pokemon_by_api_id
+        .get(&PokemonApiId(companion.species_api_id as u16))
+    else {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to find a pokemon with ID {}.",
+            companion.species_api_id
+        ))));
+    };
+
+    let settings = GuildSettings::load(&ctx.data().database, guild_id as i64).await;
+    let mon = companion.to_encounter_mon(pokemon, &settings);
+    for part in helpers::split_long_messages(mon.build_string(pokemon, &ctx.data().game)) {
+        ctx.say(part).await?;
+    }
+
+    Ok(())
+}
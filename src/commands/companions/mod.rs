@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+
+use poise::Command;
+use sqlx::{Pool, Sqlite};
+
+use crate::commands::encounter::EncounterMon;
+use crate::data::Data;
+use crate::enums::Gender;
+use crate::errors::ParseError;
+use crate::game_data::pokemon::Pokemon;
+use crate::game_data::{GameData, PokemonApiId};
+use crate::guild_settings::GuildSettings;
+use crate::{helpers, Error};
+
+mod add_companion;
+mod companion;
+mod release_companion;
+
+pub fn get_all_commands() -> Vec<Command<Data, Error>> {
+    vec![
+        add_companion::add_companion(),
+        companion::companion(),
+        release_companion::release_companion(),
+    ]
+}
+
+/// A partner pokemon which belongs to a character.
+pub struct Companion {
+    pub id: i64,
+    pub name: String,
+    pub species_api_id: i64,
+    pub emoji: Option<String>,
+    pub phenotype: i64,
+    pub ability: String,
+    pub experience: i64,
+    pub stat_strength: i64,
+    pub stat_dexterity: i64,
+    pub stat_vitality: i64,
+    pub stat_special: i64,
+    pub stat_insight: i64,
+    pub stat_tough: i64,
+    pub stat_cool: i64,
+    pub stat_beauty: i64,
+    pub stat_clever: i64,
+    pub stat_cute: i64,
+}
+
+impl Companion {
+    pub fn display_name(&self) -> String {
+        match &self.emoji {
+            Some(emoji) => format!("{} {}", emoji, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn to_encounter_mon(&self, pokemon: &Pokemon, settings: &GuildSettings) -> EncounterMon {
+        let level = settings.calculate_level_from_experience(self.experience);
+        EncounterMon {
+            name: format!("{} ({})", self.display_name(), pokemon.name),
+            gender: Gender::from_phenotype(self.phenotype),
+            type1: pokemon.type1,
+            type2: pokemon.type2,
+            level: level.min(u8::MAX as i64) as u8,
+            rank: settings.calculate_rank(level),
+            ability: self.ability.clone(),
+            hp: helpers::calculate_max_hp(pokemon.base_hp, self.stat_vitality) as u8,
+            will: helpers::calculate_max_will(self.stat_insight) as u8,
+            strength: self.stat_strength as u8,
+            dexterity: self.stat_dexterity as u8,
+            vitality: self.stat_vitality as u8,
+            special: self.stat_special as u8,
+            insight: self.stat_insight as u8,
+            tough: self.stat_tough as u8,
+            cool: self.stat_cool as u8,
+            beauty: self.stat_beauty as u8,
+            clever: self.stat_clever as u8,
+            cute: self.stat_cute as u8,
+            moves: Vec::new(),
+        }
+    }
+
+    fn apply_stats(&mut self, mon: &EncounterMon) {
+        self.stat_strength = mon.strength as i64;
+        self.stat_dexterity = mon.dexterity as i64;
+        self.stat_vitality = mon.vitality as i64;
+        self.stat_special = mon.special as i64;
+        self.stat_insight = mon.insight as i64;
+        self.stat_tough = mon.tough as i64;
+        self.stat_cool = mon.cool as i64;
+        self.stat_beauty = mon.beauty as i64;
+        self.stat_clever = mon.clever as i64;
+        self.stat_cute = mon.cute as i64;
+    }
+}
+
+pub async fn get_companions(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+) -> Result<Vec<Companion>, sqlx::Error> {
+    sqlx::query_as!(
+        Companion,
+        "SELECT id, name, species_api_id, emoji, phenotype, ability, experience, \
+                stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+                stat_tough, stat_cool, stat_beauty, stat_clever, stat_cute \
+         FROM companion WHERE character_id = ? ORDER BY creation_timestamp",
+        character_id
+    )
+    .fetch_all(database)
+    .await
+}
+
+pub async fn find_companion(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+    name: &str,
+) -> Result<Companion, ParseError> {
+    let companion = sqlx::query_as!(
+        Companion,
+        "SELECT id, name, species_api_id, emoji, phenotype, ability, experience, \
+                stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+                stat_tough, stat_cool, stat_beauty, stat_clever, stat_cute \
+         FROM companion WHERE character_id = ? AND name = ?",
+        character_id,
+        name
+    )
+    .fetch_optional(database)
+    .await;
+
+    match companion {
+        Ok(Some(companion)) => Ok(companion),
+        _ => Err(ParseError::new(format!(
+            "Unable to find a companion named {}",
+            name
+        ))),
+    }
+}
+
+/// Lists the companions of a character for their character post.
+pub fn build_companion_list(
+    game_data: &GameData,
+    settings: &GuildSettings,
+    companions: &[Companion],
+) -> String {
+    let mut result = String::new();
+    for companion in companions {
+        let species = match game_data
+            .pokemon_by_api_id
+            .get(&PokemonApiId(companion.species_api_id as u16))
+        {
+            Some(pokemon) => pokemon.name.clone(),
+            None => String::from("Unknown Pokemon"),
+        };
+
+        result.push_str(&format!(
+            "- **{}** ({}) Lv. {}\n",
+            companion.display_name(),
+            species,
+            settings.calculate_level_from_experience(companion.experience)
+        ));
+    }
+
+    result
+}
+
+/// Companions gain the same amount of experience as their partner.
+/// Stat points gained through level ups are distributed randomly, just like for encounters.
+/// Returns a notification for every companion which leveled up.
+pub async fn grant_experience(
+    database: &Pool<Sqlite>,
+    pokemon_by_api_id: &HashMap<PokemonApiId, Pokemon>,
+    settings: &GuildSettings,
+    character_id: i64,
+    amount: i64,
+) -> Result<Vec<String>, Error> {
+    let mut level_ups = Vec::new();
+    for mut companion in get_companions(database, character_id).await? {
+        let old_level = settings.calculate_level_from_experience(companion.experience);
+        companion.experience += amount;
+        let new_level = settings.calculate_level_from_experience(companion.experience);
+
+        if new_level > old_level {
+            if let Some(pokemon) =
+                pokemon_by_api_id.get(&PokemonApiId(companion.species_api_id as u16))
+            {
+                let mut mon = companion.to_encounter_mon(pokemon, settings);
+                mon.distribute_combat_points(
+                    pokemon,
+                    settings.combat_points_per_level * (new_level - old_level),
+                );
+                mon.distribute_social_points(
+                    settings.calculate_available_social_points(&settings.calculate_rank(new_level))
+                        - settings
+                            .calculate_available_social_points(&settings.calculate_rank(old_level)),
+                );
+                companion.apply_stats(&mon);
+            }
+
+            level_ups.push(format!(
+                "**{}** just reached level {}!",
+                companion.display_name(),
+                new_level
+            ));
+        }
+
+        sqlx::query!(
+            "UPDATE companion SET experience = ?, \
+                stat_strength = ?, stat_dexterity = ?, stat_vitality = ?, stat_special = ?, stat_insight = ?, \
+                stat_tough = ?, stat_cool = ?, stat_beauty = ?, stat_clever = ?, stat_cute = ? \
+             WHERE id = ?",
+            companion.experience,
+            companion.stat_strength,
+            companion.stat_dexterity,
+            companion.stat_vitality,
+            companion.stat_special,
+            companion.stat_insight,
+            companion.stat_tough,
+            companion.stat_cool,
+            companion.stat_beauty,
+            companion.stat_clever,
+            companion.stat_cute,
+            companion.id
+        )
+        .execute(database)
+        .await?;
+    }
+
+    Ok(level_ups)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use sqlx::{Pool, Sqlite};
+
+    use crate::commands::companions::{find_companion, grant_experience};
+    use crate::enums::{PokemonGeneration, PokemonType};
+    use crate::game_data::pokemon::{
+        DataSource, Height, LearnablePokemonMoves, Pokemon, PokemonSpeciesData, PokemonStat, Weight,
+    };
+    use crate::game_data::PokemonApiId;
+    use crate::guild_settings::GuildSettings;
+    use crate::{database_helpers, Error};
+
+    fn create_pokemon(api_id: u16) -> Pokemon {
+        let stat = || PokemonStat { min: 1, max: 5 };
+        Pokemon {
+            number: api_id,
+            poke_api_id: PokemonApiId(api_id),
+            data_source: DataSource::PokeRole,
+            species_data: PokemonSpeciesData {
+                has_gender_differences: false,
+                generation: PokemonGeneration::One,
+                pokedex_entries: Vec::new(),
+            },
+            regional_variant: None,
+            evolves_from: None,
+            api_issue: None,
+            name: String::from("Pikachu"),
+            type1: PokemonType::Electric,
+            type2: None,
+            base_hp: 4,
+            strength: stat(),
+            dexterity: stat(),
+            vitality: stat(),
+            special: stat(),
+            insight: stat(),
+            ability1: String::from("Static"),
+            ability2: None,
+            hidden_ability: None,
+            event_abilities: None,
+            height: Height {
+                meters: 0.4,
+                feet: 1.3,
+            },
+            weight: Weight {
+                kilograms: 6.0,
+                pounds: 13.2,
+            },
+            moves: LearnablePokemonMoves {
+                by_pokerole_rank: Vec::new(),
+                by_level_up: Vec::new(),
+                by_machine: Vec::new(),
+                by_tutor: Vec::new(),
+                by_egg: Vec::new(),
+            },
+        }
+    }
+
+    async fn get_stat_sums(db: &Pool<Sqlite>, character_id: i64) -> (i64, i64) {
+        let record = sqlx::query!(
+            "SELECT stat_strength + stat_dexterity + stat_vitality + stat_special + stat_insight as \"combat!: i64\", \
+                    stat_tough + stat_cool + stat_beauty + stat_clever + stat_cute as \"social!: i64\" \
+             FROM companion WHERE character_id = ?",
+            character_id
+        )
+        .fetch_one(db)
+        .await
+        .unwrap();
+        (record.combat, record.social)
+    }
+
+    #[sqlx::test]
+    async fn companion_names_should_be_unique_per_character(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        database_helpers::create_mock::character_in_database(
+            &db, guild_id, user_id, 1, "Partner", 0,
+        )
+        .await;
+        database_helpers::create_mock::character_in_database(&db, guild_id, user_id, 2, "Rival", 0)
+            .await;
+
+        let insert = "INSERT INTO companion (character_id, name, species_api_id, phenotype, ability, experience, \
+                stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+                stat_tough, stat_cool, stat_beauty, stat_clever, stat_cute, creation_timestamp) \
+             VALUES (?, ?, 25, 1, 'Static', 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0)";
+        sqlx::query(insert)
+            .bind(1)
+            .bind("Sparky")
+            .execute(&db)
+            .await?;
+        sqlx::query(insert)
+            .bind(2)
+            .bind("Sparky")
+            .execute(&db)
+            .await?;
+        assert!(sqlx::query(insert)
+            .bind(1)
+            .bind("sparky")
+            .execute(&db)
+            .await
+            .is_err());
+
+        let companion = find_companion(&db, 1, "SPARKY").await?;
+        assert_eq!("Sparky", companion.name);
+        assert!(find_companion(&db, 1, "Bolt").await.is_err());
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn companions_should_only_gain_stats_on_level_up(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        let character_id = 1;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        database_helpers::create_mock::character_in_database(
+            &db,
+            guild_id,
+            user_id,
+            character_id,
+            "Partner",
+            0,
+        )
+        .await;
+        sqlx::query!(
+            "INSERT INTO companion (character_id, name, species_api_id, phenotype, ability, experience, \
+                stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight, \
+                stat_tough, stat_cool, stat_beauty, stat_clever, stat_cute, creation_timestamp) \
+             VALUES (?, 'Sparky', 25, 1, 'Static', 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0)",
+            character_id
+        )
+        .execute(&db)
+        .await?;
+
+        let pokemon = HashMap::from([(PokemonApiId(25), create_pokemon(25))]);
+        let settings = GuildSettings::default();
+
+        let level_ups = grant_experience(&db, &pokemon, &settings, character_id, 50).await?;
+        assert!(level_ups.is_empty());
+        assert_eq!((5, 5), get_stat_sums(&db, character_id).await);
+
+        // Reaching level 2 also means reaching silver rank with the default settings.
+        let level_ups = grant_experience(&db, &pokemon, &settings, character_id, 50).await?;
+        assert_eq!(1, level_ups.len());
+        assert_eq!(
+            (
+                5 + settings.combat_points_per_level,
+                5 + settings.social_points_per_rank
+            ),
+            get_stat_sums(&db, character_id).await
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_companion_name};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::companions::find_companion;
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, send_ephemeral_reply,
+    update_character_post, Context, Error,
+};
+
+/// Part ways with a companion. This cannot be undone!
+#[poise::command(slash_command, guild_only)]
+pub async fn release_companion(
+    ctx: Context<'_>,
+    #[description = "Whose companion?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which companion?"]
+    #[autocomplete = "autocomplete_companion_name"]
+    companion: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(&ctx, &character).await?;
+    let companion = find_companion(&ctx.data().database, character.id, &companion).await?;
+
+    sqlx::query!("DELETE FROM companion WHERE id = ?", companion.id)
+        .execute(&ctx.data().database)
+        .await?;
+
+    update_character_post(&ctx, character.id).await;
+    send_ephemeral_reply(
+        &ctx,
+        &format!(
+            "{} and {} have parted ways.",
+            character.name, companion.name
+        ),
+    )
+    .await?;
+    log_action_with_target(
+        &ActionType::CompanionChange,
+        &ctx,
+        format!(
+            "{} released their companion {}.",
+            character.name, companion.name
+        ),
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
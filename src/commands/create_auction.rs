@@ -0,0 +1,123 @@
+use chrono::Utc;
+use serenity::all::CreateMessage;
+
+use crate::auctions;
+use crate::commands::autocompletion::{autocomplete_item, autocomplete_wallet_name};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::inventory::find_item;
+use crate::commands::{find_wallet, send_ephemeral_reply, Context, Error};
+use crate::errors::ValidationError;
+
+const SECONDS_PER_HOUR: i64 = 60 * 60;
+
+/// Auction off an item or service. Bids are held in escrow and paid into a wallet once it ends.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn create_auction(
+    ctx: Context<'_>,
+    #[description = "Which wallet receives the money?"]
+    #[autocomplete = "autocomplete_wallet_name"]
+    seller_wallet: String,
+    #[description = "What's being auctioned?"]
+    #[max_length = 100]
+    name: String,
+    #[min = 1_u32]
+    #[description = "What's the lowest possible bid?"]
+    starting_price: u32,
+    #[min = 1_u32]
+    #[description = "By how much does each bid need to beat the previous one?"]
+    minimum_increment: u32,
+    #[min = 1_u32]
+    #[max = 720_u32]
+    #[description = "In how many hours should the auction end?"]
+    ends_in_hours: u32,
+    #[description = "Optional. An item which is automatically handed to the winner."]
+    #[autocomplete = "autocomplete_item"]
+    item: Option<String>,
+    #[description = "Optional. Any further details for the bidders."]
+    #[max_length = 500]
+    description: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let seller_wallet = find_wallet(ctx.data(), guild_id, &seller_wallet).await?;
+    let item_name = match item {
+        Some(item) => Some(find_item(&ctx.data().game, &item)?.name.clone()),
+        None => None,
+    };
+
+    let guild_id = guild_id as i64;
+    let starting_price = starting_price as i64;
+    let minimum_increment = minimum_increment as i64;
+    let creator_id = ctx.author().id.get() as i64;
+    let channel_id = ctx.channel_id().get() as i64;
+    let timestamp = Utc::now().timestamp();
+    let end_timestamp = timestamp + ends_in_hours as i64 * SECONDS_PER_HOUR;
+    let record = sqlx::query!(
+        "INSERT INTO auction (guild_id, seller_wallet_id, name, description, item_name, starting_price, minimum_increment, end_timestamp, channel_id, message_id, creator_id, creation_timestamp) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 0, ?, ?) RETURNING id",
+        guild_id,
+        seller_wallet.id,
+        name,
+        description,
+        item_name,
+        starting_price,
+        minimum_increment,
+        end_timestamp,
+        channel_id,
+        creator_id,
+        timestamp
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let Some(auction) = auctions::load(&ctx.data().database, record.id).await? else {
+        return Err(Box::new(ValidationError::new(
+            "Unable to load the auction after creating it.",
+        )));
+    };
+
+    let message = match ctx
+        .channel_id()
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .content(auction.build_message())
+                .components(auction.create_bid_buttons()),
+        )
+        .await
+    {
+        Ok(message) => message,
+        Err(e) => {
+            let _ = sqlx::query!("DELETE FROM auction WHERE id = ?", record.id)
+                .execute(&ctx.data().database)
+                .await;
+            return Err(Box::new(e));
+        }
+    };
+
+    let message_id = message.id.get() as i64;
+    sqlx::query!(
+        "UPDATE auction SET message_id = ? WHERE id = ?",
+        message_id,
+        record.id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    let log_message = format!(
+        "Created auction #{} ({}) for {}, ending <t:{}:f>.",
+        record.id, name, seller_wallet.name, end_timestamp
+    );
+    send_ephemeral_reply(&ctx, &log_message).await?;
+    log_action_with_target(
+        &ActionType::Auction,
+        &ctx,
+        &log_message,
+        ActionLogTarget::wallet(seller_wallet.id, None),
+    )
+    .await
+}
@@ -8,7 +8,7 @@ use crate::game_data::pokemon::Pokemon;
 use crate::{emoji, Error};
 use image::{DynamicImage, GenericImageView, ImageFormat};
 use log::info;
-use serenity::all::{CreateAttachment, Emoji, GuildId};
+use serenity::all::{CacheHttp, CreateAttachment, Emoji, GuildId, Http};
 use sqlx::{Pool, Sqlite};
 use std::fs::File;
 use std::io::{BufReader, Cursor, Read, Seek};
@@ -153,6 +153,19 @@ fn get_emoji_data(
     })
 }
 
+/// Loads the cropped, non-animated sprite from the local sprite files.
+pub(crate) fn load_local_sprite(
+    pokemon: &Pokemon,
+    gender: &Gender,
+    is_shiny: bool,
+) -> Result<DynamicImage, Error> {
+    let use_female_sprite =
+        pokemon.species_data.has_gender_differences && gender == &Gender::Female;
+
+    let path = local_emoji_path(pokemon, use_female_sprite, is_shiny, false);
+    Ok(crop_whitespace(image::open(path)?))
+}
+
 fn crop_whitespace(image: DynamicImage) -> DynamicImage {
     let mut top_border = find_top_border(&image);
     let bottom_border = find_bottom_border(&image);
@@ -174,43 +187,41 @@ fn crop_whitespace(image: DynamicImage) -> DynamicImage {
     image.crop_imm(left_border, top_border, width, bottom_border - top_border)
 }
 
-async fn upload_emoji_to_server_or_emoji_guild<'a>(
-    ctx: &Context<'a>,
+/// Returns the guild the emoji was uploaded to, the emoji, and a notification about it.
+async fn upload_emoji_to_server_or_emoji_guild(
+    http: &Http,
+    database: &Pool<Sqlite>,
+    guild_id: GuildId,
     emoji_data: EmojiData,
-) -> Result<(GuildId, Emoji), serenity::all::Error> {
-    let guild_id = ctx.guild_id().expect("create_emoji Command is guild_only!");
+) -> Result<(GuildId, Emoji, String), serenity::all::Error> {
     let attachment = CreateAttachment::bytes(emoji_data.data, &emoji_data.name);
     match guild_id
-        .create_emoji(&ctx, emoji_data.name.as_str(), &attachment.to_base64())
+        .create_emoji(http, emoji_data.name.as_str(), &attachment.to_base64())
         .await
     {
         Ok(emoji) => {
-            let _ = send_ephemeral_reply(ctx, &format!("Created new emoji: {}", emoji)).await;
-            Ok((guild_id, emoji))
+            let notification = format!("Created new emoji: {}", emoji);
+            Ok((guild_id, emoji, notification))
         }
         Err(e) => {
             // Server is probably at emoji capacity, upload to emoji server instead.
             match sqlx::query!("SELECT id, emoji_count FROM emoji_guild ORDER BY emoji_count DESC")
-                .fetch_one(&ctx.data().database)
+                .fetch_one(database)
                 .await
             {
                 Ok(record) => {
                     let guild_id = GuildId::new(record.id as u64);
                     match guild_id
-                        .create_emoji(&ctx, emoji_data.name.as_str(), &attachment.to_base64())
+                        .create_emoji(http, emoji_data.name.as_str(), &attachment.to_base64())
                         .await
                     {
                         Ok(emoji) => {
-                            let _ = send_ephemeral_reply(
-                                ctx,
-                                &format!(
-                                    "\
+                            let notification = format!(
+                                "\
 Created new emoji: {}\n\
 This server has reached its emoji capacity, but I won't be stopped by such trivial things!",
-                                    emoji
-                                ),
-                            )
-                            .await;
+                                emoji
+                            );
 
                             let new_count = record.emoji_count + 1;
                             let _ = sqlx::query!(
@@ -218,10 +229,10 @@ This server has reached its emoji capacity, but I won't be stopped by such trivi
                                 new_count,
                                 record.id
                             )
-                            .execute(&ctx.data().database)
+                            .execute(database)
                             .await;
 
-                            Ok((guild_id, emoji))
+                            Ok((guild_id, emoji, notification))
                         }
                         Err(e) => Err(e),
                     }
@@ -262,38 +273,77 @@ pub async fn create_emojis_for_pokemon<'a>(
     gender: &Gender,
     is_shiny: bool,
 ) -> u8 {
-    let guild_id = ctx.guild_id().expect("Emoji creation is guild_only.").get() as i64;
-    let mut created_emojis = 0u8;
-    if !does_emoji_exist_in_database(
+    let guild_id = ctx.guild_id().expect("Emoji creation is guild_only.");
+    ensure_guild_exists(ctx, guild_id.get() as i64).await;
+
+    let results = create_missing_emojis_for_pokemon(
+        ctx.http(),
         &ctx.data().database,
         guild_id,
         pokemon,
         gender,
         is_shiny,
-        false,
     )
-    .await
-    {
-        create_emoji_and_notify_user(ctx, pokemon, gender, is_shiny, false).await;
-        created_emojis += 1u8;
+    .await;
+
+    let mut created_emojis = 0u8;
+    for result in &results {
+        match result {
+            Ok(notification) => {
+                created_emojis += 1u8;
+                let _ = send_ephemeral_reply(ctx, notification).await;
+            }
+            Err(e) => {
+                let _ = send_error(ctx, e).await;
+            }
+        }
     }
 
-    if pokemon.has_animated_sprite()
-        && !does_emoji_exist_in_database(
-            &ctx.data().database,
-            guild_id,
+    created_emojis
+}
+
+/// Creates all emojis which don't exist yet for the given pokemon.
+/// Returns a notification for every emoji which was created, or an error for every one that failed.
+pub async fn create_missing_emojis_for_pokemon(
+    http: &Http,
+    database: &Pool<Sqlite>,
+    guild_id: GuildId,
+    pokemon: &Pokemon,
+    gender: &Gender,
+    is_shiny: bool,
+) -> Vec<Result<String, String>> {
+    let mut notifications = Vec::new();
+    for is_animated in [false, true] {
+        if is_animated && !pokemon.has_animated_sprite() {
+            continue;
+        }
+
+        if !does_emoji_exist_in_database(
+            database,
+            guild_id.get() as i64,
             pokemon,
             gender,
             is_shiny,
-            true,
+            is_animated,
         )
         .await
-    {
-        create_emoji_and_notify_user(ctx, pokemon, gender, is_shiny, true).await;
-        created_emojis += 1u8;
+        {
+            notifications.push(
+                create_emoji(
+                    http,
+                    database,
+                    guild_id,
+                    pokemon,
+                    gender,
+                    is_shiny,
+                    is_animated,
+                )
+                .await,
+            );
+        }
     }
 
-    created_emojis
+    notifications
 }
 
 pub async fn store_emoji_in_database(
@@ -337,47 +387,41 @@ pub async fn does_emoji_exist_in_database(
     }
 }
 
-async fn create_emoji_and_notify_user<'a>(
-    ctx: &Context<'a>,
+async fn create_emoji(
+    http: &Http,
+    database: &Pool<Sqlite>,
+    guild_id: GuildId,
     pokemon: &Pokemon,
     gender: &Gender,
     is_shiny: bool,
     is_animated: bool,
-) {
-    let guild_id = ctx.guild_id().expect("Creating emoji is guild_only!").get() as i64;
-    ensure_guild_exists(ctx, guild_id).await;
-
+) -> Result<String, String> {
     match get_emoji_data(pokemon, gender, is_shiny, is_animated) {
-        Ok(emoji_data) => match upload_emoji_to_server_or_emoji_guild(ctx, emoji_data).await {
-            Ok((guild_id, emoji)) => {
-                store_emoji_in_database(
-                    &ctx.data().database,
-                    guild_id,
-                    &emoji,
-                    pokemon,
-                    gender,
-                    is_shiny,
-                    is_animated,
-                )
-                .await;
-            }
-            Err(e) => {
-                let _ = send_error(
-                    ctx,
-                    &format!(
-                        "Something went wrong when uploading the emoji to discord: {:?}",
-                        e
-                    ),
-                )
-                .await;
+        Ok(emoji_data) => {
+            match upload_emoji_to_server_or_emoji_guild(http, database, guild_id, emoji_data).await
+            {
+                Ok((guild_id, emoji, notification)) => {
+                    store_emoji_in_database(
+                        database,
+                        guild_id,
+                        &emoji,
+                        pokemon,
+                        gender,
+                        is_shiny,
+                        is_animated,
+                    )
+                    .await;
+                    Ok(notification)
+                }
+                Err(e) => Err(format!(
+                    "Something went wrong when uploading the emoji to discord: {:?}",
+                    e
+                )),
             }
-        },
-        Err(e) => {
-            let _ = send_error(
-                ctx,
-                &format!("Something went wrong when parsing the emoji: {:?}", e),
-            )
-            .await;
         }
+        Err(e) => Err(format!(
+            "Something went wrong when parsing the emoji: {:?}",
+            e
+        )),
     }
 }
@@ -8,6 +8,7 @@ use crate::enums::{CombatOrSocialStat, Gender, MysteryDungeonRank, PokemonType,
 use crate::game_data::pokemon::Pokemon;
 use crate::game_data::r#move::Move;
 use crate::game_data::GameData;
+use crate::guild_settings::GuildSettings;
 use crate::helpers;
 
 /// Encounter some wild pokemon!
@@ -27,7 +28,11 @@ pub async fn encounter(
     amount: Option<u8>,
 ) -> Result<(), Error> {
     let pokemon = pokemon_from_autocomplete_string(&ctx, &pokemon)?;
-    for encounter in build_encounter(pokemon, level, amount) {
+    let settings = match ctx.guild_id() {
+        Some(guild_id) => GuildSettings::load(&ctx.data().database, guild_id.get() as i64).await,
+        None => GuildSettings::default(),
+    };
+    for encounter in build_encounter(pokemon, level, amount, &settings) {
         for part in helpers::split_long_messages(encounter.build_string(pokemon, &ctx.data().game))
         {
             ctx.say(part).await?;
@@ -37,17 +42,22 @@ pub async fn encounter(
     Ok(())
 }
 
-fn build_encounter(pokemon: &Pokemon, level: u8, amount: Option<u8>) -> Vec<EncounterMon> {
+fn build_encounter(
+    pokemon: &Pokemon,
+    level: u8,
+    amount: Option<u8>,
+    settings: &GuildSettings,
+) -> Vec<EncounterMon> {
     let mut result = Vec::new();
     for _ in 0..amount.unwrap_or(1) {
-        result.push(EncounterMon::from_pokemon(pokemon, level));
+        result.push(EncounterMon::from_pokemon(pokemon, level, settings));
     }
 
     result
 }
 
 #[derive(Debug)]
-struct EncounterMon {
+pub struct EncounterMon {
     pub name: String,
     pub gender: Gender,
     pub type1: PokemonType,
@@ -71,14 +81,14 @@ struct EncounterMon {
 }
 
 impl EncounterMon {
-    pub fn from_pokemon(pokemon: &Pokemon, level: u8) -> Self {
+    pub fn from_pokemon(pokemon: &Pokemon, level: u8, settings: &GuildSettings) -> Self {
         let mut result = EncounterMon {
             name: pokemon.name.clone(),
             gender: EncounterMon::get_random_gender(pokemon),
             type1: pokemon.type1,
             type2: pokemon.type2,
             level,
-            rank: MysteryDungeonRank::from_level(level),
+            rank: settings.calculate_rank(level as i64),
             ability: EncounterMon::get_random_ability(pokemon),
             hp: 0,
             will: 0,
@@ -95,49 +105,88 @@ impl EncounterMon {
             moves: Vec::new(),
         };
 
+        result.distribute_combat_points(
+            pokemon,
+            settings.calculate_available_combat_points(level as i64),
+        );
+        result.distribute_social_points(settings.calculate_available_social_points(&result.rank));
+
+        result.hp = helpers::calculate_max_hp(pokemon.base_hp, result.vitality as i64) as u8;
+        result.will = helpers::calculate_max_will(result.insight as i64) as u8;
+
+        let available_moves = pokemon
+            .moves
+            .by_pokerole_rank
+            .iter()
+            .filter(|x| x.rank <= result.rank)
+            .map(|x| x.name.clone());
+
+        let move_count = result.insight + 2;
+        result.moves = available_moves.choose_multiple(&mut thread_rng(), move_count as usize);
+
+        result
+    }
+
+    /// Randomly spends the given amount of combat points, starting from the current stats.
+    /// Once all stats are maxed out, the remaining points are used for limit breaks.
+    pub fn distribute_combat_points(&mut self, pokemon: &Pokemon, points: i64) {
         let mut rng = thread_rng();
-        let all_stats = vec![
+        let all_stats = [
             Stat::Strength,
             Stat::Vitality,
             Stat::Dexterity,
             Stat::Special,
             Stat::Insight,
         ];
-        let mut non_maxed_stat_points = all_stats.clone();
-        let mut remaining_stat_points = helpers::calculate_available_combat_points(level as i64);
-        let mut limit_break_count = 0;
+        let mut non_maxed_stat_points: Vec<Stat> = all_stats
+            .iter()
+            .filter(|x| self.get_stat(x) < pokemon.get_stat(x).max)
+            .copied()
+            .collect();
+        let mut remaining_stat_points = points;
+        let mut limit_break_count = all_stats
+            .iter()
+            .map(|x| (self.get_stat(x) as i64 - pokemon.get_stat(x).max as i64).max(0))
+            .sum::<i64>();
         while remaining_stat_points > 0 {
             if let Some(mut stat) = non_maxed_stat_points.choose(&mut rng) {
-                result.increase_stat(stat);
+                self.increase_stat(stat);
 
-                if result.get_stat(stat) == pokemon.get_stat(stat).max {
+                if self.get_stat(stat) >= pokemon.get_stat(stat).max {
                     let el_drop_o = *stat;
                     stat = &el_drop_o;
                     non_maxed_stat_points.retain(|x| x != stat);
                 }
                 remaining_stat_points -= 1;
             } else if remaining_stat_points > 2 + limit_break_count {
-                result.increase_stat(all_stats.choose(&mut rng).unwrap());
+                self.increase_stat(all_stats.choose(&mut rng).unwrap());
                 remaining_stat_points -= 2 + limit_break_count;
                 limit_break_count += 1;
             } else {
                 break;
             }
         }
+    }
 
-        let mut non_maxed_social_stats = vec![
+    /// Randomly spends the given amount of social points, starting from the current stats.
+    pub fn distribute_social_points(&mut self, points: i64) {
+        let mut rng = thread_rng();
+        let mut non_maxed_social_stats: Vec<SocialStat> = [
             SocialStat::Tough,
             SocialStat::Cool,
             SocialStat::Beauty,
             SocialStat::Clever,
             SocialStat::Cute,
-        ];
-        let mut remaining_social_points = helpers::calculate_available_social_points(&result.rank);
+        ]
+        .into_iter()
+        .filter(|x| self.get_social_stat(x) < 5)
+        .collect();
+        let mut remaining_social_points = points;
         while remaining_social_points > 0 {
             if let Some(mut stat) = non_maxed_social_stats.choose(&mut rng) {
-                result.increase_social_stat(stat);
+                self.increase_social_stat(stat);
 
-                if result.get_social_stat(stat) == 5 {
+                if self.get_social_stat(stat) == 5 {
                     let el_drop_o = *stat;
                     stat = &el_drop_o;
                     non_maxed_social_stats.retain(|x| x != stat);
@@ -146,21 +195,6 @@ impl EncounterMon {
 
             remaining_social_points -= 1;
         }
-
-        result.hp = (pokemon.base_hp + result.vitality) * 2;
-        result.will = result.insight + 2;
-
-        let available_moves = pokemon
-            .moves
-            .by_pokerole_rank
-            .iter()
-            .filter(|x| x.rank <= result.rank)
-            .map(|x| x.name.clone());
-
-        let move_count = result.insight + 2;
-        result.moves = available_moves.choose_multiple(&mut thread_rng(), move_count as usize);
-
-        result
     }
 
     fn get_random_gender(_pokemon: &Pokemon) -> Gender {
@@ -232,7 +266,7 @@ impl EncounterMon {
         }
     }
 
-    fn get_social_stat(&mut self, stat: &SocialStat) -> u8 {
+    fn get_social_stat(&self, stat: &SocialStat) -> u8 {
         match stat {
             SocialStat::Tough => self.tough,
             SocialStat::Cool => self.cool,
@@ -294,7 +328,9 @@ INS: {:>2} / {:>2}      Cute:   {} / 5
             result.push_str(std::format!("**Ability**: {}\n*Not Found!*\n", self.ability).as_str());
         }
 
-        result.push_str("## Moves\n");
+        if !self.moves.is_empty() {
+            result.push_str("## Moves\n");
+        }
         for move_name in &self.moves {
             let move_name = move_name
                 .trim_end_matches('¹')
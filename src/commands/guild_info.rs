@@ -0,0 +1,71 @@
+use crate::commands::{Context, Error};
+use crate::enums::PayoutSchedule;
+use crate::guild_settings::GuildSettings;
+use crate::guild_treasury::GUILD_TREASURY_NAME;
+use crate::{emoji, guild_treasury, recurring_payouts};
+
+/// View a summary of this server, including the guild treasury.
+#[poise::command(slash_command, guild_only)]
+pub async fn guild_info(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let database = &ctx.data().database;
+    let guild_name = sqlx::query!("SELECT name FROM guild WHERE id = ?", guild_id)
+        .fetch_optional(database)
+        .await?
+        .and_then(|x| x.name)
+        .or_else(|| ctx.guild().map(|x| x.name.clone()))
+        .unwrap_or_else(|| String::from("This Server"));
+    let counts = sqlx::query!(
+        "SELECT \
+            (SELECT COUNT(*) FROM character WHERE guild_id = ? AND is_retired = false) as characters, \
+            (SELECT COUNT(*) FROM wallet WHERE guild_id = ?) as wallets, \
+            (SELECT COUNT(*) FROM shop WHERE guild_id = ?) as shops",
+        guild_id,
+        guild_id,
+        guild_id
+    )
+    .fetch_one(database)
+    .await?;
+
+    let balance = guild_treasury::get_balance(database, guild_id).await?;
+    let settings = GuildSettings::load(database, guild_id).await;
+    let treasury_payouts = recurring_payouts::load(database, Some(guild_id), None)
+        .await?
+        .into_iter()
+        .filter(|x| x.source_wallet_id.is_none())
+        .collect::<Vec<_>>();
+    let sum_payouts = |schedule: PayoutSchedule| -> i64 {
+        treasury_payouts
+            .iter()
+            .filter(|x| x.schedule == schedule)
+            .map(|x| x.amount)
+            .sum()
+    };
+
+    ctx.say(format!(
+        "\
+## {}
+- Active Characters: **{}**
+- Wallets: **{}** | Shops: **{}**
+### {}
+- Balance: **{}** {}
+- Shop Tax: **{}%** | Backpack Upgrade Fee: **{}%**
+- Recurring Payouts: **{}** {} weekly, **{}** {} monthly",
+        guild_name,
+        counts.characters,
+        counts.wallets,
+        counts.shops,
+        GUILD_TREASURY_NAME,
+        balance,
+        emoji::POKE_COIN,
+        settings.shop_tax_percentage,
+        settings.backpack_upgrade_fee_percentage,
+        sum_payouts(PayoutSchedule::Weekly),
+        emoji::POKE_COIN,
+        sum_payouts(PayoutSchedule::Monthly),
+        emoji::POKE_COIN,
+    ))
+    .await?;
+
+    Ok(())
+}
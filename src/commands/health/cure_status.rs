@@ -0,0 +1,62 @@
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_status_effect};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::health::find_status_effect;
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, update_character_post, Context, Error,
+};
+use crate::errors::ValidationError;
+
+/// Remove a status condition from a character. Leave the status empty to remove all of them.
+#[poise::command(slash_command, guild_only)]
+pub async fn cure_status(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which status effect? Leave empty to cure all of them."]
+    #[autocomplete = "autocomplete_status_effect"]
+    status: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(&ctx, &character).await?;
+    let status_effect = match status {
+        Some(status) => Some(find_status_effect(&ctx.data().game, &status)?),
+        None => None,
+    };
+
+    let status_effect_name = status_effect.map(|x| x.name.as_str());
+    let result = sqlx::query!(
+        "DELETE FROM character_status_effect WHERE character_id = ? AND (? IS NULL OR status_effect_name = ?)",
+        character.id,
+        status_effect_name,
+        status_effect_name
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} isn't affected by {}.**",
+            character.name,
+            status_effect_name.unwrap_or("any status effects")
+        ))));
+    }
+
+    let message = match status_effect_name {
+        Some(name) => format!("{} is no longer affected by {}.", character.name, name),
+        None => format!(
+            "{} is no longer affected by any status effects.",
+            character.name
+        ),
+    };
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::StatusEffectChange,
+        &ctx,
+        message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
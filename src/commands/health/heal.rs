@@ -0,0 +1,45 @@
+use poise::ChoiceParameter;
+
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::health::{change_health, HealthStat};
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, update_character_post, Context, Error,
+};
+
+/// Restore a character's current HP or Will.
+#[poise::command(slash_command, guild_only)]
+pub async fn heal(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "How much?"]
+    #[min = 1_i64]
+    amount: i64,
+    #[description = "Optional. Defaults to HP."] stat: Option<HealthStat>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(&ctx, &character).await?;
+    let stat = stat.unwrap_or(HealthStat::Hp);
+
+    let health = change_health(ctx.data(), character.id, stat, amount).await?;
+
+    let message = format!(
+        "{} restored {} {}.\n{}",
+        character.name,
+        amount,
+        stat.name(),
+        health.build_string()
+    );
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::HealthChange,
+        &ctx,
+        message,
+        ActionLogTarget::character(character.id, Some(amount)),
+    )
+    .await
+}
@@ -0,0 +1,53 @@
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_status_effect};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::health::find_status_effect;
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, update_character_post, Context, Error,
+};
+use crate::errors::ValidationError;
+
+/// Apply a status condition to a character.
+#[poise::command(slash_command, guild_only)]
+pub async fn inflict_status(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which status effect?"]
+    #[autocomplete = "autocomplete_status_effect"]
+    status: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(&ctx, &character).await?;
+    let status_effect = find_status_effect(&ctx.data().game, &status)?;
+
+    let result = sqlx::query!(
+        "INSERT OR IGNORE INTO character_status_effect (character_id, status_effect_name) VALUES (?, ?)",
+        character.id,
+        status_effect.name
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} is already affected by {}.**",
+            character.name, status_effect.name
+        ))));
+    }
+
+    let message = format!(
+        "{} is now affected by {}!",
+        character.name, status_effect.name
+    );
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::StatusEffectChange,
+        &ctx,
+        message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
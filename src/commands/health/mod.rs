@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use poise::{ChoiceParameter, Command};
+use sqlx::{Pool, Sqlite};
+
+use crate::data::Data;
+use crate::errors::ParseError;
+use crate::game_data::status_effect::StatusEffect;
+use crate::game_data::{GameData, PokemonApiId};
+use crate::guild_settings::GuildSettings;
+use crate::{helpers, Error};
+
+mod cure_status;
+mod damage;
+mod heal;
+mod inflict_status;
+mod rest;
+
+pub fn get_all_commands() -> Vec<Command<Data, Error>> {
+    vec![
+        damage::damage(),
+        heal::heal(),
+        rest::rest(),
+        inflict_status::inflict_status(),
+        cure_status::cure_status(),
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ChoiceParameter)]
+pub enum HealthStat {
+    #[name = "HP"]
+    Hp,
+    Will,
+}
+
+pub struct CharacterHealth {
+    pub max_hp: i64,
+    pub current_hp: i64,
+    pub max_will: i64,
+    pub current_will: i64,
+}
+
+impl CharacterHealth {
+    /// Missing values mean the character is fully rested.
+    /// Stats might have decreased since the last update, so current values get clamped.
+    pub fn new(
+        max_hp: i64,
+        current_hp: Option<i64>,
+        max_will: i64,
+        current_will: Option<i64>,
+    ) -> Self {
+        CharacterHealth {
+            max_hp,
+            current_hp: current_hp.unwrap_or(max_hp).clamp(0, max_hp),
+            max_will,
+            current_will: current_will.unwrap_or(max_will).clamp(0, max_will),
+        }
+    }
+
+    pub fn build_string(&self) -> String {
+        format!(
+            "❤️ `{}/{}` HP | 💭 `{}/{}` Will",
+            self.current_hp, self.max_hp, self.current_will, self.max_will
+        )
+    }
+}
+
+pub async fn get_character_health(
+    database: &Pool<Sqlite>,
+    game_data: &Arc<GameData>,
+    character_id: i64,
+) -> Result<CharacterHealth, Error> {
+    let record = sqlx::query!(
+        "SELECT guild_id, species_api_id, species_override_for_stats, active_form_api_id, experience, stat_vitality, stat_insight, current_hp, current_will \
+         FROM character WHERE id = ?",
+        character_id
+    )
+    .fetch_one(database)
+    .await?;
+
+    let pokemon = game_data
+        .pokemon_by_api_id
+        .get(&PokemonApiId(record.species_api_id as u16))
+        .expect("DB IDs should always be mappable.");
+    let level = GuildSettings::load(database, record.guild_id)
+        .await
+        .calculate_level_from_experience(record.experience);
+    let pokemon_for_stats = helpers::get_usual_evolution_stage_for_level(
+        level,
+        helpers::get_active_form(pokemon, game_data, record.active_form_api_id),
+        game_data,
+        record.species_override_for_stats,
+    );
+
+    Ok(CharacterHealth::new(
+        helpers::calculate_max_hp(pokemon_for_stats.base_hp, record.stat_vitality),
+        record.current_hp,
+        helpers::calculate_max_will(record.stat_insight),
+        record.current_will,
+    ))
+}
+
+/// Adds the given amount to the character's current HP or Will, staying within zero and their max value.
+/// The change is applied relative to the stored value, so concurrent changes don't overwrite each other.
+pub async fn change_health(
+    data: &Data,
+    character_id: i64,
+    stat: HealthStat,
+    amount: i64,
+) -> Result<CharacterHealth, Error> {
+    let health = get_character_health(&data.database, &data.game, character_id).await?;
+    match stat {
+        HealthStat::Hp => sqlx::query!(
+            "UPDATE character SET current_hp = MIN(MAX(COALESCE(current_hp, ?) + ?, 0), ?) WHERE id = ?",
+            health.max_hp,
+            amount,
+            health.max_hp,
+            character_id
+        )
+        .execute(&data.database)
+        .await?,
+        HealthStat::Will => sqlx::query!(
+            "UPDATE character SET current_will = MIN(MAX(COALESCE(current_will, ?) + ?, 0), ?) WHERE id = ?",
+            health.max_will,
+            amount,
+            health.max_will,
+            character_id
+        )
+        .execute(&data.database)
+        .await?,
+    };
+
+    get_character_health(&data.database, &data.game, character_id).await
+}
+
+pub fn find_status_effect<'a>(
+    game_data: &'a GameData,
+    name: &str,
+) -> Result<&'a StatusEffect, ParseError> {
+    match game_data.status_effects.get(&name.to_lowercase()) {
+        Some(status_effect) => Ok(status_effect),
+        None => Err(ParseError::new(format!(
+            "Unable to find a status effect named **{}**, sorry!",
+            name
+        ))),
+    }
+}
+
+pub async fn get_status_effects(database: &Pool<Sqlite>, character_id: i64) -> Vec<String> {
+    match sqlx::query!(
+        "SELECT status_effect_name FROM character_status_effect WHERE character_id = ? ORDER BY status_effect_name",
+        character_id
+    )
+    .fetch_all(database)
+    .await
+    {
+        Ok(records) => records.into_iter().map(|x| x.status_effect_name).collect(),
+        Err(e) => {
+            log::error!(
+                "Was unable to load status effects for character {}: {}",
+                character_id,
+                e
+            );
+            Vec::new()
+        }
+    }
+}
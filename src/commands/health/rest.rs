@@ -0,0 +1,55 @@
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::health::get_character_health;
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, update_character_post, Context, Error,
+};
+use crate::enums::TeraChargeReset;
+use crate::guild_settings::GuildSettings;
+use crate::tera_charges;
+
+/// Fully restore a character's HP and Will. Depending on server settings, Tera charges too.
+#[poise::command(slash_command, guild_only)]
+pub async fn rest(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(&ctx, &character).await?;
+
+    sqlx::query!(
+        "UPDATE character SET current_hp = NULL, current_will = NULL WHERE id = ?",
+        character.id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    let settings = GuildSettings::load(&ctx.data().database, guild_id as i64).await;
+    let tera_charges_restored = settings.tera_charge_reset == TeraChargeReset::OnRest
+        && tera_charges::reset_used_charges_for_character(&ctx.data().database, character.id)
+            .await?;
+
+    let health = get_character_health(&ctx.data().database, &ctx.data().game, character.id).await?;
+    let message = format!(
+        "{} took a rest and feels refreshed!\n{}{}",
+        character.name,
+        health.build_string(),
+        if tera_charges_restored {
+            "\nTheir Terastallization Charges have been restored."
+        } else {
+            ""
+        }
+    );
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::HealthChange,
+        &ctx,
+        message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
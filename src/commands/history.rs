@@ -0,0 +1,400 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate};
+use poise::CreateReply;
+use serenity::all::{
+    ComponentInteractionCollector, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage, User,
+};
+use sqlx::{Pool, Sqlite};
+
+use crate::commands::autocompletion::{autocomplete_action_type, autocomplete_character_name};
+use crate::commands::characters::ActionType;
+use crate::commands::{find_character, Context, Error};
+use crate::errors::ValidationError;
+use crate::helpers;
+
+/// Entries are loaded in batches while browsing, so huge histories don't need to be loaded at once.
+const ENTRIES_PER_BATCH: i64 = 50;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+pub(super) const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+struct HistoryEntry {
+    timestamp: i64,
+    actor_id: i64,
+    action_type: String,
+    amount: Option<i64>,
+    message: String,
+    channel_id: i64,
+    character_name: Option<String>,
+    wallet_name: Option<String>,
+}
+
+struct HistoryFilters {
+    guild_id: i64,
+    character_id: Option<i64>,
+    actor_id: Option<i64>,
+    action_type: Option<&'static str>,
+    from: Option<i64>,
+    until: Option<i64>,
+}
+
+/// Browse through all logged actions on this server.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn history(
+    ctx: Context<'_>,
+    #[description = "Only show actions affecting this character."]
+    #[autocomplete = "autocomplete_character_name"]
+    character: Option<String>,
+    #[description = "Only show actions triggered by this user."] user: Option<User>,
+    #[description = "Only show actions of this type."]
+    #[autocomplete = "autocomplete_action_type"]
+    action_type: Option<String>,
+    #[description = "First day to include. (YYYY-MM-DD)"] from: Option<String>,
+    #[description = "Last day to include. (YYYY-MM-DD)"] until: Option<String>,
+    #[description = "Upload the results as CSV file instead."] export_csv: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character_id = match character {
+        Some(character) => Some(find_character(ctx.data(), guild_id, &character).await?.id),
+        None => None,
+    };
+    let actor_id = user.map(|x| x.id.get() as i64);
+    let action_type = match action_type {
+        Some(action_type) => match ActionType::from_str(&action_type) {
+            Ok(action_type) => Some(<&'static str>::from(action_type)),
+            Err(_) => {
+                return Err(Box::new(ValidationError::new(format!(
+                    "Unknown action type: {}",
+                    action_type
+                ))))
+            }
+        },
+        None => None,
+    };
+    let from = match from {
+        Some(from) => Some(parse_date(&from)?),
+        None => None,
+    };
+    let until = match until {
+        Some(until) => Some(parse_date(&until)? + SECONDS_PER_DAY),
+        None => None,
+    };
+
+    let filters = HistoryFilters {
+        guild_id: guild_id as i64,
+        character_id,
+        actor_id,
+        action_type,
+        from,
+        until,
+    };
+    let database = &ctx.data().database;
+    let total = count_entries(database, &filters).await?;
+    if total == 0 {
+        ctx.say("Seems like there's no logged action matching these filters.")
+            .await?;
+        return Ok(());
+    }
+
+    if export_csv.unwrap_or(false) {
+        let entries = load_entries(database, &filters, -1, 0).await?;
+        let csv = build_csv(&entries)?;
+        ctx.send(
+            CreateReply::default()
+                .content(format!("Found {} actions.", entries.len()))
+                .attachment(CreateAttachment::bytes(csv, "history.csv")),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    paginate(ctx, &filters, total).await
+}
+
+async fn count_entries(database: &Pool<Sqlite>, filters: &HistoryFilters) -> Result<i64, Error> {
+    let record = sqlx::query!(
+        "SELECT COUNT(*) as \"count: i64\" FROM action_log \
+         WHERE guild_id = ? \
+            AND (? IS NULL OR character_id = ?) \
+            AND (? IS NULL OR actor_id = ?) \
+            AND (? IS NULL OR action_type = ?) \
+            AND (? IS NULL OR timestamp >= ?) \
+            AND (? IS NULL OR timestamp < ?)",
+        filters.guild_id,
+        filters.character_id,
+        filters.character_id,
+        filters.actor_id,
+        filters.actor_id,
+        filters.action_type,
+        filters.action_type,
+        filters.from,
+        filters.from,
+        filters.until,
+        filters.until
+    )
+    .fetch_one(database)
+    .await?;
+
+    Ok(record.count)
+}
+
+/// A negative limit loads all remaining entries.
+async fn load_entries(
+    database: &Pool<Sqlite>,
+    filters: &HistoryFilters,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<HistoryEntry>, Error> {
+    let entries = sqlx::query_as!(
+        HistoryEntry,
+        "SELECT action_log.timestamp, action_log.actor_id, action_log.action_type, action_log.amount, action_log.message, action_log.channel_id, \
+            character.name as character_name, wallet.name as wallet_name \
+         FROM action_log \
+            LEFT JOIN character ON character.id = action_log.character_id \
+            LEFT JOIN wallet ON wallet.id = action_log.wallet_id \
+         WHERE action_log.guild_id = ? \
+            AND (? IS NULL OR action_log.character_id = ?) \
+            AND (? IS NULL OR action_log.actor_id = ?) \
+            AND (? IS NULL OR action_log.action_type = ?) \
+            AND (? IS NULL OR action_log.timestamp >= ?) \
+            AND (? IS NULL OR action_log.timestamp < ?) \
+         ORDER BY action_log.timestamp DESC, action_log.id DESC \
+         LIMIT ? OFFSET ?",
+        filters.guild_id,
+        filters.character_id,
+        filters.character_id,
+        filters.actor_id,
+        filters.actor_id,
+        filters.action_type,
+        filters.action_type,
+        filters.from,
+        filters.from,
+        filters.until,
+        filters.until,
+        limit,
+        offset
+    )
+    .fetch_all(database)
+    .await?;
+
+    Ok(entries)
+}
+
+/// Loads the next batch of entries and appends them as pages which fit into a single message.
+async fn load_next_pages(
+    database: &Pool<Sqlite>,
+    filters: &HistoryFilters,
+    loaded_entries: &mut i64,
+    pages: &mut Vec<String>,
+) -> Result<(), Error> {
+    let entries = load_entries(database, filters, ENTRIES_PER_BATCH, *loaded_entries).await?;
+    *loaded_entries += entries.len() as i64;
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let lines = entries
+        .iter()
+        .map(build_history_line)
+        .collect::<Vec<String>>()
+        .join("\n");
+    pages.extend(helpers::split_long_messages(lines));
+    Ok(())
+}
+
+fn build_page(pages: &[String], index: usize, total: i64, is_fully_loaded: bool) -> CreateEmbed {
+    let page_count = if is_fully_loaded {
+        format!("/{}", pages.len())
+    } else {
+        String::new()
+    };
+
+    CreateEmbed::new().description(format!(
+        "### Action History\n*Page {}{}, {} actions in total.*\n{}",
+        index + 1,
+        page_count,
+        total,
+        pages[index]
+    ))
+}
+
+/// Works like [poise::builtins::paginate], but only loads further entries once they are needed.
+async fn paginate(ctx: Context<'_>, filters: &HistoryFilters, total: i64) -> Result<(), Error> {
+    let database = &ctx.data().database;
+    let mut pages = Vec::new();
+    let mut loaded_entries = 0;
+    load_next_pages(database, filters, &mut loaded_entries, &mut pages).await?;
+
+    let ctx_id = ctx.id();
+    let prev_button_id = format!("{}prev", ctx_id);
+    let next_button_id = format!("{}next", ctx_id);
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(&prev_button_id).emoji('◀'),
+        CreateButton::new(&next_button_id).emoji('▶'),
+    ]);
+    ctx.send(
+        CreateReply::default()
+            .embed(build_page(&pages, 0, total, loaded_entries >= total))
+            .components(vec![buttons]),
+    )
+    .await?;
+
+    let mut current_page = 0;
+    while let Some(press) = ComponentInteractionCollector::new(ctx)
+        .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+        .timeout(std::time::Duration::from_secs(3600 * 24))
+        .await
+    {
+        if press.data.custom_id == next_button_id {
+            if current_page + 1 >= pages.len() && loaded_entries < total {
+                load_next_pages(database, filters, &mut loaded_entries, &mut pages).await?;
+            }
+            current_page += 1;
+            if current_page >= pages.len() {
+                current_page = 0;
+            }
+        } else if press.data.custom_id == prev_button_id {
+            // Jumping to the end is only possible once we know where it is.
+            current_page = match current_page.checked_sub(1) {
+                Some(page) => page,
+                None if loaded_entries >= total => pages.len() - 1,
+                None => 0,
+            };
+        } else {
+            continue;
+        }
+
+        press
+            .create_response(
+                ctx.serenity_context(),
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new().embed(build_page(
+                        &pages,
+                        current_page,
+                        total,
+                        loaded_entries >= total,
+                    )),
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn parse_date(input: &str) -> Result<i64, ValidationError> {
+    match NaiveDate::parse_from_str(input, DATE_FORMAT) {
+        Ok(date) => Ok(date
+            .and_hms_opt(0, 0, 0)
+            .expect("Midnight should always be valid")
+            .and_utc()
+            .timestamp()),
+        Err(_) => Err(ValidationError::new(format!(
+            "Unable to parse {} as date. Please use the format YYYY-MM-DD, e.g. 2024-10-23.",
+            input
+        ))),
+    }
+}
+
+fn build_history_line(entry: &HistoryEntry) -> String {
+    let action_type = match ActionType::from_str(&entry.action_type) {
+        Ok(action_type) => action_type.to_string(),
+        Err(_) => entry.action_type.clone(),
+    };
+
+    format!(
+        "<t:{}:f> {} {} (by <@{}>)",
+        entry.timestamp, action_type, entry.message, entry.actor_id
+    )
+}
+
+fn build_csv(entries: &[HistoryEntry]) -> Result<Vec<u8>, Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "timestamp",
+        "actor_id",
+        "action_type",
+        "character",
+        "wallet",
+        "amount",
+        "channel_id",
+        "message",
+    ])?;
+
+    for entry in entries {
+        let timestamp = match DateTime::from_timestamp(entry.timestamp, 0) {
+            Some(timestamp) => timestamp.to_rfc3339(),
+            None => entry.timestamp.to_string(),
+        };
+        writer.write_record([
+            timestamp,
+            entry.actor_id.to_string(),
+            entry.action_type.clone(),
+            entry.character_name.clone().unwrap_or_default(),
+            entry.wallet_name.clone().unwrap_or_default(),
+            entry.amount.map(|x| x.to_string()).unwrap_or_default(),
+            entry.channel_id.to_string(),
+            entry.message.clone(),
+        ])?;
+    }
+
+    Ok(writer.into_inner()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Pool, Sqlite};
+
+    use crate::commands::history::{
+        count_entries, load_next_pages, HistoryFilters, ENTRIES_PER_BATCH,
+    };
+    use crate::{database_helpers, Error};
+
+    #[sqlx::test]
+    async fn history_should_be_loaded_in_batches(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        let long_message = "x".repeat(200);
+        for timestamp in 0..ENTRIES_PER_BATCH + 5 {
+            sqlx::query!(
+                "INSERT INTO action_log (guild_id, actor_id, action_type, message, channel_id, timestamp) \
+                 VALUES (?, 1, 'Reward', ?, 0, ?)",
+                guild_id,
+                long_message,
+                timestamp
+            )
+            .execute(&db)
+            .await?;
+        }
+
+        let filters = HistoryFilters {
+            guild_id,
+            character_id: None,
+            actor_id: None,
+            action_type: None,
+            from: None,
+            until: None,
+        };
+        assert_eq!(ENTRIES_PER_BATCH + 5, count_entries(&db, &filters).await?);
+
+        let mut pages = Vec::new();
+        let mut loaded_entries = 0;
+        load_next_pages(&db, &filters, &mut loaded_entries, &mut pages).await?;
+        assert_eq!(ENTRIES_PER_BATCH, loaded_entries);
+        assert!(pages.len() > 1);
+        assert!(pages.iter().all(|x| x.chars().count() <= 2000));
+        assert!(pages[0].starts_with(&format!("<t:{}:f>", ENTRIES_PER_BATCH + 4)));
+
+        load_next_pages(&db, &filters, &mut loaded_entries, &mut pages).await?;
+        assert_eq!(ENTRIES_PER_BATCH + 5, loaded_entries);
+        assert!(pages.last().unwrap().contains("<t:0:f>"));
+
+        Ok(())
+    }
+}
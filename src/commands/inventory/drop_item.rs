@@ -0,0 +1,40 @@
+use crate::commands::autocompletion::{autocomplete_item, autocomplete_owned_character_name};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::inventory::{find_item, remove_item_from_inventory};
+use crate::commands::{
+    ensure_user_owns_character, find_character, update_character_post, Context, Error,
+};
+
+/// Throw an item out of your backpack.
+#[poise::command(slash_command, guild_only)]
+pub async fn drop_item(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+    #[description = "Optional. Defaults to 1."]
+    #[min = 1_i64]
+    amount: Option<i64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+    let item = find_item(&ctx.data().game, &item)?;
+    let amount = amount.unwrap_or(1);
+
+    remove_item_from_inventory(ctx.data(), &character, item, amount).await?;
+
+    let message = format!("{} dropped {}x {}.", character.name, amount, item.name);
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::ItemDrop,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, Some(-amount)),
+    )
+    .await
+}
@@ -0,0 +1,44 @@
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_item};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::inventory::{add_item_to_inventory, find_item};
+use crate::commands::{find_character, update_character_post, Context, Error};
+
+/// Put an item into a character's backpack.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn give_item(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+    #[description = "Optional. Defaults to 1."]
+    #[min = 1_i64]
+    amount: Option<i64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    let item = find_item(&ctx.data().game, &item)?;
+    let amount = amount.unwrap_or(1);
+
+    add_item_to_inventory(ctx.data(), &character, item, amount).await?;
+
+    let message = format!(
+        "Added {}x {} to {}'s backpack.",
+        amount, item.name, character.name
+    );
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::ItemReward,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, Some(amount)),
+    )
+    .await
+}
@@ -0,0 +1,50 @@
+use crate::commands::autocompletion::{autocomplete_item, autocomplete_owned_character_name};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::inventory::{change_held_item, find_item};
+use crate::commands::{
+    ensure_user_owns_character, find_character, update_character_post, Context, Error,
+};
+
+/// Take an item out of your backpack and hold it. Leave the item empty to put it back.
+#[poise::command(slash_command, guild_only)]
+pub async fn hold_item(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Which item? Leave empty to stop holding anything."]
+    #[autocomplete = "autocomplete_item"]
+    item: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+    let item = match item {
+        Some(item) => Some(find_item(&ctx.data().game, &item)?),
+        None => None,
+    };
+
+    let previous = change_held_item(ctx.data(), &character, item).await?;
+
+    let message = match (item, previous) {
+        (Some(item), Some(previous)) => format!(
+            "{} put {} back into their backpack and is now holding {}.",
+            character.name, previous, item.name
+        ),
+        (Some(item), None) => format!("{} is now holding {}.", character.name, item.name),
+        (None, Some(previous)) => format!(
+            "{} put {} back into their backpack.",
+            character.name, previous
+        ),
+        (None, None) => format!("{} isn't holding anything.", character.name),
+    };
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::HeldItemChange,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
@@ -0,0 +1,364 @@
+use poise::Command;
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::cache::CharacterCacheItem;
+use crate::commands::characters::DEFAULT_BACKPACK_SLOTS;
+use crate::data::Data;
+use crate::errors::{ParseError, ValidationError};
+use crate::game_data::item::Item;
+use crate::game_data::GameData;
+use crate::Error;
+
+mod drop_item;
+mod give_item;
+mod hold_item;
+mod use_item;
+mod view_inventory;
+
+pub fn get_all_commands() -> Vec<Command<Data, Error>> {
+    vec![
+        view_inventory::inventory(),
+        give_item::give_item(),
+        drop_item::drop_item(),
+        use_item::use_item(),
+        hold_item::hold_item(),
+    ]
+}
+
+pub struct InventoryEntry {
+    pub name: String,
+    pub amount: i64,
+}
+
+pub fn find_item<'a>(game_data: &'a GameData, name: &str) -> Result<&'a Item, ParseError> {
+    match game_data.items.get(&name.to_lowercase()) {
+        Some(item) => Ok(item),
+        None => Err(ParseError::new(format!(
+            "Unable to find an item named **{}**, sorry!",
+            name
+        ))),
+    }
+}
+
+pub async fn get_inventory(database: &Pool<Sqlite>, character_id: i64) -> Vec<InventoryEntry> {
+    match sqlx::query_as!(
+        InventoryEntry,
+        "SELECT item_name as name, amount FROM character_item WHERE character_id = ? ORDER BY item_name",
+        character_id
+    )
+    .fetch_all(database)
+    .await
+    {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::error!(
+                "Was unable to load inventory for character {}: {}",
+                character_id,
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Single use items stack within one slot, everything else needs one slot per piece.
+fn slots_required(game_data: &GameData, item_name: &str, amount: i64) -> i64 {
+    match game_data.items.get(&item_name.to_lowercase()) {
+        Some(item) if item.single_use => {
+            if amount > 0 {
+                1
+            } else {
+                0
+            }
+        }
+        _ => amount,
+    }
+}
+
+pub fn count_used_slots(game_data: &GameData, inventory: &[InventoryEntry]) -> i64 {
+    inventory
+        .iter()
+        .map(|x| slots_required(game_data, &x.name, x.amount))
+        .sum()
+}
+
+pub fn calculate_backpack_slots(backpack_upgrade_count: i64) -> i64 {
+    DEFAULT_BACKPACK_SLOTS + backpack_upgrade_count
+}
+
+pub fn build_inventory_list(inventory: &[InventoryEntry]) -> String {
+    let mut result = String::new();
+    for x in inventory {
+        if x.amount > 1 {
+            result.push_str(&format!("- {}x {}\n", x.amount, x.name));
+        } else {
+            result.push_str(&format!("- {}\n", x.name));
+        }
+    }
+
+    result
+}
+
+pub async fn add_item_to_inventory(
+    data: &Data,
+    character: &CharacterCacheItem,
+    item: &Item,
+    amount: i64,
+) -> Result<(), Error> {
+    let mut transaction = data.database.begin().await?;
+    add_item_to_inventory_in_transaction(&mut transaction, &data.game, character, item, amount)
+        .await?;
+    transaction.commit().await?;
+    Ok(())
+}
+
+pub async fn add_item_to_inventory_in_transaction(
+    connection: &mut SqliteConnection,
+    game_data: &GameData,
+    character: &CharacterCacheItem,
+    item: &Item,
+    amount: i64,
+) -> Result<(), Error> {
+    let record = sqlx::query!(
+        "SELECT backpack_upgrade_count FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&mut *connection)
+    .await?;
+
+    let inventory = sqlx::query_as!(
+        InventoryEntry,
+        "SELECT item_name as name, amount FROM character_item WHERE character_id = ?",
+        character.id
+    )
+    .fetch_all(&mut *connection)
+    .await?;
+
+    let current_amount = inventory
+        .iter()
+        .find(|x| x.name.to_lowercase() == item.name.to_lowercase())
+        .map(|x| x.amount)
+        .unwrap_or(0);
+
+    let available_slots = calculate_backpack_slots(record.backpack_upgrade_count);
+    let used_slots = count_used_slots(game_data, &inventory);
+    let additional_slots = slots_required(game_data, &item.name, current_amount + amount)
+        - slots_required(game_data, &item.name, current_amount);
+
+    if used_slots + additional_slots > available_slots {
+        return Err(Box::new(ValidationError::new(format!(
+            "**Unable to add {}x {} to {}'s backpack.**\n*That would require {} free slots, but only {} out of {} slots are free.*",
+            amount,
+            item.name,
+            character.name,
+            additional_slots,
+            available_slots - used_slots,
+            available_slots,
+        ))));
+    }
+
+    sqlx::query!(
+        "INSERT INTO character_item (character_id, item_name, amount) VALUES (?, ?, ?) \
+         ON CONFLICT (character_id, item_name) DO UPDATE SET amount = amount + excluded.amount",
+        character.id,
+        item.name,
+        amount
+    )
+    .execute(&mut *connection)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn remove_item_from_inventory(
+    data: &Data,
+    character: &CharacterCacheItem,
+    item: &Item,
+    amount: i64,
+) -> Result<(), Error> {
+    let mut transaction = data.database.begin().await?;
+    remove_item_from_inventory_in_transaction(&mut transaction, character, item, amount).await?;
+    transaction.commit().await?;
+    Ok(())
+}
+
+pub async fn remove_item_from_inventory_in_transaction(
+    connection: &mut SqliteConnection,
+    character: &CharacterCacheItem,
+    item: &Item,
+    amount: i64,
+) -> Result<(), Error> {
+    let current_amount = sqlx::query!(
+        "SELECT amount FROM character_item WHERE character_id = ? AND item_name = ?",
+        character.id,
+        item.name
+    )
+    .fetch_optional(&mut *connection)
+    .await?
+    .map(|x| x.amount)
+    .unwrap_or(0);
+
+    if current_amount < amount {
+        return Err(Box::new(ValidationError::new(format!(
+            "**Unable to remove {}x {} from {}'s backpack.**\n*{} only owns {}.*",
+            amount, item.name, character.name, character.name, current_amount
+        ))));
+    }
+
+    if current_amount == amount {
+        sqlx::query!(
+            "DELETE FROM character_item WHERE character_id = ? AND item_name = ?",
+            character.id,
+            item.name
+        )
+        .execute(&mut *connection)
+        .await?;
+    } else {
+        let new_amount = current_amount - amount;
+        sqlx::query!(
+            "UPDATE character_item SET amount = ? WHERE character_id = ? AND item_name = ?",
+            new_amount,
+            character.id,
+            item.name
+        )
+        .execute(&mut *connection)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Puts the item into the character's hands and moves the previously held item back into their backpack.
+/// Returns the name of the previously held item.
+pub async fn change_held_item(
+    data: &Data,
+    character: &CharacterCacheItem,
+    item: Option<&Item>,
+) -> Result<Option<String>, Error> {
+    let mut transaction = data.database.begin().await?;
+    let previous = sqlx::query!("SELECT held_item FROM character WHERE id = ?", character.id)
+        .fetch_one(&mut *transaction)
+        .await?
+        .held_item;
+
+    match (&previous, item) {
+        (Some(previous), Some(item)) if previous.eq_ignore_ascii_case(&item.name) => {
+            return Err(Box::new(ValidationError::new(format!(
+                "**{} is already holding {}.**",
+                character.name, item.name
+            ))));
+        }
+        (None, None) => {
+            return Err(Box::new(ValidationError::new(format!(
+                "**{} isn't holding anything right now.**",
+                character.name
+            ))));
+        }
+        _ => {}
+    }
+
+    if let Some(item) = item {
+        remove_item_from_inventory_in_transaction(&mut transaction, character, item, 1).await?;
+    }
+    if let Some(previous) = &previous {
+        let previous = find_item(&data.game, previous)?;
+        add_item_to_inventory_in_transaction(&mut transaction, &data.game, character, previous, 1)
+            .await?;
+    }
+
+    let held_item = item.map(|x| x.name.as_str());
+    sqlx::query!(
+        "UPDATE character SET held_item = ? WHERE id = ?",
+        held_item,
+        character.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+    Ok(previous)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::characters::DEFAULT_BACKPACK_SLOTS;
+    use crate::commands::inventory::{
+        add_item_to_inventory, get_inventory, remove_item_from_inventory,
+    };
+    use crate::game_data::item::Item;
+    use crate::{database_helpers, Error};
+    use sqlx::{Pool, Sqlite};
+
+    fn test_item() -> Item {
+        Item {
+            name: String::from("Test Rock"),
+            price: None,
+            description: String::new(),
+            category: String::new(),
+            single_use: false,
+            health_restored: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn adding_items_should_respect_backpack_slots(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_helpers::create_mock::data(db).await;
+        let guild_id = 100;
+        let user_id = 200;
+        let character_id = 300;
+        let item = test_item();
+
+        database_helpers::create_mock::guild(&data.database, guild_id).await;
+        database_helpers::create_mock::user(&data.database, user_id).await;
+        database_helpers::create_mock::character(
+            &data,
+            guild_id,
+            user_id,
+            character_id,
+            &String::from("Test"),
+        )
+        .await;
+        let character = data.cache.get_character(character_id).await.unwrap();
+
+        add_item_to_inventory(&data, &character, &item, DEFAULT_BACKPACK_SLOTS).await?;
+        let result = add_item_to_inventory(&data, &character, &item, 1).await;
+        assert!(result.is_err());
+
+        let inventory = get_inventory(&data.database, character_id).await;
+        assert_eq!(1, inventory.len());
+        assert_eq!(DEFAULT_BACKPACK_SLOTS, inventory[0].amount);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn removing_items_should_work(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_helpers::create_mock::data(db).await;
+        let guild_id = 100;
+        let user_id = 200;
+        let character_id = 300;
+        let item = test_item();
+
+        database_helpers::create_mock::guild(&data.database, guild_id).await;
+        database_helpers::create_mock::user(&data.database, user_id).await;
+        database_helpers::create_mock::character(
+            &data,
+            guild_id,
+            user_id,
+            character_id,
+            &String::from("Test"),
+        )
+        .await;
+        let character = data.cache.get_character(character_id).await.unwrap();
+
+        add_item_to_inventory(&data, &character, &item, 2).await?;
+        assert!(remove_item_from_inventory(&data, &character, &item, 3)
+            .await
+            .is_err());
+
+        remove_item_from_inventory(&data, &character, &item, 2).await?;
+        assert!(get_inventory(&data.database, character_id).await.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use crate::commands::autocompletion::{autocomplete_item, autocomplete_owned_character_name};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::inventory::{find_item, remove_item_from_inventory};
+use crate::commands::{
+    ensure_user_owns_character, find_character, update_character_post, Context, Error,
+};
+use crate::errors::ValidationError;
+
+/// Use a single use item from your backpack.
+#[poise::command(slash_command, guild_only)]
+pub async fn use_item(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+    let item = find_item(&ctx.data().game, &item)?;
+
+    if !item.single_use {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} isn't used up when being used, so there's no need to track that. Just use it!",
+            item.name
+        ))));
+    }
+
+    remove_item_from_inventory(ctx.data(), &character, item, 1).await?;
+
+    let mut message = format!("{} used a {}!", character.name, item.name);
+    if let Some(health_restored) = item.health_restored {
+        message.push_str(&format!(" *(Restores {} HP)*", health_restored));
+    }
+
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::ItemUse,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, Some(-1)),
+    )
+    .await
+}
@@ -0,0 +1,51 @@
+use crate::commands::autocompletion::autocomplete_character_name;
+use crate::commands::inventory::{
+    build_inventory_list, calculate_backpack_slots, count_used_slots, get_inventory,
+};
+use crate::commands::{find_character, Context, Error};
+use crate::emoji;
+
+/// Take a look inside a character's backpack.
+#[poise::command(slash_command, guild_only)]
+pub async fn inventory(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+
+    let record = sqlx::query!(
+        "SELECT backpack_upgrade_count FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let inventory = get_inventory(&ctx.data().database, character.id).await;
+    let used_slots = count_used_slots(&ctx.data().game, &inventory);
+    let available_slots = calculate_backpack_slots(record.backpack_upgrade_count);
+
+    let content = if inventory.is_empty() {
+        format!(
+            "### {} {}'s Backpack `{}/{}`\n*It's empty!*",
+            emoji::BACKPACK,
+            character.name,
+            used_slots,
+            available_slots
+        )
+    } else {
+        format!(
+            "### {} {}'s Backpack `{}/{}`\n{}",
+            emoji::BACKPACK,
+            character.name,
+            used_slots,
+            available_slots,
+            build_inventory_list(&inventory)
+        )
+    };
+
+    ctx.say(content).await?;
+    Ok(())
+}
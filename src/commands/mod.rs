@@ -5,13 +5,14 @@ use serenity::all::{ChannelId, CreateActionRow, EditMessage, Message, MessageId}
 use serenity::model::guild::Member;
 use serenity::model::id::{GuildId, UserId};
 use serenity::model::prelude::User;
+use sqlx::{Pool, Sqlite};
 
 use crate::cache::{CharacterCacheItem, WalletCacheItem};
 use crate::commands::characters::build_character_string;
 use crate::data::Data;
 use crate::errors::{ParseError, ValidationError};
 use crate::game_data::pokemon::Pokemon;
-use crate::{helpers, Error};
+use crate::{helpers, ledger, Error};
 
 type Context<'a> = poise::Context<'a, Data, Error>;
 
@@ -21,6 +22,7 @@ pub mod ability;
 pub mod about;
 pub mod calculate_hp_damage_modifier;
 pub mod create_emojis;
+mod create_auction;
 mod create_role_reaction_post;
 pub mod efficiency;
 pub mod encounter;
@@ -42,10 +44,17 @@ pub mod weather;
 pub mod open_box;
 pub mod season;
 pub mod list_characters;
+mod guild_info;
+mod history;
 
 pub mod characters;
+mod companions;
+pub mod health;
+pub mod inventory;
+mod moves;
 mod pin_or_unpin;
 mod player_info;
+mod progression_settings;
 mod prune_emojis;
 mod quests;
 mod say;
@@ -53,14 +62,17 @@ mod server_stats;
 mod setting_time_offset;
 mod setup_emoji_guild;
 mod setup_guild;
+mod shops;
 mod store_gm_experience;
+mod teams;
 mod use_gm_experience;
-mod wallets;
+pub mod wallets;
 
 pub fn get_all_commands() -> Vec<Command<Data, Error>> {
     let mut result = vec![
         setup_guild::setup_guild(),
         setup_emoji_guild::setup_emoji_guild(),
+        progression_settings::progression_settings(),
         //roll::roll(),
         //roll::r(),
         //r#move::poke_move(),
@@ -96,7 +108,10 @@ pub fn get_all_commands() -> Vec<Command<Data, Error>> {
         use_gm_experience::use_gm_experience(),
         //open_box::open_box(),
         season::season(),
-        list_characters::list_characters()
+        list_characters::list_characters(),
+        history::history(),
+        create_auction::create_auction(),
+        guild_info::guild_info(),
     ];
 
     for x in characters::get_all_commands() {
@@ -105,9 +120,27 @@ pub fn get_all_commands() -> Vec<Command<Data, Error>> {
     for x in wallets::get_all_commands() {
         result.push(x);
     }
+    for x in health::get_all_commands() {
+        result.push(x);
+    }
+    for x in inventory::get_all_commands() {
+        result.push(x);
+    }
+    for x in moves::get_all_commands() {
+        result.push(x);
+    }
+    for x in shops::get_all_commands() {
+        result.push(x);
+    }
     for x in quests::get_all_commands() {
         result.push(x);
     }
+    for x in companions::get_all_commands() {
+        result.push(x);
+    }
+    for x in teams::get_all_commands() {
+        result.push(x);
+    }
     // Print the list of all registered commands
     println!("Registered Commands:");
     for command in &result {
@@ -130,6 +163,19 @@ pub async fn send_ephemeral_reply<'a>(
         .await
 }
 
+/// Replies with a button which allows reverting all ledger entries created by this command.
+pub async fn reply_with_undo_button<'a>(
+    ctx: &Context<'a>,
+    content: impl Into<String>,
+) -> Result<ReplyHandle<'a>, serenity::Error> {
+    ctx.send(
+        CreateReply::default()
+            .content(content)
+            .components(vec![ledger::create_undo_button(ctx.id())]),
+    )
+    .await
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn parse_variadic_args<T>(
     arg1: T,
@@ -335,14 +381,14 @@ pub async fn ensure_character_has_money(
 }
 
 pub async fn ensure_wallet_has_money(
-    data: &Data,
+    database: &Pool<Sqlite>,
     wallet: &WalletCacheItem,
     amount: i64,
     verb: &str,
 ) -> Result<(), ValidationError> {
     let record = sqlx::query_as("SELECT money FROM wallet WHERE id = ?")
         .bind(wallet.id)
-        .fetch_one(&data.database)
+        .fetch_one(database)
         .await;
 
     ensure_money_record_has_money(&wallet.name, amount, verb, record)
@@ -389,6 +435,19 @@ pub fn ensure_user_owns_character(
     }
 }
 
+pub async fn ensure_user_owns_character_or_is_gm<'a>(
+    ctx: &Context<'a>,
+    character: &CharacterCacheItem,
+) -> Result<(), ValidationError> {
+    if let Some(member) = ctx.author_member().await {
+        if is_user_admin_or_gm(member) {
+            return Ok(());
+        }
+    }
+
+    ensure_user_owns_character(ctx.author(), character)
+}
+
 // TODO: Technically this should be persisted in the database and configurable on a per-server basis, but... as long as only one server uses the bot, who cares...? :D
 const ADMIN_ROLE_ID: u64 = 1113123557292134480;
 const GM_ROLE_ID: u64 = 1114261188323319878;
@@ -0,0 +1,36 @@
+use crate::commands::autocompletion::{autocomplete_move, autocomplete_owned_character_name};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::moves::find_move;
+use crate::commands::{
+    ensure_user_owns_character, find_character, update_character_post, Context, Error,
+};
+
+/// Remove a move from your character's moveset.
+#[poise::command(slash_command, guild_only)]
+pub async fn forget_move(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Which move?"]
+    #[autocomplete = "autocomplete_move"]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+    let m = find_move(&ctx.data().game, &name)?;
+
+    super::forget_move(&ctx.data().database, character.id, m).await?;
+
+    let message = format!("{} forgot {}.", character.name, m.name);
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::MoveForgotten,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
@@ -0,0 +1,59 @@
+use crate::commands::autocompletion::{autocomplete_move, autocomplete_owned_character_name};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::moves::{calculate_move_limit, find_move, validate_move_is_learnable};
+use crate::commands::{
+    ensure_user_owns_character, find_character, update_character_post, Context, Error,
+};
+use crate::game_data::PokemonApiId;
+use crate::guild_settings::GuildSettings;
+use crate::helpers;
+
+/// Teach your character a new move.
+#[poise::command(slash_command, guild_only)]
+pub async fn learn_move(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Which move?"]
+    #[autocomplete = "autocomplete_move"]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+    let m = find_move(&ctx.data().game, &name)?;
+
+    let record = sqlx::query!(
+        "SELECT species_api_id, active_form_api_id, experience, stat_insight FROM character WHERE id = ?",
+        character.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let pokemon = ctx
+        .data()
+        .game
+        .pokemon_by_api_id
+        .get(&PokemonApiId(record.species_api_id as u16))
+        .expect("DB IDs should always be mappable.");
+    let form = helpers::get_active_form(pokemon, &ctx.data().game, record.active_form_api_id);
+    let settings = GuildSettings::load(&ctx.data().database, guild_id as i64).await;
+    let level = settings.calculate_level_from_experience(record.experience);
+    let rank = settings.calculate_rank(level);
+    validate_move_is_learnable(form, rank, m)?;
+
+    let move_limit = calculate_move_limit(record.stat_insight, settings.move_limit_bonus);
+    super::learn_move(&ctx.data().database, character.id, m, move_limit).await?;
+
+    let message = format!("{} learned {}!", character.name, m.name);
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    log_action_with_target(
+        &ActionType::MoveLearned,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
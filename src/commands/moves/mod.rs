@@ -0,0 +1,251 @@
+use poise::Command;
+use sqlx::{Pool, Sqlite};
+
+use crate::data::Data;
+use crate::enums::MysteryDungeonRank;
+use crate::errors::{ParseError, ValidationError};
+use crate::game_data::pokemon::{Pokemon, PokemonMoveLearnedByRank};
+use crate::game_data::r#move::Move;
+use crate::game_data::GameData;
+use crate::Error;
+
+mod forget_move;
+mod learn_move;
+
+pub fn get_all_commands() -> Vec<Command<Data, Error>> {
+    vec![learn_move::learn_move(), forget_move::forget_move()]
+}
+
+pub fn find_move<'a>(game_data: &'a GameData, name: &str) -> Result<&'a Move, ParseError> {
+    match game_data
+        .moves
+        .get(&strip_pre_evolution_marker(name).to_lowercase())
+    {
+        Some(m) => Ok(m),
+        None => Err(ParseError::new(format!(
+            "Unable to find a move named **{}**, sorry!",
+            name
+        ))),
+    }
+}
+
+/// Moves inherited from pre-evolutions are flagged with ¹ or ² in the species' move list.
+fn strip_pre_evolution_marker(name: &str) -> &str {
+    name.trim_end_matches('¹').trim_end_matches('²')
+}
+
+/// Returns the rank move list entry for the given move, including the ones inherited from pre-evolutions.
+pub fn find_rank_learnable_move<'a>(
+    pokemon: &'a Pokemon,
+    move_name: &str,
+) -> Option<&'a PokemonMoveLearnedByRank> {
+    pokemon
+        .moves
+        .by_pokerole_rank
+        .iter()
+        .find(|x| strip_pre_evolution_marker(&x.name).eq_ignore_ascii_case(move_name))
+}
+
+pub fn validate_move_is_learnable(
+    pokemon: &Pokemon,
+    rank: MysteryDungeonRank,
+    m: &Move,
+) -> Result<(), ValidationError> {
+    match find_rank_learnable_move(pokemon, &m.name) {
+        Some(entry) if entry.rank <= rank => Ok(()),
+        Some(entry) => Err(ValidationError::new(format!(
+            "**{} can only learn {} once they've reached {}.**",
+            pokemon.name, m.name, entry.rank
+        ))),
+        None => Err(ValidationError::new(format!(
+            "**{} is unable to learn {}.**\n*Use `/learns` to see which moves are available.*",
+            pokemon.name, m.name
+        ))),
+    }
+}
+
+pub fn calculate_move_limit(insight: i64, move_limit_bonus: i64) -> i64 {
+    insight + move_limit_bonus
+}
+
+pub async fn get_known_moves(database: &Pool<Sqlite>, character_id: i64) -> Vec<String> {
+    match sqlx::query!(
+        "SELECT move_name FROM character_move WHERE character_id = ? ORDER BY move_name",
+        character_id
+    )
+    .fetch_all(database)
+    .await
+    {
+        Ok(records) => records.into_iter().map(|x| x.move_name).collect(),
+        Err(e) => {
+            log::error!(
+                "Was unable to load moves for character {}: {}",
+                character_id,
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
+pub fn build_move_list(game_data: &GameData, pokemon: &Pokemon, known_moves: &[String]) -> String {
+    let mut result = String::new();
+    for name in known_moves {
+        let display_name = match find_rank_learnable_move(pokemon, name) {
+            Some(entry) => entry.name.as_str(),
+            None => name.as_str(),
+        };
+
+        let Some(m) = game_data.moves.get(&name.to_lowercase()) else {
+            result.push_str(&format!("- **{}**\n", display_name));
+            continue;
+        };
+
+        let mut dice = Vec::new();
+        if let Some(accuracy) = m.build_accuracy_dice_string() {
+            dice.push(format!("ACC: {}", accuracy));
+        }
+        if let Some(damage) = m.build_damage_dice_string() {
+            dice.push(format!("DMG: {}", damage));
+        }
+
+        if dice.is_empty() {
+            result.push_str(&format!("- **{}**\n", display_name));
+        } else {
+            result.push_str(&format!("- **{}** — {}\n", display_name, dice.join(" | ")));
+        }
+    }
+
+    result
+}
+
+/// Adds the move to the character's moveset, unless they already know it or have no space left.
+pub async fn learn_move(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+    m: &Move,
+    move_limit: i64,
+) -> Result<(), Error> {
+    let mut transaction = database.begin().await?;
+
+    let known_moves = sqlx::query!(
+        "SELECT move_name FROM character_move WHERE character_id = ?",
+        character_id
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    if known_moves
+        .iter()
+        .any(|x| x.move_name.eq_ignore_ascii_case(&m.name))
+    {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} is already known.**",
+            m.name
+        ))));
+    }
+
+    if known_moves.len() as i64 >= move_limit {
+        return Err(Box::new(ValidationError::new(format!(
+            "**Unable to learn {}.**\n*Only {} moves can be known at once. Forget one first.*",
+            m.name, move_limit
+        ))));
+    }
+
+    sqlx::query!(
+        "INSERT INTO character_move (character_id, move_name) VALUES (?, ?)",
+        character_id,
+        m.name
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+pub async fn forget_move(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+    m: &Move,
+) -> Result<(), Error> {
+    let result = sqlx::query!(
+        "DELETE FROM character_move WHERE character_id = ? AND move_name = ?",
+        character_id,
+        m.name
+    )
+    .execute(database)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} isn't known right now.**",
+            m.name
+        ))));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::moves::{forget_move, get_known_moves, learn_move};
+    use crate::enums::{MoveCategory, MoveType};
+    use crate::game_data::r#move::Move;
+    use crate::{database_helpers, Error};
+    use sqlx::{Pool, Sqlite};
+
+    fn create_move(name: &str) -> Move {
+        Move {
+            name: String::from(name),
+            typing: MoveType::Normal,
+            power: 2,
+            damage1: None,
+            happiness_damage: None,
+            accuracy1: None,
+            accuracy2: None,
+            target: String::new(),
+            effect: None,
+            description: None,
+            category: MoveCategory::Physical,
+        }
+    }
+
+    #[sqlx::test]
+    async fn learning_moves_should_respect_move_limit(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_helpers::create_mock::data(db).await;
+        let guild_id = 100;
+        let user_id = 200;
+        let character_id = 300;
+        let tackle = create_move("Tackle");
+        let growl = create_move("Growl");
+
+        database_helpers::create_mock::guild(&data.database, guild_id).await;
+        database_helpers::create_mock::user(&data.database, user_id).await;
+        database_helpers::create_mock::character(
+            &data,
+            guild_id,
+            user_id,
+            character_id,
+            &String::from("Test"),
+        )
+        .await;
+
+        learn_move(&data.database, character_id, &tackle, 1).await?;
+        assert!(learn_move(&data.database, character_id, &tackle, 2)
+            .await
+            .is_err());
+        assert!(learn_move(&data.database, character_id, &growl, 1)
+            .await
+            .is_err());
+
+        forget_move(&data.database, character_id, &tackle).await?;
+        learn_move(&data.database, character_id, &growl, 1).await?;
+        assert_eq!(
+            vec![String::from("Growl")],
+            get_known_moves(&data.database, character_id).await
+        );
+
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ use crate::commands::{Context, Error};
 use crate::data::Data;
 use crate::errors::DatabaseError;
 use crate::game_data::PokemonApiId;
+use crate::guild_settings::GuildSettings;
 use crate::helpers::split_long_messages;
 use crate::{emoji, helpers};
 
@@ -31,8 +32,10 @@ pub async fn player_info(
 
     match characters {
         Ok(characters) => {
+            let settings = GuildSettings::load(&ctx.data().database, guild_id).await;
             let reply = build_reply(
                 ctx.data(),
+                &settings,
                 &user_in_guild,
                 characters,
                 hosted_quest_count,
@@ -82,6 +85,7 @@ async fn query_gm_experience(ctx: &Context<'_>, user_id: i64, guild_id: i64) ->
 
 async fn build_reply(
     data: &Data,
+    settings: &GuildSettings,
     user_in_guild: &Member,
     characters: Vec<QueryObject>,
     hosted_quest_count: Option<i64>,
@@ -94,9 +98,9 @@ async fn build_reply(
     for character in characters {
         total_exp += character.experience;
 
-        let character_level = helpers::calculate_level_from_experience(character.experience);
+        let character_level = settings.calculate_level_from_experience(character.experience);
         total_levels += character_level;
-        let current_exp = helpers::calculate_current_experience(character.experience);
+        let current_exp = settings.calculate_current_experience(character.experience);
 
         let channel_id = ChannelId::new(character.stat_channel_id as u64);
         let api_id = PokemonApiId(character.species_api_id as u16);
@@ -0,0 +1,128 @@
+use crate::commands::characters::{log_action, ActionType};
+use crate::commands::{ensure_guild_exists, send_ephemeral_reply, Context, Error};
+use crate::enums::TeraChargeReset;
+use crate::errors::ValidationError;
+use crate::guild_settings::GuildSettings;
+
+/// View or change this server's progression rules. All arguments are optional.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn progression_settings(
+    ctx: Context<'_>,
+    #[description = "How much experience is needed per level?"]
+    #[min = 1_i64]
+    experience_per_level: Option<i64>,
+    #[description = "At which level do characters reach Silver Rank?"]
+    #[min = 2_i64]
+    silver_rank_level: Option<i64>,
+    #[description = "At which level do characters reach Gold Rank?"]
+    #[min = 2_i64]
+    gold_rank_level: Option<i64>,
+    #[description = "At which level do characters reach Platinum Rank?"]
+    #[min = 2_i64]
+    platinum_rank_level: Option<i64>,
+    #[description = "At which level do characters reach Diamond Rank?"]
+    #[min = 2_i64]
+    diamond_rank_level: Option<i64>,
+    #[description = "At which level do characters reach Master Rank?"]
+    #[min = 2_i64]
+    master_rank_level: Option<i64>,
+    #[description = "How many combat stat points do characters have at level 1?"]
+    #[min = 0_i64]
+    base_combat_points: Option<i64>,
+    #[description = "How many combat stat points do characters gain per level?"]
+    #[min = 0_i64]
+    combat_points_per_level: Option<i64>,
+    #[description = "How many social stat points do characters have at Bronze Rank?"]
+    #[min = 0_i64]
+    base_social_points: Option<i64>,
+    #[description = "How many social stat points do characters gain per rank?"]
+    #[min = 0_i64]
+    social_points_per_rank: Option<i64>,
+    #[description = "How many moves can characters learn in addition to their Insight?"]
+    #[min = 0_i64]
+    move_limit_bonus: Option<i64>,
+    #[description = "How much does the first backpack upgrade cost?"]
+    #[min = 0_i64]
+    backpack_upgrade_base_price: Option<i64>,
+    #[description = "By how much does the price increase with every backpack upgrade?"]
+    #[min = 0_i64]
+    backpack_upgrade_price_increase: Option<i64>,
+    #[description = "When should used Terastallization charges be restored?"]
+    tera_charge_reset: Option<TeraChargeReset>,
+    #[description = "Which percentage of shop purchases goes to the guild treasury?"]
+    #[min = 0_i64]
+    #[max = 100_i64]
+    shop_tax_percentage: Option<i64>,
+    #[description = "Which percentage of backpack upgrade prices goes to the guild treasury?"]
+    #[min = 0_i64]
+    #[max = 100_i64]
+    backpack_upgrade_fee_percentage: Option<i64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let old_settings = GuildSettings::load(&ctx.data().database, guild_id).await;
+
+    let settings = GuildSettings {
+        experience_per_level: experience_per_level.unwrap_or(old_settings.experience_per_level),
+        silver_rank_level: silver_rank_level.unwrap_or(old_settings.silver_rank_level),
+        gold_rank_level: gold_rank_level.unwrap_or(old_settings.gold_rank_level),
+        platinum_rank_level: platinum_rank_level.unwrap_or(old_settings.platinum_rank_level),
+        diamond_rank_level: diamond_rank_level.unwrap_or(old_settings.diamond_rank_level),
+        master_rank_level: master_rank_level.unwrap_or(old_settings.master_rank_level),
+        base_combat_points: base_combat_points.unwrap_or(old_settings.base_combat_points),
+        combat_points_per_level: combat_points_per_level
+            .unwrap_or(old_settings.combat_points_per_level),
+        base_social_points: base_social_points.unwrap_or(old_settings.base_social_points),
+        social_points_per_rank: social_points_per_rank
+            .unwrap_or(old_settings.social_points_per_rank),
+        backpack_upgrade_base_price: backpack_upgrade_base_price
+            .unwrap_or(old_settings.backpack_upgrade_base_price),
+        backpack_upgrade_price_increase: backpack_upgrade_price_increase
+            .unwrap_or(old_settings.backpack_upgrade_price_increase),
+        tera_charge_reset: tera_charge_reset.unwrap_or(old_settings.tera_charge_reset),
+        shop_tax_percentage: shop_tax_percentage.unwrap_or(old_settings.shop_tax_percentage),
+        backpack_upgrade_fee_percentage: backpack_upgrade_fee_percentage
+            .unwrap_or(old_settings.backpack_upgrade_fee_percentage),
+        move_limit_bonus: move_limit_bonus.unwrap_or(old_settings.move_limit_bonus),
+    };
+
+    if settings == old_settings {
+        send_ephemeral_reply(
+            &ctx,
+            &format!("## Progression Settings\n{}", settings.build_string()),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if let Err(e) = settings.validate() {
+        return Err(Box::new(ValidationError::new(format!(
+            "**Unable to apply these settings.**\n*{}*",
+            e
+        ))));
+    }
+
+    ensure_guild_exists(&ctx, guild_id).await;
+    settings.save(&ctx.data().database, guild_id).await?;
+
+    send_ephemeral_reply(
+        &ctx,
+        &format!(
+            "## Updated Progression Settings\n{}\n*Character posts will reflect these changes once they get updated the next time.*",
+            settings.build_string()
+        ),
+    )
+    .await?;
+    log_action(
+        &ActionType::SettingsChange,
+        &ctx,
+        "Progression settings have been updated.",
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,10 +1,17 @@
 use chrono::Utc;
+use sqlx::SqliteConnection;
 
+use crate::cache::CharacterCacheItem;
 use crate::commands::autocompletion::autocomplete_character_name;
-use crate::commands::characters::build_character_list;
+use crate::commands::characters::{
+    award_achievements, build_character_list, log_action_with_target, ActionLogTarget, ActionType,
+};
+use crate::commands::teams::{get_team_ids_for_characters, update_team_post};
 use crate::commands::{
     parse_character_names, parse_variadic_args, send_error, update_character_post, Context, Error,
 };
+use crate::guild_treasury::GUILD_TREASURY_NAME;
+use crate::{emoji, guild_treasury};
 
 /// Use this to increase the quest completion counter.
 #[allow(clippy::too_many_arguments)]
@@ -26,6 +33,9 @@ pub async fn complete_quest(
     #[autocomplete = "autocomplete_character_name"] character7: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character8: Option<String>,
     #[autocomplete = "autocomplete_character_name"] character9: Option<String>,
+    #[description = "Optional. Money paid to every character out of the guild treasury."]
+    #[min = 1_u32]
+    money_reward: Option<u32>,
 ) -> Result<(), Error> {
     let args = parse_variadic_args(
         character1, character2, character3, character4, character5, character6, character7,
@@ -48,33 +58,121 @@ pub async fn complete_quest(
     }
 
     let characters = parse_character_names(&ctx, guild_id.get(), &args).await?;
+
+    let mut transaction = ctx.data().database.begin().await?;
     let timestamp = Utc::now().timestamp();
     sqlx::query!(
         "UPDATE quest SET completion_timestamp = ? WHERE channel_id = ?",
         timestamp,
         channel_id
     )
-    .execute(&ctx.data().database)
+    .execute(&mut *transaction)
     .await?;
 
+    let mut newly_completed = Vec::new();
     for x in &characters {
-        sqlx::query!(
+        let result = sqlx::query!(
             "INSERT INTO quest_completion (quest_id, character_id) VALUES (?, ?) ON CONFLICT DO NOTHING",
             channel_id,
             x.id,
         )
-        .execute(&ctx.data().database)
+        .execute(&mut *transaction)
         .await?;
+        if result.rows_affected() > 0 {
+            newly_completed.push(x);
+        }
     }
 
-    ctx.say(format!(
-        "{} completed a quest!",
-        build_character_list(&characters)
-    ))
-    .await?;
+    // Characters who already completed this quest were paid back then.
+    let paid_characters = match money_reward {
+        Some(money_reward) if !newly_completed.is_empty() => {
+            pay_rewards_in_transaction(
+                &mut transaction,
+                guild_id.get() as i64,
+                &newly_completed,
+                money_reward as i64,
+            )
+            .await?;
+            newly_completed
+        }
+        _ => Vec::new(),
+    };
+    transaction.commit().await?;
+
+    let mut message = format!("{} completed a quest!", build_character_list(&characters));
+    if let Some(money_reward) = money_reward {
+        if paid_characters.is_empty() {
+            message.push_str(&format!(
+                "\nEveryone was already rewarded for this quest, so the {} didn't pay anything.",
+                GUILD_TREASURY_NAME
+            ));
+        } else {
+            message.push_str(&format!(
+                "\n{} received {} {} from the {}.",
+                paid_characters
+                    .iter()
+                    .map(|x| x.name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+                money_reward,
+                emoji::POKE_COIN,
+                GUILD_TREASURY_NAME
+            ));
+        }
 
+        for x in &paid_characters {
+            log_action_with_target(
+                &ActionType::Reward,
+                &ctx,
+                format!(
+                    "Added {} {} to {} from the {} for completing a quest",
+                    money_reward,
+                    emoji::POKE_COIN,
+                    x.name,
+                    GUILD_TREASURY_NAME
+                ),
+                ActionLogTarget::character(x.id, Some(money_reward as i64)),
+            )
+            .await?;
+        }
+    }
+    ctx.say(message).await?;
+
+    let character_ids = characters.iter().map(|x| x.id).collect::<Vec<i64>>();
     for x in characters {
         update_character_post(&ctx, x.id).await;
+        award_achievements(&ctx, x.id, &x.name).await;
+    }
+    for team_id in get_team_ids_for_characters(&ctx.data().database, &character_ids).await {
+        update_team_post(&ctx, team_id).await;
+    }
+
+    Ok(())
+}
+
+/// Pays every character the same reward. Either everyone gets paid, or nobody does.
+async fn pay_rewards_in_transaction(
+    connection: &mut SqliteConnection,
+    guild_id: i64,
+    characters: &[&CharacterCacheItem],
+    money_reward: i64,
+) -> Result<(), Error> {
+    for x in characters {
+        guild_treasury::withdraw_in_transaction(
+            &mut *connection,
+            guild_id,
+            money_reward,
+            Some(x.id),
+            "Quest reward",
+        )
+        .await?;
+        sqlx::query!(
+            "UPDATE character SET money = money + ? WHERE id = ?",
+            money_reward,
+            x.id
+        )
+        .execute(&mut *connection)
+        .await?;
     }
 
     Ok(())
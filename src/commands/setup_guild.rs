@@ -1,7 +1,7 @@
 use crate::commands::characters::{log_action, validate_user_input, ActionType};
 use crate::commands::{send_ephemeral_reply, Context, Error};
 use crate::errors::CommandInvocationError;
-use serenity::all::{Mention, Role, RoleId};
+use serenity::all::{ChannelId, Mention, Mentionable, Role, RoleId};
 use serenity::model::channel::Channel;
 
 /// Register this server within the database, or change values that have been set up earlier.
@@ -15,6 +15,10 @@ pub async fn setup_guild(
     name: Option<String>,
     action_log_channel: Option<Channel>,
     default_member_role: Option<Role>,
+    #[description = "Should a GM have to approve character adoptions?"]
+    adoption_requires_gm_approval: Option<bool>,
+    #[description = "Where should character submissions be posted for review?"]
+    character_review_channel: Option<Channel>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
 
@@ -26,18 +30,24 @@ pub async fn setup_guild(
     };
     let action_log_channel_id = action_log_channel.map(|x| x.id().get() as i64);
     let default_member_role_id = default_member_role.map(|x| x.id.get() as i64);
+    let character_review_channel_id = character_review_channel.map(|x| x.id().get() as i64);
 
     match sqlx::query!(
-        "INSERT INTO guild (id, name, action_log_channel_id, default_member_role_id) VALUES (?, ?, ?, ?)
+        "INSERT INTO guild (id, name, action_log_channel_id, default_member_role_id, adoption_requires_gm_approval, character_review_channel_id) VALUES (?, ?, ?, ?, COALESCE(?, false), ?)
 ON CONFLICT (id) DO UPDATE SET
     name = excluded.name,
     action_log_channel_id = excluded.action_log_channel_id,
-    default_member_role_id = excluded.default_member_role_id
+    default_member_role_id = excluded.default_member_role_id,
+    adoption_requires_gm_approval = COALESCE(?, guild.adoption_requires_gm_approval),
+    character_review_channel_id = COALESCE(excluded.character_review_channel_id, guild.character_review_channel_id)
 RETURNING *",
         guild_id,
         name,
         action_log_channel_id,
-        default_member_role_id
+        default_member_role_id,
+        adoption_requires_gm_approval,
+        character_review_channel_id,
+        adoption_requires_gm_approval
     )
     .fetch_one(&ctx.data().database)
     .await {
@@ -52,6 +62,23 @@ RETURNING *",
                 )
                     .await?;
             }
+            if record.adoption_requires_gm_approval {
+                log_action(
+                    &ActionType::Initialization,
+                    &ctx,
+                    "Character adoptions now require approval by a GM.",
+                )
+                    .await?;
+            }
+            if let Some(character_review_channel_id) = record.character_review_channel_id {
+                let channel = ChannelId::new(character_review_channel_id as u64);
+                log_action(
+                    &ActionType::Initialization,
+                    &ctx,
+                    format!("Character submissions will be reviewed in {}", channel.mention()),
+                )
+                    .await?;
+            }
             if let Some(default_member_role_id) = record.default_member_role_id {
                 let role = RoleId::new(default_member_role_id as u64);
                 let mention = Mention::Role(role);
@@ -0,0 +1,66 @@
+use crate::commands::autocompletion::{autocomplete_item, autocomplete_shop_name};
+use crate::commands::characters::{log_action, ActionType};
+use crate::commands::inventory::find_item;
+use crate::commands::shops::find_shop;
+use crate::commands::{send_error, Context, Error};
+use crate::emoji;
+
+/// Add an item to a shop, or change its price and stock.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn add_shop_item(
+    ctx: Context<'_>,
+    #[description = "Which shop?"]
+    #[autocomplete = "autocomplete_shop_name"]
+    shop: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+    #[description = "Optional. Overrides the item's default price."]
+    #[min = 0_i64]
+    price: Option<i64>,
+    #[description = "Optional. Leave empty for unlimited stock."]
+    #[min = 0_i64]
+    stock: Option<i64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let shop = find_shop(ctx.data(), guild_id, &shop).await?;
+    let item = find_item(&ctx.data().game, &item)?;
+
+    let Some(effective_price) = price.or(item.price.map(|x| x as i64)) else {
+        return send_error(
+            &ctx,
+            &format!("{} has no default price, please provide one.", item.name),
+        )
+        .await;
+    };
+
+    sqlx::query!(
+        "INSERT INTO shop_item (shop_id, item_name, price, stock) VALUES (?, ?, ?, ?) \
+         ON CONFLICT (shop_id, item_name) DO UPDATE SET price = excluded.price, stock = excluded.stock",
+        shop.id,
+        item.name,
+        price,
+        stock
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    let stock = match stock {
+        Some(stock) => format!("{} in stock", stock),
+        None => String::from("unlimited stock"),
+    };
+    let message = format!(
+        "{} now sells {} for {} {} ({}).",
+        shop.name,
+        item.name,
+        effective_price,
+        emoji::POKE_COIN,
+        stock
+    );
+    ctx.say(&message).await?;
+    log_action(&ActionType::Initialization, &ctx, &message).await
+}
@@ -0,0 +1,55 @@
+use crate::commands::autocompletion::autocomplete_wallet_name;
+use crate::commands::characters::{log_action, validate_user_input, ActionType};
+use crate::commands::{ensure_guild_exists, find_wallet, send_error, Context, Error};
+
+/// Create a new shop. Optionally, purchases can be paid into a wallet.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn create_shop(
+    ctx: Context<'_>,
+    #[description = "What name should we use?"] name: String,
+    #[description = "Optional. Which wallet receives the money?"]
+    #[autocomplete = "autocomplete_wallet_name"]
+    wallet: Option<String>,
+) -> Result<(), Error> {
+    if let Err(e) = validate_user_input(name.as_str()) {
+        return send_error(&ctx, e).await;
+    }
+
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let wallet = match wallet {
+        Some(wallet) => Some(find_wallet(ctx.data(), guild_id, &wallet).await?),
+        None => None,
+    };
+
+    let guild_id = guild_id as i64;
+    ensure_guild_exists(&ctx, guild_id).await;
+
+    let wallet_id = wallet.as_ref().map(|x| x.id);
+    let result = sqlx::query!(
+        "INSERT INTO shop (name, guild_id, wallet_id) VALUES (?, ?, ?)",
+        name,
+        guild_id,
+        wallet_id
+    )
+    .execute(&ctx.data().database)
+    .await;
+
+    if result.is_err() {
+        return send_error(
+            &ctx,
+            "Something went wrong! Does a shop with this name already exist on this server?",
+        )
+        .await;
+    }
+
+    let message = match wallet {
+        Some(wallet) => format!("Opened {}, which pays into {}.", name, wallet.name),
+        None => format!("Opened {}.", name),
+    };
+    ctx.say(&message).await?;
+    log_action(&ActionType::Initialization, &ctx, &message).await
+}
@@ -0,0 +1,411 @@
+use poise::Command;
+use sqlx::{Pool, Sqlite};
+
+use crate::cache::CharacterCacheItem;
+use crate::commands::inventory::{
+    add_item_to_inventory_in_transaction, remove_item_from_inventory_in_transaction,
+};
+use crate::data::Data;
+use crate::errors::{ParseError, ValidationError};
+use crate::game_data::item::Item;
+use crate::game_data::GameData;
+use crate::guild_settings::GuildSettings;
+use crate::wallet_statement::WalletTransaction;
+use crate::{emoji, guild_treasury, wallet_statement, Error};
+
+mod add_shop_item;
+mod buy_item;
+mod create_shop;
+mod remove_shop_item;
+mod sell_item;
+mod view_shop;
+
+/// Shops buy items back for this fraction of their selling price.
+const SELL_PRICE_DIVISOR: i64 = 2;
+
+pub fn get_all_commands() -> Vec<Command<Data, Error>> {
+    vec![
+        create_shop::create_shop(),
+        add_shop_item::add_shop_item(),
+        remove_shop_item::remove_shop_item(),
+        view_shop::shop(),
+        buy_item::buy_item(),
+        sell_item::sell_item(),
+    ]
+}
+
+pub struct Shop {
+    pub id: i64,
+    pub name: String,
+    pub wallet_id: Option<i64>,
+}
+
+pub struct ShopItem {
+    pub item_name: String,
+    pub price: Option<i64>,
+    pub stock: Option<i64>,
+}
+
+impl ShopItem {
+    /// Price overrides take precedence over the price listed in the item data.
+    pub fn get_price(&self, game_data: &GameData) -> Option<i64> {
+        self.price.or_else(|| {
+            game_data
+                .items
+                .get(&self.item_name.to_lowercase())
+                .and_then(|x| x.price)
+                .map(|x| x as i64)
+        })
+    }
+}
+
+pub async fn find_shop(data: &Data, guild_id: u64, name: &str) -> Result<Shop, ParseError> {
+    let guild_id = guild_id as i64;
+    let shop = sqlx::query_as!(
+        Shop,
+        "SELECT id, name, wallet_id FROM shop WHERE guild_id = ? AND name = ?",
+        guild_id,
+        name
+    )
+    .fetch_optional(&data.database)
+    .await;
+
+    match shop {
+        Ok(Some(shop)) => Ok(shop),
+        _ => Err(ParseError::new(format!(
+            "Unable to find a shop named {}",
+            name
+        ))),
+    }
+}
+
+pub async fn get_shop_items(database: &Pool<Sqlite>, shop_id: i64) -> Vec<ShopItem> {
+    match sqlx::query_as!(
+        ShopItem,
+        "SELECT item_name, price, stock FROM shop_item WHERE shop_id = ? ORDER BY item_name",
+        shop_id
+    )
+    .fetch_all(database)
+    .await
+    {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::error!("Was unable to load items for shop {}: {}", shop_id, e);
+            Vec::new()
+        }
+    }
+}
+
+pub fn build_shop_string(game_data: &GameData, shop: &Shop, items: &[ShopItem]) -> String {
+    let mut result = format!("## 🛒 {}\n", shop.name);
+    if items.is_empty() {
+        result.push_str("*This shop doesn't sell anything yet.*");
+        return result;
+    }
+
+    for x in items {
+        let price = match x.get_price(game_data) {
+            Some(price) => format!("{} {}", price, emoji::POKE_COIN),
+            None => String::from("???"),
+        };
+        match x.stock {
+            Some(stock) => {
+                result.push_str(&format!("- {} — {} ({} left)\n", x.item_name, price, stock))
+            }
+            None => result.push_str(&format!("- {} — {}\n", x.item_name, price)),
+        }
+    }
+
+    result
+}
+
+async fn get_shop_item(
+    connection: &mut sqlx::SqliteConnection,
+    shop: &Shop,
+    item: &Item,
+) -> Result<ShopItem, Error> {
+    let shop_item = sqlx::query_as!(
+        ShopItem,
+        "SELECT item_name, price, stock FROM shop_item WHERE shop_id = ? AND item_name = ?",
+        shop.id,
+        item.name
+    )
+    .fetch_optional(&mut *connection)
+    .await?;
+
+    match shop_item {
+        Some(shop_item) => Ok(shop_item),
+        None => Err(Box::new(ValidationError::new(format!(
+            "**{} doesn't trade in {}.**",
+            shop.name, item.name
+        )))),
+    }
+}
+
+/// Moves money from the character to the shop and the item into the character's backpack.
+/// The guild's shop tax is taken from the shop's share and goes to the guild treasury.
+/// Returns the total price paid.
+pub async fn execute_purchase(
+    data: &Data,
+    character: &CharacterCacheItem,
+    shop: &Shop,
+    item: &Item,
+    amount: i64,
+) -> Result<i64, Error> {
+    let guild_id = character.guild_id as i64;
+    let settings = GuildSettings::load(&data.database, guild_id).await;
+    let mut transaction = data.database.begin().await?;
+    let shop_item = get_shop_item(&mut transaction, shop, item).await?;
+
+    let Some(price) = shop_item.get_price(&data.game) else {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} has no price set for {}.**",
+            shop.name, item.name
+        ))));
+    };
+
+    if let Some(stock) = shop_item.stock {
+        if stock < amount {
+            return Err(Box::new(ValidationError::new(format!(
+                "**Unable to buy {}x {}.**\n*{} only has {} left in stock.*",
+                amount, item.name, shop.name, stock
+            ))));
+        }
+
+        let new_stock = stock - amount;
+        sqlx::query!(
+            "UPDATE shop_item SET stock = ? WHERE shop_id = ? AND item_name = ?",
+            new_stock,
+            shop.id,
+            shop_item.item_name
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    let total_price = price * amount;
+    let character_money = sqlx::query!("SELECT money FROM character WHERE id = ?", character.id)
+        .fetch_one(&mut *transaction)
+        .await?
+        .money;
+    if character_money < total_price {
+        return Err(Box::new(ValidationError::new(format!(
+            "**Unable to buy {}x {} for {} {}.**\n*{} only owns {} {}.*",
+            amount,
+            item.name,
+            total_price,
+            emoji::POKE_COIN,
+            character.name,
+            character_money,
+            emoji::POKE_COIN
+        ))));
+    }
+
+    sqlx::query!(
+        "UPDATE character SET money = money - ? WHERE id = ?",
+        total_price,
+        character.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    let tax = guild_treasury::calculate_tax(total_price, settings.shop_tax_percentage);
+    if tax > 0 {
+        guild_treasury::deposit_in_transaction(
+            &mut transaction,
+            guild_id,
+            tax,
+            Some(character.id),
+            &format!("Tax on {}x {} from {}", amount, item.name, shop.name),
+        )
+        .await?;
+    }
+
+    if let Some(wallet_id) = shop.wallet_id {
+        let shop_share = total_price - tax;
+        sqlx::query!(
+            "UPDATE wallet SET money = money + ? WHERE id = ?",
+            shop_share,
+            wallet_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+        wallet_statement::record(
+            &mut transaction,
+            WalletTransaction {
+                wallet_id,
+                amount: shop_share,
+                counterpart_character_id: Some(character.id),
+                memo: Some(&format!("Sold {}x {}", amount, item.name)),
+            },
+        )
+        .await?;
+    }
+
+    add_item_to_inventory_in_transaction(&mut transaction, &data.game, character, item, amount)
+        .await?;
+
+    transaction.commit().await?;
+    Ok(total_price)
+}
+
+/// Moves the item from the character's backpack back into the shop and pays the character.
+/// Returns the total price received.
+pub async fn execute_sale(
+    data: &Data,
+    character: &CharacterCacheItem,
+    shop: &Shop,
+    item: &Item,
+    amount: i64,
+) -> Result<i64, Error> {
+    let mut transaction = data.database.begin().await?;
+    let shop_item = get_shop_item(&mut transaction, shop, item).await?;
+
+    let Some(price) = shop_item.get_price(&data.game) else {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} has no price set for {}.**",
+            shop.name, item.name
+        ))));
+    };
+
+    let total_price = price / SELL_PRICE_DIVISOR * amount;
+    remove_item_from_inventory_in_transaction(&mut transaction, character, item, amount).await?;
+
+    if let Some(wallet_id) = shop.wallet_id {
+        let wallet_money = sqlx::query!("SELECT money FROM wallet WHERE id = ?", wallet_id)
+            .fetch_one(&mut *transaction)
+            .await?
+            .money;
+        if wallet_money < total_price {
+            return Err(Box::new(ValidationError::new(format!(
+                "**{} can't afford to pay {} {} right now.**",
+                shop.name,
+                total_price,
+                emoji::POKE_COIN
+            ))));
+        }
+
+        sqlx::query!(
+            "UPDATE wallet SET money = money - ? WHERE id = ?",
+            total_price,
+            wallet_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+        wallet_statement::record(
+            &mut transaction,
+            WalletTransaction {
+                wallet_id,
+                amount: -total_price,
+                counterpart_character_id: Some(character.id),
+                memo: Some(&format!("Bought {}x {}", amount, item.name)),
+            },
+        )
+        .await?;
+    }
+
+    sqlx::query!(
+        "UPDATE character SET money = money + ? WHERE id = ?",
+        total_price,
+        character.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    if shop_item.stock.is_some() {
+        sqlx::query!(
+            "UPDATE shop_item SET stock = stock + ? WHERE shop_id = ? AND item_name = ?",
+            amount,
+            shop.id,
+            shop_item.item_name
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    transaction.commit().await?;
+    Ok(total_price)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::inventory::get_inventory;
+    use crate::commands::shops::{execute_purchase, execute_sale, Shop};
+    use crate::game_data::item::Item;
+    use crate::{database_helpers, Error};
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn buying_and_selling_should_move_money_and_items(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_helpers::create_mock::data(db).await;
+        let guild_id = 100;
+        let user_id = 200;
+        let character_id = 300;
+        let item = Item {
+            name: String::from("Test Rock"),
+            price: Some(100),
+            description: String::new(),
+            category: String::new(),
+            single_use: false,
+            health_restored: None,
+        };
+
+        database_helpers::create_mock::guild(&data.database, guild_id).await;
+        database_helpers::create_mock::user(&data.database, user_id).await;
+        database_helpers::create_mock::character(
+            &data,
+            guild_id,
+            user_id,
+            character_id,
+            &String::from("Test"),
+        )
+        .await;
+        let character = data.cache.get_character(character_id).await.unwrap();
+        sqlx::query!(
+            "UPDATE character SET money = 250 WHERE id = ?",
+            character_id
+        )
+        .execute(&data.database)
+        .await?;
+
+        let shop_id = sqlx::query!(
+            "INSERT INTO shop (name, guild_id) VALUES ('Test Shop', ?) RETURNING id",
+            guild_id
+        )
+        .fetch_one(&data.database)
+        .await?
+        .id;
+        sqlx::query!(
+            "INSERT INTO shop_item (shop_id, item_name, price, stock) VALUES (?, ?, 100, 2)",
+            shop_id,
+            item.name
+        )
+        .execute(&data.database)
+        .await?;
+        let shop = Shop {
+            id: shop_id,
+            name: String::from("Test Shop"),
+            wallet_id: None,
+        };
+
+        assert_eq!(
+            200,
+            execute_purchase(&data, &character, &shop, &item, 2).await?
+        );
+        assert!(execute_purchase(&data, &character, &shop, &item, 1)
+            .await
+            .is_err());
+        assert_eq!(
+            2,
+            get_inventory(&data.database, character_id).await[0].amount
+        );
+
+        assert_eq!(50, execute_sale(&data, &character, &shop, &item, 1).await?);
+        let record = sqlx::query!("SELECT money FROM character WHERE id = ?", character_id)
+            .fetch_one(&data.database)
+            .await?;
+        assert_eq!(100, record.money);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::commands::autocompletion::{autocomplete_item, autocomplete_shop_name};
+use crate::commands::characters::{log_action, ActionType};
+use crate::commands::inventory::find_item;
+use crate::commands::shops::find_shop;
+use crate::commands::{send_error, Context, Error};
+
+/// Stop selling an item in a shop.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn remove_shop_item(
+    ctx: Context<'_>,
+    #[description = "Which shop?"]
+    #[autocomplete = "autocomplete_shop_name"]
+    shop: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let shop = find_shop(ctx.data(), guild_id, &shop).await?;
+    let item = find_item(&ctx.data().game, &item)?;
+
+    let result = sqlx::query!(
+        "DELETE FROM shop_item WHERE shop_id = ? AND item_name = ?",
+        shop.id,
+        item.name
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return send_error(&ctx, &format!("{} doesn't sell {}.", shop.name, item.name)).await;
+    }
+
+    let message = format!("{} no longer sells {}.", shop.name, item.name);
+    ctx.say(&message).await?;
+    log_action(&ActionType::Initialization, &ctx, &message).await
+}
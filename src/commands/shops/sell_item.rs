@@ -0,0 +1,60 @@
+use crate::commands::autocompletion::{
+    autocomplete_item, autocomplete_owned_character_name, autocomplete_shop_name,
+};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::inventory::find_item;
+use crate::commands::shops::{execute_sale, find_shop};
+use crate::commands::wallets::update_wallet_post;
+use crate::commands::{
+    ensure_user_owns_character, find_character, update_character_post, Context, Error,
+};
+use crate::emoji;
+
+/// Sell an item from your backpack to a shop.
+#[poise::command(slash_command, guild_only)]
+pub async fn sell_item(
+    ctx: Context<'_>,
+    #[description = "Who's selling?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+    #[description = "Which shop?"]
+    #[autocomplete = "autocomplete_shop_name"]
+    shop: String,
+    #[description = "Which item?"]
+    #[autocomplete = "autocomplete_item"]
+    item: String,
+    #[description = "Optional. Defaults to 1."]
+    #[min = 1_i64]
+    amount: Option<i64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character(ctx.author(), &character)?;
+    let shop = find_shop(ctx.data(), guild_id, &shop).await?;
+    let item = find_item(&ctx.data().game, &item)?;
+    let amount = amount.unwrap_or(1);
+
+    let total_price = execute_sale(ctx.data(), &character, &shop, item, amount).await?;
+
+    let message = format!(
+        "{} sold {}x {} to {} for {} {}.",
+        character.name,
+        amount,
+        item.name,
+        shop.name,
+        total_price,
+        emoji::POKE_COIN
+    );
+    ctx.say(&message).await?;
+    update_character_post(&ctx, character.id).await;
+    if let Some(wallet_id) = shop.wallet_id {
+        update_wallet_post(&ctx, wallet_id).await;
+    }
+    log_action_with_target(
+        &ActionType::ItemSale,
+        &ctx,
+        &message,
+        ActionLogTarget::character(character.id, Some(total_price)),
+    )
+    .await
+}
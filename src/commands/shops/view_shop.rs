@@ -0,0 +1,20 @@
+use crate::commands::autocompletion::autocomplete_shop_name;
+use crate::commands::shops::{build_shop_string, find_shop, get_shop_items};
+use crate::commands::{Context, Error};
+
+/// Have a look at what a shop has to offer.
+#[poise::command(slash_command, guild_only)]
+pub async fn shop(
+    ctx: Context<'_>,
+    #[description = "Which shop?"]
+    #[autocomplete = "autocomplete_shop_name"]
+    shop: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let shop = find_shop(ctx.data(), guild_id, &shop).await?;
+    let items = get_shop_items(&ctx.data().database, shop.id).await;
+
+    ctx.say(build_shop_string(&ctx.data().game, &shop, &items))
+        .await?;
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use tokio::join;
 
 use crate::commands::characters::{log_action, ActionType};
-use crate::commands::{ensure_user_exists, Context, Error};
+use crate::commands::{ensure_user_exists, reply_with_undo_button, Context, Error};
 use crate::errors::CommandInvocationError;
+use crate::ledger;
+use crate::ledger::{LedgerEntry, LedgerTarget};
 
 /// Store your GM Experience after a quest.
 #[poise::command(
@@ -30,18 +32,30 @@ pub async fn store_gm_experience(
     {
         Ok(record) => {
             let new_amount = record.gm_experience + amount;
+            let mut transaction = ctx.data().database.begin().await?;
             match sqlx::query!(
                 "UPDATE user_in_guild SET gm_experience = ? WHERE user_id = ? AND guild_id = ?",
                 new_amount,
                 user_id,
                 guild_id
             )
-            .execute(&ctx.data().database)
+            .execute(&mut *transaction)
                 .await
             {
                 Ok(_) => {
+                    ledger::record(&mut transaction, LedgerEntry {
+                        guild_id,
+                        interaction_id: ctx.id() as i64,
+                        actor_id: user_id,
+                        target: LedgerTarget::User(user_id),
+                        stat: "gm_experience",
+                        amount,
+                        action_type: &ActionType::StoreGMExperience,
+                    }).await?;
+                    transaction.commit().await?;
+
                     let text = format!("{} stored {} GM Experience!", ctx.author(), amount);
-                    let reply = ctx.say(&text);
+                    let reply = reply_with_undo_button(&ctx, &text);
                     let log = log_action(&ActionType::StoreGMExperience, &ctx, &text);
                     let _ = join!(reply, log);
                 }
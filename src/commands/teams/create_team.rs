@@ -0,0 +1,149 @@
+use chrono::Utc;
+use serenity::all::CreateMessage;
+use sqlx::{Pool, Sqlite};
+
+use crate::commands::autocompletion::{
+    autocomplete_owned_character_name, autocomplete_wallet_name,
+};
+use crate::commands::characters::{log_action, validate_user_input, ActionType};
+use crate::commands::teams::update_team_post;
+use crate::commands::{
+    ensure_guild_exists, ensure_user_owns_character, ensure_user_owns_wallet_or_is_gm,
+    find_character, find_wallet, send_ephemeral_reply, send_error, Context, Error,
+};
+use crate::errors::ValidationError;
+
+/// Found a new exploration team with one of your characters as its leader.
+#[poise::command(slash_command, guild_only)]
+pub async fn create_team(
+    ctx: Context<'_>,
+    #[description = "What name should we use?"] name: String,
+    #[description = "Which character will lead the team?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    leader: String,
+    #[description = "Optional. Which wallet should the team share?"]
+    #[autocomplete = "autocomplete_wallet_name"]
+    wallet: Option<String>,
+) -> Result<(), Error> {
+    if let Err(e) = validate_user_input(name.as_str()) {
+        return send_error(&ctx, e).await;
+    }
+
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let leader = find_character(ctx.data(), guild_id, &leader).await?;
+    ensure_user_owns_character(ctx.author(), &leader)?;
+
+    let wallet = match wallet {
+        Some(wallet) => {
+            let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
+            let member = ctx.author_member().await.expect("Command is guild_only");
+            ensure_user_owns_wallet_or_is_gm(
+                ctx.data(),
+                ctx.author().id.get() as i64,
+                member,
+                &wallet,
+            )
+            .await?;
+            Some(wallet)
+        }
+        None => None,
+    };
+
+    let message = ctx
+        .channel_id()
+        .send_message(
+            ctx,
+            CreateMessage::new().content(
+                "[Placeholder. This should get replaced or deleted within a couple seconds.]",
+            ),
+        )
+        .await?;
+
+    let guild_id = guild_id as i64;
+    ensure_guild_exists(&ctx, guild_id).await;
+
+    let result = execute_create_team(
+        &ctx.data().database,
+        &name,
+        guild_id,
+        leader.id,
+        wallet.as_ref().map(|x| x.id),
+        message.channel_id.get() as i64,
+        message.id.get() as i64,
+    )
+    .await;
+
+    match result {
+        Ok(id) => {
+            send_ephemeral_reply(&ctx, "Team has been successfully created!").await?;
+            update_team_post(&ctx, id).await;
+            log_action(
+                &ActionType::TeamChange,
+                &ctx,
+                &format!("{} founded the team {}.", leader.name, name),
+            )
+            .await
+        }
+        Err(e) => {
+            message.delete(ctx).await?;
+            Err(e)
+        }
+    }
+}
+
+async fn execute_create_team(
+    database: &Pool<Sqlite>,
+    name: &str,
+    guild_id: i64,
+    leader_id: i64,
+    wallet_id: Option<i64>,
+    channel_id: i64,
+    message_id: i64,
+) -> Result<i64, Error> {
+    let mut transaction = database.begin().await?;
+    let existing_team = sqlx::query!(
+        "SELECT team.name FROM team_member \
+         INNER JOIN team ON team.id = team_member.team_id \
+         WHERE team_member.character_id = ?",
+        leader_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await?;
+    if let Some(existing_team) = existing_team {
+        return Err(Box::new(ValidationError::new(format!(
+            "**Unable to create the team.**\n*This character is already a member of {}.*",
+            existing_team.name
+        ))));
+    }
+
+    let timestamp = Utc::now().timestamp();
+    let team = sqlx::query!(
+        "INSERT INTO team (name, guild_id, leader_id, wallet_id, bot_message_channel_id, bot_message_id, creation_timestamp) \
+         VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING id",
+        name,
+        guild_id,
+        leader_id,
+        wallet_id,
+        channel_id,
+        message_id,
+        timestamp
+    )
+    .fetch_one(&mut *transaction)
+    .await;
+    let Ok(team) = team else {
+        return Err(Box::new(ValidationError::new(
+            "Something went wrong! Does a team with this name already exist on this server?",
+        )));
+    };
+
+    sqlx::query!(
+        "INSERT INTO team_member (team_id, character_id) VALUES (?, ?)",
+        team.id,
+        leader_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+    Ok(team.id)
+}
@@ -0,0 +1,46 @@
+use serenity::all::{ChannelId, MessageId};
+
+use crate::commands::autocompletion::autocomplete_team_name;
+use crate::commands::characters::{log_action, ActionType};
+use crate::commands::teams::{ensure_user_leads_team_or_is_gm, find_team};
+use crate::commands::{send_ephemeral_reply, Context, Error};
+
+/// Disband a team. Its shared wallet will remain untouched.
+#[poise::command(slash_command, guild_only)]
+pub async fn disband_team(
+    ctx: Context<'_>,
+    #[description = "Which team?"]
+    #[autocomplete = "autocomplete_team_name"]
+    team: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let team = find_team(ctx.data(), guild_id, &team).await?;
+    ensure_user_leads_team_or_is_gm(&ctx, &team).await?;
+
+    let mut transaction = ctx.data().database.begin().await?;
+    sqlx::query!("DELETE FROM team_member WHERE team_id = ?", team.id)
+        .execute(&mut *transaction)
+        .await?;
+    sqlx::query!("DELETE FROM team WHERE id = ?", team.id)
+        .execute(&mut *transaction)
+        .await?;
+    transaction.commit().await?;
+
+    let _ = ctx
+        .serenity_context()
+        .http
+        .delete_message(
+            ChannelId::from(team.bot_message_channel_id as u64),
+            MessageId::from(team.bot_message_id as u64),
+            Some("Team was disbanded."),
+        )
+        .await;
+
+    send_ephemeral_reply(&ctx, &format!("{} has been disbanded.", team.name)).await?;
+    log_action(
+        &ActionType::TeamChange,
+        &ctx,
+        &format!("{} has been disbanded.", team.name),
+    )
+    .await
+}
@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use poise::{CreateReply, ReplyHandle};
+use serenity::all::{ButtonStyle, CreateActionRow, CreateInteractionResponse, UserId};
+use serenity::prelude::Mentionable;
+
+use crate::cache::CharacterCacheItem;
+use crate::commands::autocompletion::{autocomplete_character_name, autocomplete_team_name};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::teams::{ensure_user_leads_team_or_is_gm, find_team, update_team_post, Team};
+use crate::commands::{find_character, Context, Error};
+use crate::errors::ValidationError;
+use crate::helpers;
+
+const ACCEPT: &str = "invite_to_team_accept";
+const DECLINE: &str = "invite_to_team_decline";
+const TIMEOUT: Duration = Duration::from_secs(60 * 10);
+
+/// Invite a character to join your team. Their owner will have to accept first.
+#[poise::command(slash_command, guild_only)]
+pub async fn invite_to_team(
+    ctx: Context<'_>,
+    #[description = "Which team?"]
+    #[autocomplete = "autocomplete_team_name"]
+    team: String,
+    #[description = "Which character should join?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let team = find_team(ctx.data(), guild_id, &team).await?;
+    ensure_user_leads_team_or_is_gm(&ctx, &team).await?;
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_character_can_join(&ctx, &character).await?;
+
+    if character.user_id != ctx.author().id.get() {
+        let reply = ctx
+            .send(CreateReply::default().content(format!(
+                "**{} has been invited to join {}!**\n{}, do you accept?",
+                character.name,
+                team.name,
+                UserId::new(character.user_id).mention()
+            )))
+            .await?;
+
+        let accepted = await_answer(&ctx, &reply, UserId::new(character.user_id)).await?;
+        let result = match accepted {
+            Some(true) => format!("**{} has joined {}!**", character.name, team.name),
+            Some(false) => format!("**{} declined to join {}.**", character.name, team.name),
+            None => String::from("**Invitation timed out. Use the command again if needed.**"),
+        };
+        reply
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .content(&result)
+                    .components(Vec::new()),
+            )
+            .await?;
+        if accepted != Some(true) {
+            return Ok(());
+        }
+    } else {
+        ctx.say(format!("**{} has joined {}!**", character.name, team.name))
+            .await?;
+    }
+
+    // Things might have changed while we were waiting for an answer.
+    ensure_character_can_join(&ctx, &character).await?;
+    add_team_member(&ctx, &team, &character).await
+}
+
+async fn ensure_character_can_join<'a>(
+    ctx: &Context<'a>,
+    character: &CharacterCacheItem,
+) -> Result<(), ValidationError> {
+    if character.is_retired {
+        return Err(ValidationError::new(format!(
+            "**{} is retired and can't join a team.**",
+            character.name
+        )));
+    }
+
+    let existing_team = sqlx::query!(
+        "SELECT team.name FROM team_member \
+         INNER JOIN team ON team.id = team_member.team_id \
+         WHERE team_member.character_id = ?",
+        character.id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await;
+
+    match existing_team {
+        Ok(None) => Ok(()),
+        Ok(Some(team)) => Err(ValidationError::new(format!(
+            "**{} is already a member of {}.**",
+            character.name, team.name
+        ))),
+        Err(_) => Err(ValidationError::new(
+            "Was unable to check whether this character is already in a team. Please try again.",
+        )),
+    }
+}
+
+async fn add_team_member<'a>(
+    ctx: &Context<'a>,
+    team: &Team,
+    character: &CharacterCacheItem,
+) -> Result<(), Error> {
+    sqlx::query!(
+        "INSERT INTO team_member (team_id, character_id) VALUES (?, ?)",
+        team.id,
+        character.id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    update_team_post(ctx, team.id).await;
+    log_action_with_target(
+        &ActionType::TeamChange,
+        ctx,
+        &format!("{} has joined {}.", character.name, team.name),
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
+
+/// Returns None if nobody reacted in time.
+async fn await_answer<'a>(
+    ctx: &Context<'a>,
+    reply: &ReplyHandle<'a>,
+    user_id: UserId,
+) -> Result<Option<bool>, Error> {
+    reply
+        .edit(
+            *ctx,
+            CreateReply::default().components(vec![CreateActionRow::Buttons(vec![
+                helpers::create_styled_button("Accept", ACCEPT, false, ButtonStyle::Success),
+                helpers::create_styled_button("Decline", DECLINE, false, ButtonStyle::Danger),
+            ])]),
+        )
+        .await?;
+
+    let interaction = reply
+        .message()
+        .await?
+        .await_component_interaction(ctx)
+        .author_id(user_id)
+        .timeout(TIMEOUT)
+        .await;
+
+    match interaction {
+        Some(interaction) => {
+            interaction
+                .create_response(ctx, CreateInteractionResponse::Acknowledge)
+                .await?;
+            Ok(Some(interaction.data.custom_id == ACCEPT))
+        }
+        None => Ok(None),
+    }
+}
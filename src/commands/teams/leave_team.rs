@@ -0,0 +1,59 @@
+use crate::commands::autocompletion::autocomplete_owned_character_name;
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::teams::update_team_post;
+use crate::commands::{
+    ensure_user_owns_character_or_is_gm, find_character, send_ephemeral_reply, Context, Error,
+};
+use crate::errors::ValidationError;
+
+/// Have one of your characters leave their team.
+#[poise::command(slash_command, guild_only)]
+pub async fn leave_team(
+    ctx: Context<'_>,
+    #[description = "Which character?"]
+    #[autocomplete = "autocomplete_owned_character_name"]
+    character: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let character = find_character(ctx.data(), guild_id, &character).await?;
+    ensure_user_owns_character_or_is_gm(&ctx, &character).await?;
+
+    let Some(team) = sqlx::query!(
+        "SELECT team.id, team.name, team.leader_id FROM team_member \
+         INNER JOIN team ON team.id = team_member.team_id \
+         WHERE team_member.character_id = ?",
+        character.id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?
+    else {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} isn't a member of any team.**",
+            character.name
+        ))));
+    };
+
+    if team.leader_id == character.id {
+        return Err(Box::new(ValidationError::new(format!(
+            "**{} is leading {} and can't leave it.**\n*Use `/disband_team` instead.*",
+            character.name, team.name
+        ))));
+    }
+
+    sqlx::query!(
+        "DELETE FROM team_member WHERE character_id = ?",
+        character.id
+    )
+    .execute(&ctx.data().database)
+    .await?;
+
+    update_team_post(&ctx, team.id).await;
+    send_ephemeral_reply(&ctx, &format!("{} has left {}.", character.name, team.name)).await?;
+    log_action_with_target(
+        &ActionType::TeamChange,
+        &ctx,
+        &format!("{} has left {}.", character.name, team.name),
+        ActionLogTarget::character(character.id, None),
+    )
+    .await
+}
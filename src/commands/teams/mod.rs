@@ -0,0 +1,305 @@
+use poise::Command;
+use serenity::all::{ChannelId, EditMessage, MessageId};
+use sqlx::{Pool, Sqlite};
+
+use crate::commands::{
+    handle_error_during_message_edit, is_user_admin_or_gm, BuildUpdatedStatMessageStringResult,
+    Context,
+};
+use crate::data::Data;
+use crate::enums::MysteryDungeonRank;
+use crate::errors::{ParseError, ValidationError};
+use crate::Error;
+
+mod create_team;
+mod disband_team;
+mod invite_to_team;
+mod leave_team;
+mod sign_up_team;
+
+/// How many distinct quests a team's members need to have completed to reach each rank.
+const TEAM_RANK_THRESHOLDS: [(i64, MysteryDungeonRank); 5] = [
+    (100, MysteryDungeonRank::Master),
+    (50, MysteryDungeonRank::Diamond),
+    (25, MysteryDungeonRank::Platinum),
+    (10, MysteryDungeonRank::Gold),
+    (3, MysteryDungeonRank::Silver),
+];
+
+pub fn get_all_commands() -> Vec<Command<Data, Error>> {
+    vec![
+        create_team::create_team(),
+        invite_to_team::invite_to_team(),
+        leave_team::leave_team(),
+        disband_team::disband_team(),
+        sign_up_team::sign_up_team(),
+    ]
+}
+
+pub struct Team {
+    pub id: i64,
+    pub name: String,
+    pub leader_id: i64,
+    pub bot_message_channel_id: i64,
+    pub bot_message_id: i64,
+}
+
+pub async fn find_team(data: &Data, guild_id: u64, name: &str) -> Result<Team, ParseError> {
+    let guild_id = guild_id as i64;
+    let team = sqlx::query_as!(
+        Team,
+        "SELECT id, name, leader_id, bot_message_channel_id, bot_message_id \
+         FROM team WHERE guild_id = ? AND name = ?",
+        guild_id,
+        name
+    )
+    .fetch_optional(&data.database)
+    .await;
+
+    match team {
+        Ok(Some(team)) => Ok(team),
+        _ => Err(ParseError::new(format!(
+            "Unable to find a team named {}",
+            name
+        ))),
+    }
+}
+
+pub fn calculate_team_rank(completed_quests: i64) -> MysteryDungeonRank {
+    TEAM_RANK_THRESHOLDS
+        .iter()
+        .find(|(threshold, _)| completed_quests >= *threshold)
+        .map(|(_, rank)| *rank)
+        .unwrap_or(MysteryDungeonRank::Bronze)
+}
+
+/// Quests which were completed by multiple members are only counted once.
+pub async fn count_completed_quests(database: &Pool<Sqlite>, team_id: i64) -> i64 {
+    sqlx::query!(
+        "SELECT COUNT(DISTINCT quest_id) as count FROM quest_completion \
+         WHERE character_id IN (SELECT character_id FROM team_member WHERE team_id = ?)",
+        team_id
+    )
+    .fetch_one(database)
+    .await
+    .map(|x| x.count)
+    .unwrap_or(0)
+}
+
+/// Returns the IDs of all teams the given characters are a member of.
+pub async fn get_team_ids_for_characters(
+    database: &Pool<Sqlite>,
+    character_ids: &[i64],
+) -> Vec<i64> {
+    let mut result = Vec::new();
+    for character_id in character_ids {
+        if let Ok(Some(record)) = sqlx::query!(
+            "SELECT team_id FROM team_member WHERE character_id = ?",
+            character_id
+        )
+        .fetch_optional(database)
+        .await
+        {
+            if !result.contains(&record.team_id) {
+                result.push(record.team_id);
+            }
+        }
+    }
+
+    result
+}
+
+/// Team leaders, GMs and Admins are allowed to manage a team.
+pub async fn ensure_user_leads_team_or_is_gm<'a>(
+    ctx: &Context<'a>,
+    team: &Team,
+) -> Result<(), ValidationError> {
+    if let Some(member) = ctx.author_member().await {
+        if is_user_admin_or_gm(member) {
+            return Ok(());
+        }
+    }
+
+    match ctx.data().cache.get_character(team.leader_id).await {
+        Some(leader) if leader.user_id == ctx.author().id.get() => Ok(()),
+        _ => Err(ValidationError::new(format!(
+            "Only the leader of {}, GMs and Admins can do that.",
+            team.name
+        ))),
+    }
+}
+
+pub async fn update_team_post<'a>(ctx: &Context<'a>, team_id: i64) {
+    if let Some(result) = build_team_string(&ctx.data().database, team_id).await {
+        let message = ctx
+            .serenity_context()
+            .http
+            .get_message(
+                ChannelId::from(result.stat_channel_id as u64),
+                MessageId::from(result.stat_message_id as u64),
+            )
+            .await;
+        if let Ok(mut message) = message {
+            if let Err(e) = message
+                .edit(ctx, EditMessage::new().content(&result.message))
+                .await
+            {
+                handle_error_during_message_edit(
+                    ctx,
+                    e,
+                    message,
+                    result.message,
+                    None,
+                    result.name,
+                )
+                .await;
+            }
+        }
+    }
+}
+
+pub async fn build_team_string(
+    database: &Pool<Sqlite>,
+    team_id: i64,
+) -> Option<BuildUpdatedStatMessageStringResult> {
+    let entry = sqlx::query!(
+        "SELECT team.name, team.leader_id, team.bot_message_id, team.bot_message_channel_id, wallet.name as \"wallet_name?\" \
+            FROM team \
+            LEFT JOIN wallet ON wallet.id = team.wallet_id \
+            WHERE team.id = ?",
+        team_id
+    )
+    .fetch_one(database)
+    .await
+    .ok()?;
+
+    let members = sqlx::query!(
+        "SELECT character.id, character.name FROM team_member \
+            INNER JOIN character ON character.id = team_member.character_id \
+            WHERE team_member.team_id = ? ORDER BY character.name",
+        team_id
+    )
+    .fetch_all(database)
+    .await
+    .unwrap_or_default();
+
+    let leader_name = members
+        .iter()
+        .find(|x| x.id == entry.leader_id)
+        .map(|x| x.name.clone())
+        .unwrap_or_default();
+    let member_names = members
+        .iter()
+        .filter(|x| x.id != entry.leader_id)
+        .map(|x| x.name.clone())
+        .collect::<Vec<String>>();
+
+    let completed_quests = count_completed_quests(database, team_id).await;
+    let mut message = format!(
+        "\
+## 🏕️ {}
+{}
+**Leader**: {}
+",
+        entry.name,
+        calculate_team_rank(completed_quests),
+        leader_name,
+    );
+    if !member_names.is_empty() {
+        message.push_str(&format!("**Members**: {}\n", member_names.join(", ")));
+    }
+    if let Some(wallet_name) = entry.wallet_name {
+        message.push_str(&format!("**Wallet**: {}\n", wallet_name));
+    }
+    message.push_str(&format!("Completed Quests: {}\n", completed_quests));
+
+    Some(BuildUpdatedStatMessageStringResult {
+        message,
+        name: entry.name,
+        components: Vec::new(),
+        stat_channel_id: entry.bot_message_channel_id,
+        stat_message_id: entry.bot_message_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::teams::{build_team_string, calculate_team_rank};
+    use crate::enums::{MysteryDungeonRank, QuestParticipantSelectionMechanism};
+    use crate::{database_helpers, Error};
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn team_rank_should_count_distinct_quests_of_all_members(
+        db: Pool<Sqlite>,
+    ) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        let team_id = 300;
+        let leader_id = 400;
+        let member_id = 401;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        for (character_id, name) in [(leader_id, "Leader"), (member_id, "Member")] {
+            database_helpers::create_mock::character_in_database(
+                &db,
+                guild_id,
+                user_id,
+                character_id,
+                name,
+                0,
+            )
+            .await;
+        }
+        sqlx::query!(
+            "INSERT INTO team (id, name, guild_id, leader_id, bot_message_channel_id, bot_message_id, creation_timestamp) \
+             VALUES (?, 'Team', ?, ?, 0, 0, 0)",
+            team_id,
+            guild_id,
+            leader_id
+        )
+        .execute(&db)
+        .await?;
+
+        for character_id in [leader_id, member_id] {
+            sqlx::query!(
+                "INSERT INTO team_member (team_id, character_id) VALUES (?, ?)",
+                team_id,
+                character_id
+            )
+            .execute(&db)
+            .await?;
+        }
+
+        for quest_id in 0..3 {
+            database_helpers::create_mock::quest(
+                &db,
+                quest_id,
+                guild_id,
+                user_id,
+                quest_id,
+                5,
+                QuestParticipantSelectionMechanism::Random,
+            )
+            .await;
+            for character_id in [leader_id, member_id] {
+                sqlx::query!(
+                    "INSERT INTO quest_completion (quest_id, character_id) VALUES (?, ?)",
+                    quest_id,
+                    character_id
+                )
+                .execute(&db)
+                .await?;
+            }
+        }
+
+        let result = build_team_string(&db, team_id).await.unwrap();
+        assert!(result.message.contains("**Leader**: Leader"));
+        assert!(result.message.contains("**Members**: Member"));
+        assert!(result.message.contains("Completed Quests: 3"));
+        assert_eq!(MysteryDungeonRank::Silver, calculate_team_rank(3));
+        assert_eq!(MysteryDungeonRank::Bronze, calculate_team_rank(2));
+
+        Ok(())
+    }
+}
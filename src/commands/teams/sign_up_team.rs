@@ -0,0 +1,107 @@
+use chrono::Utc;
+use sqlx::{Pool, Sqlite};
+
+use crate::commands::autocompletion::autocomplete_team_name;
+use crate::commands::teams::{ensure_user_leads_team_or_is_gm, find_team};
+use crate::commands::{send_ephemeral_reply, send_error, Context, Error};
+use crate::helpers;
+
+/// Sign up all members of your team for the quest in this channel.
+#[poise::command(slash_command, guild_only)]
+pub async fn sign_up_team(
+    ctx: Context<'_>,
+    #[description = "Which team?"]
+    #[autocomplete = "autocomplete_team_name"]
+    team: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let team = find_team(ctx.data(), guild_id, &team).await?;
+    ensure_user_leads_team_or_is_gm(&ctx, &team).await?;
+
+    let channel_id = ctx.channel_id().get() as i64;
+    let quest = sqlx::query!(
+        "SELECT completion_timestamp FROM quest WHERE channel_id = ?",
+        channel_id
+    )
+    .fetch_optional(&ctx.data().database)
+    .await?;
+
+    match quest {
+        None => {
+            return send_error(
+                &ctx,
+                "Doesn't look like there was a quest created within this channel!",
+            )
+            .await;
+        }
+        Some(quest) if quest.completion_timestamp.is_some() => {
+            return send_error(&ctx, "This quest has already been completed!").await;
+        }
+        Some(_) => {}
+    }
+
+    let signed_up = persist_team_signup(
+        &ctx.data().database,
+        channel_id,
+        team.id,
+        Utc::now().timestamp(),
+    )
+    .await?;
+
+    if signed_up.is_empty() {
+        return send_error(
+            &ctx,
+            "Seems like all active members of this team are already signed up!",
+        )
+        .await;
+    }
+
+    send_ephemeral_reply(
+        &ctx,
+        &format!(
+            "Successfully signed up {} from {}!",
+            signed_up.join(", "),
+            team.name
+        ),
+    )
+    .await?;
+    helpers::update_quest_message(ctx.serenity_context(), ctx.data(), channel_id).await
+}
+
+/// Signs up all active team members who aren't signed up yet and returns their names.
+async fn persist_team_signup(
+    database: &Pool<Sqlite>,
+    quest_id: i64,
+    team_id: i64,
+    timestamp: i64,
+) -> Result<Vec<String>, Error> {
+    let mut transaction = database.begin().await?;
+    let members = sqlx::query!(
+        "SELECT character.id, character.name FROM team_member \
+         INNER JOIN character ON character.id = team_member.character_id \
+         WHERE team_member.team_id = ? AND character.is_retired = false \
+         ORDER BY character.name",
+        team_id
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let mut result = Vec::new();
+    for member in members {
+        let insert = sqlx::query!(
+            "INSERT OR IGNORE INTO quest_signup (quest_id, character_id, timestamp) VALUES (?, ?, ?)",
+            quest_id,
+            member.id,
+            timestamp
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        if insert.rows_affected() > 0 {
+            result.push(member.name);
+        }
+    }
+
+    transaction.commit().await?;
+    Ok(result)
+}
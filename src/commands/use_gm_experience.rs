@@ -2,11 +2,15 @@ use tokio::join;
 
 use crate::commands::{
     Context, ensure_user_exists, ensure_user_owns_character, Error, find_character,
+    reply_with_undo_button,
 };
 use crate::commands::autocompletion::autocomplete_owned_character_name;
-use crate::commands::characters::{ActionType, change_character_stat_after_validation, log_action};
+use crate::commands::characters::{ActionType, award_companion_experience, change_character_stat_after_validation, log_action};
 use crate::emoji::get_character_emoji;
 use crate::errors::{CommandInvocationError, ValidationError};
+use crate::guild_settings::GuildSettings;
+use crate::ledger;
+use crate::ledger::{LedgerEntry, LedgerTarget};
 
 /// Store your GM Experience after a quest.
 #[poise::command(
@@ -42,23 +46,37 @@ pub async fn use_gm_experience(
             }
 
             let new_amount = record.gm_experience - amount;
+            let mut transaction = ctx.data().database.begin().await?;
             match sqlx::query!(
                 "UPDATE user_in_guild SET gm_experience = ? WHERE user_id = ? AND guild_id = ?",
                 new_amount,
                 user_id,
                 guild_id
             )
-            .execute(&ctx.data().database)
+            .execute(&mut *transaction)
                 .await
             {
                 Ok(_) => {
+                    ledger::record(&mut transaction, LedgerEntry {
+                        guild_id,
+                        interaction_id: ctx.id() as i64,
+                        actor_id: user_id,
+                        target: LedgerTarget::User(user_id),
+                        stat: "gm_experience",
+                        amount: -amount,
+                        action_type: &ActionType::UseGMExperience,
+                    }).await?;
+                    transaction.commit().await?;
+
                     let emoji = get_character_emoji(ctx.data(), character.id).await;
                     let text = format!("Used {} GM Experience on {}{}!", amount, emoji.unwrap_or(String::new()), character.name);
-                    let reply = ctx.say(&text);
+                    let reply = reply_with_undo_button(&ctx, &text);
                     let log = log_action(&ActionType::UseGMExperience, &ctx, &text);
                     let _ = join!(reply, log);
                     // Do this afterwards to ensure the level up message is always sent second
                     let _ = change_character_stat_after_validation(&ctx, "experience", &character, amount, &ActionType::DoNotLog).await;
+                    let settings = GuildSettings::load(&ctx.data().database, guild_id).await;
+                    award_companion_experience(&ctx, &settings, character.id, amount).await;
                 }
                 Err(e) => {
                     return Err(Box::new(
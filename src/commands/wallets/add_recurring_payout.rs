@@ -0,0 +1,119 @@
+use chrono::Utc;
+
+use crate::commands::autocompletion::{
+    autocomplete_character_name, autocomplete_wallet_name,
+    autocomplete_wallet_name_or_guild_treasury,
+};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::{find_character, find_wallet, send_ephemeral_reply, Context, Error};
+use crate::emoji;
+use crate::enums::PayoutSchedule;
+use crate::errors::ValidationError;
+use crate::guild_treasury::{is_guild_treasury, GUILD_TREASURY_NAME};
+
+/// Regularly transfer money from a wallet or the guild treasury to a character or another wallet.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn add_recurring_payout(
+    ctx: Context<'_>,
+    #[description = "Which wallet should pay? Can also be the guild treasury."]
+    #[autocomplete = "autocomplete_wallet_name_or_guild_treasury"]
+    source_wallet: String,
+    #[min = 1_u32]
+    #[description = "How much?"]
+    amount: u32,
+    #[description = "How often?"] schedule: PayoutSchedule,
+    #[description = "Pay a character?"]
+    #[autocomplete = "autocomplete_character_name"]
+    character: Option<String>,
+    #[description = "Or pay another wallet?"]
+    #[autocomplete = "autocomplete_wallet_name"]
+    target_wallet: Option<String>,
+    #[description = "Optional. What's the payout for?"]
+    #[max_length = 100]
+    memo: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let source_wallet = if is_guild_treasury(&source_wallet) {
+        None
+    } else {
+        Some(find_wallet(ctx.data(), guild_id, &source_wallet).await?)
+    };
+    let source_wallet_id = source_wallet.as_ref().map(|x| x.id);
+    let (target_character, target_wallet) = match (character, target_wallet) {
+        (Some(character), None) => (
+            Some(find_character(ctx.data(), guild_id, &character).await?),
+            None,
+        ),
+        (None, Some(target_wallet)) => (
+            None,
+            Some(find_wallet(ctx.data(), guild_id, &target_wallet).await?),
+        ),
+        _ => {
+            return Err(Box::new(ValidationError::new(
+                "Please specify either a character or a wallet to pay.",
+            )))
+        }
+    };
+    if target_wallet
+        .as_ref()
+        .is_some_and(|x| Some(x.id) == source_wallet_id)
+    {
+        return Err(Box::new(ValidationError::new("A wallet can't pay itself.")));
+    }
+
+    let guild_id = guild_id as i64;
+    let amount = amount as i64;
+    let target_character_id = target_character.as_ref().map(|x| x.id);
+    let target_wallet_id = target_wallet.as_ref().map(|x| x.id);
+    let creator_id = ctx.author().id.get() as i64;
+    let timestamp = Utc::now().timestamp();
+    let record = sqlx::query!(
+        "INSERT INTO recurring_payout (guild_id, source_wallet_id, target_character_id, target_wallet_id, amount, schedule, memo, creator_id, creation_timestamp) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id",
+        guild_id,
+        source_wallet_id,
+        target_character_id,
+        target_wallet_id,
+        amount,
+        schedule,
+        memo,
+        creator_id,
+        timestamp
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
+
+    let target_name = match (&target_character, &target_wallet) {
+        (Some(character), _) => character.name.clone(),
+        (_, Some(wallet)) => wallet.name.clone(),
+        _ => unreachable!("Validated above"),
+    };
+    let message = format!(
+        "{} will now pay {} {} to {} ({}, payout #{}).",
+        source_wallet
+            .as_ref()
+            .map(|x| x.name.as_str())
+            .unwrap_or(GUILD_TREASURY_NAME),
+        amount,
+        emoji::POKE_COIN,
+        target_name,
+        schedule,
+        record.id
+    );
+    send_ephemeral_reply(&ctx, &message).await?;
+    log_action_with_target(
+        &ActionType::RecurringPayout,
+        &ctx,
+        &message,
+        match source_wallet_id {
+            Some(wallet_id) => ActionLogTarget::wallet(wallet_id, None),
+            None => ActionLogTarget::default(),
+        },
+    )
+    .await
+}
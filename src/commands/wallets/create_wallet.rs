@@ -3,6 +3,7 @@ use crate::commands::wallets::update_wallet_post;
 use crate::commands::{ensure_guild_exists, send_ephemeral_reply, send_error, Context, Error};
 use crate::data::Data;
 use crate::emoji;
+use crate::guild_treasury::is_guild_treasury;
 use chrono::Utc;
 use serenity::all::CreateMessage;
 
@@ -20,6 +21,9 @@ pub async fn create_wallet(
     if let Err(e) = validate_user_input(name.as_str()) {
         return send_error(&ctx, e).await;
     }
+    if is_guild_treasury(&name) {
+        return send_error(&ctx, "That name is reserved for the guild treasury.").await;
+    }
 
     let money = money.unwrap_or(0);
     let message = ctx
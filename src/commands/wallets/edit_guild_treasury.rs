@@ -0,0 +1,63 @@
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
+use crate::commands::{send_ephemeral_reply, send_error, Context, Error};
+use crate::guild_treasury::GUILD_TREASURY_NAME;
+use crate::{emoji, guild_treasury};
+
+/// Add money to the guild treasury, or remove some of it.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn edit_guild_treasury(
+    ctx: Context<'_>,
+    #[description = "How much? Use negative values to remove money."] amount: i32,
+    #[description = "What's the reason? This will show up on the treasury's statement."]
+    #[max_length = 100]
+    memo: String,
+) -> Result<(), Error> {
+    if amount == 0 {
+        return send_error(&ctx, "No changes requested, aborting.").await;
+    }
+
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let amount = amount as i64;
+    let mut transaction = ctx.data().database.begin().await?;
+    if amount > 0 {
+        guild_treasury::deposit_in_transaction(&mut transaction, guild_id, amount, None, &memo)
+            .await?;
+    } else {
+        guild_treasury::withdraw_in_transaction(&mut transaction, guild_id, -amount, None, &memo)
+            .await?;
+    }
+    transaction.commit().await?;
+
+    let balance = guild_treasury::get_balance(&ctx.data().database, guild_id).await?;
+    let (verb, preposition) = if amount > 0 {
+        ("Added", "to")
+    } else {
+        ("Removed", "from")
+    };
+    let message = format!(
+        "{} {} {} {} the {} ({}). New balance: {} {}",
+        verb,
+        amount.abs(),
+        emoji::POKE_COIN,
+        preposition,
+        GUILD_TREASURY_NAME,
+        memo,
+        balance,
+        emoji::POKE_COIN
+    );
+    send_ephemeral_reply(&ctx, &message).await?;
+    log_action_with_target(
+        &ActionType::WalletChange,
+        &ctx,
+        &message,
+        ActionLogTarget {
+            amount: Some(amount),
+            ..ActionLogTarget::default()
+        },
+    )
+    .await
+}
@@ -1,7 +1,10 @@
 use crate::commands::autocompletion::autocomplete_wallet_name;
-use crate::commands::characters::{log_action, ActionType};
+use crate::commands::characters::{log_action_with_target, ActionLogTarget, ActionType};
 use crate::commands::wallets::update_wallet_post;
 use crate::commands::{find_wallet, send_ephemeral_reply, send_error, Context, Error};
+use crate::guild_treasury::is_guild_treasury;
+use crate::wallet_statement;
+use crate::wallet_statement::WalletTransaction;
 
 /// Update wallet data. All arguments are optional.
 #[allow(clippy::too_many_arguments)]
@@ -16,16 +19,25 @@ pub async fn edit_wallet(
     #[autocomplete = "autocomplete_wallet_name"]
     wallet: String,
     #[description = "Change the name?"] name: Option<String>,
+    #[description = "Post a statement into the wallet's channel at the start of every month?"]
+    post_monthly_statement: Option<bool>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
+    if name.as_ref().is_some_and(|x| is_guild_treasury(x)) {
+        return send_error(&ctx, "That name is reserved for the guild treasury.").await;
+    }
 
-    let record = sqlx::query!("SELECT name FROM wallet WHERE id = ?", wallet.id)
-        .fetch_one(&ctx.data().database)
-        .await?;
+    let record = sqlx::query!(
+        "SELECT name, post_monthly_statement FROM wallet WHERE id = ?",
+        wallet.id
+    )
+    .fetch_one(&ctx.data().database)
+    .await?;
 
     let mut action_log = Vec::new();
 
+    let renamed_from = name.as_ref().map(|_| record.name.clone());
     let name = if let Some(name) = name {
         action_log.push(format!("name to {}", name));
         name
@@ -33,22 +45,49 @@ pub async fn edit_wallet(
         record.name
     };
 
+    let post_monthly_statement = if let Some(post_monthly_statement) = post_monthly_statement {
+        action_log.push(format!("monthly statements to {}", post_monthly_statement));
+        post_monthly_statement
+    } else {
+        record.post_monthly_statement
+    };
+
     if action_log.is_empty() {
         send_error(&ctx, "No changes requested, aborting.").await?;
         return Ok(());
     }
 
-    sqlx::query!("UPDATE wallet SET name = ? WHERE id = ?", name, wallet.id,)
-        .execute(&ctx.data().database)
+    let mut transaction = ctx.data().database.begin().await?;
+    sqlx::query!(
+        "UPDATE wallet SET name = ?, post_monthly_statement = ? WHERE id = ?",
+        name,
+        post_monthly_statement,
+        wallet.id,
+    )
+    .execute(&mut *transaction)
+    .await?;
+    if let Some(renamed_from) = renamed_from {
+        wallet_statement::record(
+            &mut transaction,
+            WalletTransaction {
+                wallet_id: wallet.id,
+                amount: 0,
+                counterpart_character_id: None,
+                memo: Some(&format!("Renamed from {} to {}", renamed_from, name)),
+            },
+        )
         .await?;
+    }
+    transaction.commit().await?;
 
     update_wallet_post(&ctx, wallet.id).await;
 
     let action_log = action_log.join(", ");
-    let _ = log_action(
+    let _ = log_action_with_target(
         &ActionType::WalletEdit,
         &ctx,
         &format!("Set {}'s {}.", wallet.name, action_log),
+        ActionLogTarget::wallet(wallet.id, None),
     )
     .await;
     let _ = send_ephemeral_reply(&ctx, &format!("Updated {}'s {}.", wallet.name, action_log)).await;
@@ -0,0 +1,29 @@
+use crate::commands::{send_ephemeral_reply, Context, Error};
+use crate::{helpers, recurring_payouts};
+
+/// List all recurring payouts on this server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn list_recurring_payouts(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let payouts = recurring_payouts::load(&ctx.data().database, Some(guild_id), None).await?;
+    if payouts.is_empty() {
+        send_ephemeral_reply(&ctx, "There are no recurring payouts on this server.").await?;
+        return Ok(());
+    }
+
+    let mut text = String::from("### 🔁 Recurring Payouts\n");
+    for payout in payouts {
+        text.push_str(&payout.build_description());
+        text.push('\n');
+    }
+
+    for part in helpers::split_long_messages(text) {
+        send_ephemeral_reply(&ctx, &part).await?;
+    }
+
+    Ok(())
+}
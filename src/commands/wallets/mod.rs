@@ -1,20 +1,30 @@
-use crate::cache::WalletCacheItem;
-use crate::commands::characters::{log_action, ActionType, EntityWithNameAndNumericValue};
+use crate::cache::{CharacterCacheItem, WalletCacheItem};
+use crate::commands::characters::{
+    log_action_with_target, ActionLogTarget, ActionType, EntityWithNameAndNumericValue,
+};
 use crate::commands::{
     handle_error_during_message_edit, send_error, BuildUpdatedStatMessageStringResult, Context,
 };
 use crate::data::Data;
-use crate::{emoji, Error};
+use crate::ledger::{LedgerEntry, LedgerTarget};
+use crate::wallet_statement::WalletTransaction;
+use crate::{emoji, ledger, Error};
 use poise::Command;
 use serenity::all::{ChannelId, EditMessage, MessageId};
+use sqlx::{Pool, Sqlite};
 
+mod add_recurring_payout;
 mod add_wallet_owner;
 mod create_wallet;
+mod edit_guild_treasury;
 mod edit_wallet;
+mod list_recurring_payouts;
 mod pay;
+mod remove_recurring_payout;
+mod remove_wallet_owner;
 mod wallet_info;
+mod wallet_statement;
 mod withdraw;
-mod remove_wallet_owner;
 
 pub fn get_all_commands() -> Vec<Command<Data, Error>> {
     vec![
@@ -24,12 +34,17 @@ pub fn get_all_commands() -> Vec<Command<Data, Error>> {
         withdraw::withdraw(),
         wallet_info::wallet_info(),
         edit_wallet::edit_wallet(),
+        edit_guild_treasury::edit_guild_treasury(),
         remove_wallet_owner::remove_wallet_owner(),
+        wallet_statement::wallet_statement(),
+        add_recurring_payout::add_recurring_payout(),
+        list_recurring_payouts::list_recurring_payouts(),
+        remove_recurring_payout::remove_recurring_payout(),
     ]
 }
 
 pub async fn update_wallet_post<'a>(ctx: &Context<'a>, wallet_id: i64) {
-    if let Some(result) = build_wallet_string(ctx, wallet_id).await {
+    if let Some(result) = build_wallet_string(&ctx.data().database, wallet_id).await {
         let message = ctx
             .serenity_context()
             .http
@@ -57,8 +72,8 @@ pub async fn update_wallet_post<'a>(ctx: &Context<'a>, wallet_id: i64) {
     }
 }
 
-async fn build_wallet_string<'a>(
-    ctx: &Context<'a>,
+pub async fn build_wallet_string(
+    database: &Pool<Sqlite>,
     wallet_id: i64,
 ) -> Option<BuildUpdatedStatMessageStringResult> {
     let entry = sqlx::query!(
@@ -66,7 +81,7 @@ async fn build_wallet_string<'a>(
             FROM wallet WHERE id = ? ORDER BY rowid LIMIT 1",
         wallet_id
     )
-    .fetch_one(&ctx.data().database)
+    .fetch_one(database)
     .await;
 
     let owners = sqlx::query!(
@@ -74,7 +89,7 @@ async fn build_wallet_string<'a>(
                 SELECT character_id FROM wallet_owner WHERE wallet_id = ?)",
         wallet_id
     )
-    .fetch_all(&ctx.data().database)
+    .fetch_all(database)
     .await;
 
     let owner_line;
@@ -124,6 +139,8 @@ pub async fn change_wallet_stat_after_validation<'a>(
     wallet: &WalletCacheItem,
     amount: i64,
     action_type: &ActionType,
+    counterpart: Option<&CharacterCacheItem>,
+    memo: Option<&str>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
     let record = sqlx::query_as::<_, EntityWithNameAndNumericValue>(
@@ -140,17 +157,35 @@ pub async fn change_wallet_stat_after_validation<'a>(
     match record {
         Ok(record) => {
             let new_value = record.value + amount;
+            let mut transaction = ctx.data().database.begin().await?;
             let result = sqlx::query(
                 format!("UPDATE wallet SET {} = ? WHERE id = ? AND {} = ?", database_column, database_column).as_str())
                 .bind(new_value)
                 .bind(record.id)
                 .bind(record.value)
-                .execute(&ctx.data().database).await;
+                .execute(&mut *transaction).await;
 
             if result.is_err() || result.unwrap().rows_affected() != 1 {
                 return crate::commands::characters::send_stale_data_error(ctx).await
             }
 
+            ledger::record(&mut transaction, LedgerEntry {
+                guild_id: wallet.guild_id as i64,
+                interaction_id: ctx.id() as i64,
+                actor_id: ctx.author().id.get() as i64,
+                target: LedgerTarget::Wallet(record.id),
+                stat: database_column,
+                amount,
+                action_type,
+            }).await?;
+            crate::wallet_statement::record(&mut transaction, WalletTransaction {
+                wallet_id: record.id,
+                amount,
+                counterpart_character_id: counterpart.map(|x| x.id),
+                memo,
+            }).await?;
+            transaction.commit().await?;
+
             update_wallet_post(ctx, record.id).await;
             let action = if database_column == "money" {
                 emoji::POKE_COIN
@@ -167,7 +202,7 @@ pub async fn change_wallet_stat_after_validation<'a>(
                 to_or_from = "from";
             }
 
-            log_action(action_type, ctx, format!("{} {} {} {} {}", added_or_removed, amount.abs(), action, to_or_from, record.name).as_str()).await
+            log_action_with_target(action_type, ctx, format!("{} {} {} {} {}", added_or_removed, amount.abs(), action, to_or_from, record.name).as_str(), ActionLogTarget::wallet(record.id, Some(amount))).await
         }
         Err(_) => {
             send_error(ctx, format!("Unable to find a wallet named {}.\n**Internal cache must be out of date. Please let me know if this ever happens.**", wallet.name).as_str()).await
@@ -5,8 +5,8 @@ use crate::commands::autocompletion::{
 use crate::commands::characters::{change_character_stat_after_validation, ActionType};
 use crate::commands::wallets::change_wallet_stat_after_validation;
 use crate::commands::{
-    ensure_character_has_money, ensure_user_owns_character, find_character, find_wallet, Context,
-    Error,
+    ensure_character_has_money, ensure_user_owns_character, find_character, find_wallet,
+    reply_with_undo_button, Context, Error,
 };
 use crate::emoji;
 
@@ -15,6 +15,7 @@ async fn transfer_money_from_character_to_wallet<'a>(
     character: CharacterCacheItem,
     wallet: WalletCacheItem,
     amount: i64,
+    memo: Option<String>,
 ) -> Result<(), Error> {
     ensure_user_owns_character(ctx.author(), &character)?;
     ensure_character_has_money(ctx.data(), &character, amount, "pay").await?;
@@ -36,16 +37,21 @@ async fn transfer_money_from_character_to_wallet<'a>(
             &wallet,
             amount,
             &ActionType::WalletPayment,
+            Some(&character),
+            memo.as_deref(),
         )
         .await
         {
-            ctx.say(format!(
-                "***{}** paid {} {} to **{}***!",
-                character.name,
-                amount,
-                emoji::POKE_COIN,
-                wallet.name
-            ))
+            reply_with_undo_button(
+                ctx,
+                format!(
+                    "***{}** paid {} {} to **{}***!",
+                    character.name,
+                    amount,
+                    emoji::POKE_COIN,
+                    wallet.name
+                ),
+            )
             .await?;
         } else {
             // TODO: The undo might fail.
@@ -74,11 +80,13 @@ pub async fn pay(
     #[description = "What's the wallet's name?"]
     #[autocomplete = "autocomplete_wallet_name"]
     wallet: String,
+    #[description = "Optional. What's the payment for?"]
+    #[max_length = 100]
+    memo: Option<String>,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let character = find_character(ctx.data(), guild_id, &character).await?;
     let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
 
-    transfer_money_from_character_to_wallet(&ctx, character, wallet, amount as i64).await
+    transfer_money_from_character_to_wallet(&ctx, character, wallet, amount as i64, memo).await
 }
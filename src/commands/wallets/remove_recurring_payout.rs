@@ -0,0 +1,36 @@
+use crate::commands::characters::{log_action, ActionType};
+use crate::commands::{send_ephemeral_reply, Context, Error};
+use crate::errors::ValidationError;
+use crate::recurring_payouts;
+
+/// Stop a recurring payout. Use /list_recurring_payouts to find its number.
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn remove_recurring_payout(
+    ctx: Context<'_>,
+    #[description = "Which payout number?"] payout: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get() as i64;
+    let payout_id = payout as i64;
+    let Some(payout) = recurring_payouts::load(&ctx.data().database, Some(guild_id), None)
+        .await?
+        .into_iter()
+        .find(|x| x.id == payout_id)
+    else {
+        return Err(Box::new(ValidationError::new(format!(
+            "Unable to find a recurring payout with number {}.",
+            payout_id
+        ))));
+    };
+
+    sqlx::query!("DELETE FROM recurring_payout WHERE id = ?", payout.id)
+        .execute(&ctx.data().database)
+        .await?;
+
+    let message = format!("Removed recurring payout {}", payout.build_description());
+    send_ephemeral_reply(&ctx, &message).await?;
+    log_action(&ActionType::RecurringPayout, &ctx, &message).await
+}
@@ -15,7 +15,7 @@ pub async fn wallet_info(
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
 
-    let result = build_wallet_string(&ctx, wallet.id).await;
+    let result = build_wallet_string(&ctx.data().database, wallet.id).await;
     if let Some(result) = result {
         ctx.reply(result.message).await?;
     } else {
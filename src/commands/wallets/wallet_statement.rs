@@ -0,0 +1,69 @@
+use crate::commands::autocompletion::autocomplete_wallet_name_or_guild_treasury;
+use crate::commands::history::{parse_date, SECONDS_PER_DAY};
+use crate::commands::{
+    ensure_user_owns_wallet_or_is_gm, find_wallet, is_user_admin_or_gm, send_ephemeral_reply,
+    Context, Error,
+};
+use crate::errors::ValidationError;
+use crate::guild_treasury::{is_guild_treasury, GUILD_TREASURY_NAME};
+use crate::{helpers, wallet_statement};
+
+/// List all transactions of a wallet, including the total amount of money going in and out.
+#[poise::command(slash_command, guild_only)]
+pub async fn wallet_statement(
+    ctx: Context<'_>,
+    #[description = "What's the wallet's name?"]
+    #[autocomplete = "autocomplete_wallet_name_or_guild_treasury"]
+    wallet: String,
+    #[description = "First day to include. (YYYY-MM-DD)"] from: Option<String>,
+    #[description = "Last day to include. (YYYY-MM-DD)"] until: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Command is guild_only").get();
+    let author_member = ctx
+        .author_member()
+        .await
+        .expect("author_member should be set within guild context.");
+    let (wallet_id, wallet_name) = if is_guild_treasury(&wallet) {
+        if !is_user_admin_or_gm(author_member) {
+            return Err(Box::new(ValidationError::new(format!(
+                "Only GMs and Admins can look at the statement of the {}.",
+                GUILD_TREASURY_NAME
+            ))));
+        }
+        (None, String::from(GUILD_TREASURY_NAME))
+    } else {
+        let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
+        ensure_user_owns_wallet_or_is_gm(
+            ctx.data(),
+            ctx.author().id.get() as i64,
+            author_member,
+            &wallet,
+        )
+        .await?;
+        (Some(wallet.id), wallet.name)
+    };
+
+    let from = match from {
+        Some(from) => Some(parse_date(&from)?),
+        None => None,
+    };
+    let until = match until {
+        Some(until) => Some(parse_date(&until)? + SECONDS_PER_DAY),
+        None => None,
+    };
+
+    let entries = wallet_statement::load_entries(
+        &ctx.data().database,
+        guild_id as i64,
+        wallet_id,
+        from,
+        until,
+    )
+    .await?;
+    let statement = wallet_statement::build_statement_string(&wallet_name, &entries, from, until);
+    for part in helpers::split_long_messages(statement) {
+        send_ephemeral_reply(&ctx, &part).await?;
+    }
+
+    Ok(())
+}
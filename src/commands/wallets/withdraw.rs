@@ -4,7 +4,7 @@ use crate::commands::characters::{change_character_stat_after_validation, Action
 use crate::commands::wallets::change_wallet_stat_after_validation;
 use crate::commands::{
     ensure_user_owns_wallet_or_is_gm, ensure_wallet_has_money, find_character, find_wallet,
-    Context, Error,
+    reply_with_undo_button, Context, Error,
 };
 use crate::emoji;
 
@@ -13,6 +13,7 @@ async fn transfer_money_from_wallet_to_character<'a>(
     character: CharacterCacheItem,
     wallet: WalletCacheItem,
     amount: i64,
+    memo: Option<String>,
 ) -> Result<(), Error> {
     ensure_user_owns_wallet_or_is_gm(
         ctx.data(),
@@ -23,7 +24,7 @@ async fn transfer_money_from_wallet_to_character<'a>(
         &wallet,
     )
     .await?;
-    ensure_wallet_has_money(ctx.data(), &wallet, amount, "pay").await?;
+    ensure_wallet_has_money(&ctx.data().database, &wallet, amount, "pay").await?;
 
     // TODO: Potential flaw: Money gets transferred by someone else in between, this might not be detected. Figure out how to use sqlx transactions instead.
     // For now, it should be fine if we only subtract the money - people are way more likely to complain in that case. :'D
@@ -33,6 +34,8 @@ async fn transfer_money_from_wallet_to_character<'a>(
         &wallet,
         -amount,
         &ActionType::WalletWithdrawal,
+        Some(&character),
+        memo.as_deref(),
     )
     .await
     {
@@ -45,18 +48,29 @@ async fn transfer_money_from_wallet_to_character<'a>(
         )
         .await
         {
-            ctx.say(format!(
-                "***{}** has withdrawn {} {} from **{}***!",
-                character.name,
-                amount,
-                emoji::POKE_COIN,
-                wallet.name
-            ))
+            reply_with_undo_button(
+                ctx,
+                format!(
+                    "***{}** has withdrawn {} {} from **{}***!",
+                    character.name,
+                    amount,
+                    emoji::POKE_COIN,
+                    wallet.name
+                ),
+            )
             .await?;
         } else {
             // TODO: The undo might fail.
-            change_wallet_stat_after_validation(ctx, "money", &wallet, amount, &ActionType::Undo)
-                .await?;
+            change_wallet_stat_after_validation(
+                ctx,
+                "money",
+                &wallet,
+                amount,
+                &ActionType::Undo,
+                Some(&character),
+                Some("Refund for a failed withdrawal"),
+            )
+            .await?;
         }
     }
 
@@ -74,11 +88,13 @@ pub async fn withdraw(
     #[description = "To whom?"]
     #[autocomplete = "autocomplete_character_name"]
     character: String,
+    #[description = "Optional. What's the withdrawal for?"]
+    #[max_length = 100]
+    memo: Option<String>,
 ) -> Result<(), Error> {
-    // TODO: Button to undo the transaction which lasts for a minute or so.
     let guild_id = ctx.guild_id().expect("Command is guild_only").get();
     let character = find_character(ctx.data(), guild_id, &character).await?;
     let wallet = find_wallet(ctx.data(), guild_id, &wallet).await?;
 
-    transfer_money_from_wallet_to_character(&ctx, character, wallet, amount as i64).await
+    transfer_money_from_wallet_to_character(&ctx, character, wallet, amount as i64, memo).await
 }
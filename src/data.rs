@@ -13,6 +13,7 @@ pub struct Data {
     pub is_backup_thread_running: AtomicBool,
     pub is_weekly_reset_thread_running: AtomicBool,
     pub is_monthly_reset_thread_running: AtomicBool,
+    pub is_auction_thread_running: AtomicBool,
 }
 
 impl Data {
@@ -24,6 +25,7 @@ impl Data {
             is_backup_thread_running: AtomicBool::new(false),
             is_weekly_reset_thread_running: AtomicBool::new(false),
             is_monthly_reset_thread_running: AtomicBool::new(false),
+            is_auction_thread_running: AtomicBool::new(false),
         };
 
         result.cache.update_character_names(&result.database).await;
@@ -78,6 +78,45 @@ pub mod create_mock {
         data.cache.update_character_names(&data.database).await;
     }
 
+    /// Inserts a character without touching the cache, for tests which don't need any game data.
+    pub async fn character_in_database(
+        db: &Pool<Sqlite>,
+        guild_id: i64,
+        user_id: i64,
+        character_id: i64,
+        name: &str,
+        money: i64,
+    ) {
+        let _ = sqlx::query!(
+            "INSERT INTO character (id, user_id, guild_id, name, stat_message_id, stat_channel_id, creation_date, experience, money) VALUES (?, ?, ?, ?, 0, 0, 0, 0, ?)",
+            character_id,
+            user_id,
+            guild_id,
+            name,
+            money
+        )
+        .execute(db)
+        .await;
+    }
+
+    pub async fn wallet_with_money(
+        db: &Pool<Sqlite>,
+        guild_id: i64,
+        wallet_id: i64,
+        name: &str,
+        money: i64,
+    ) {
+        let _ = sqlx::query!(
+            "INSERT INTO wallet (id, name, guild_id, bot_message_channel_id, bot_message_id, creation_timestamp, money) VALUES (?, ?, ?, 0, 0, 0, ?)",
+            wallet_id,
+            name,
+            guild_id,
+            money
+        )
+        .execute(db)
+        .await;
+    }
+
     pub async fn wallet(db: &Pool<Sqlite>, guild_id: i64, wallet_id: i64, name: &String) {
         let timestamp = Utc::now().timestamp();
         let _ = sqlx::query!("INSERT INTO wallet (id, name, guild_id, bot_message_channel_id, bot_message_id, creation_timestamp, money) VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING id", 
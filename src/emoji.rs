@@ -25,8 +25,11 @@ pub const BACKPACK: &str = "🎒";
 pub const FENCING: &str = "🗣️";
 pub const TICKET: &str = "🎫";
 pub const CROSSED_SWORDS: &str = "⚔️";
+pub const BUSTS_IN_SILHOUETTE: &str = "👥";
 pub const PARTY_POPPER: &str = "🎉";
 pub const PARTYING_FACE: &str = "🥳";
+pub const SPORTS_MEDAL: &str = "🏅";
+pub const PAW_PRINTS: &str = "🐾";
 
 pub const DOT_EMPTY: char = '⭘';
 pub const DOT_FILLED: char = '⬤';
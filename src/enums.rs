@@ -106,52 +106,6 @@ impl PokemonTypeWithoutShadow {
             String::from("Fairy"),
         ]
     }
-
-    pub fn get_tera_unlocked_column(&self) -> &str {
-        match self {
-            PokemonTypeWithoutShadow::Normal => "tera_unlocked_normal",
-            PokemonTypeWithoutShadow::Fighting => "tera_unlocked_fighting",
-            PokemonTypeWithoutShadow::Flying => "tera_unlocked_flying",
-            PokemonTypeWithoutShadow::Poison => "tera_unlocked_poison",
-            PokemonTypeWithoutShadow::Ground => "tera_unlocked_ground",
-            PokemonTypeWithoutShadow::Rock => "tera_unlocked_rock",
-            PokemonTypeWithoutShadow::Bug => "tera_unlocked_bug",
-            PokemonTypeWithoutShadow::Ghost => "tera_unlocked_ghost",
-            PokemonTypeWithoutShadow::Steel => "tera_unlocked_steel",
-            PokemonTypeWithoutShadow::Fire => "tera_unlocked_fire",
-            PokemonTypeWithoutShadow::Water => "tera_unlocked_water",
-            PokemonTypeWithoutShadow::Grass => "tera_unlocked_grass",
-            PokemonTypeWithoutShadow::Electric => "tera_unlocked_electric",
-            PokemonTypeWithoutShadow::Psychic => "tera_unlocked_psychic",
-            PokemonTypeWithoutShadow::Ice => "tera_unlocked_ice",
-            PokemonTypeWithoutShadow::Dragon => "tera_unlocked_dragon",
-            PokemonTypeWithoutShadow::Dark => "tera_unlocked_dark",
-            PokemonTypeWithoutShadow::Fairy => "tera_unlocked_fairy",
-        }
-    }
-
-    pub fn get_tera_used_column(&self) -> &str {
-        match self {
-            PokemonTypeWithoutShadow::Normal => "tera_used_normal",
-            PokemonTypeWithoutShadow::Fighting => "tera_used_fighting",
-            PokemonTypeWithoutShadow::Flying => "tera_used_flying",
-            PokemonTypeWithoutShadow::Poison => "tera_used_poison",
-            PokemonTypeWithoutShadow::Ground => "tera_used_ground",
-            PokemonTypeWithoutShadow::Rock => "tera_used_rock",
-            PokemonTypeWithoutShadow::Bug => "tera_used_bug",
-            PokemonTypeWithoutShadow::Ghost => "tera_used_ghost",
-            PokemonTypeWithoutShadow::Steel => "tera_used_steel",
-            PokemonTypeWithoutShadow::Fire => "tera_used_fire",
-            PokemonTypeWithoutShadow::Water => "tera_used_water",
-            PokemonTypeWithoutShadow::Grass => "tera_used_grass",
-            PokemonTypeWithoutShadow::Electric => "tera_used_electric",
-            PokemonTypeWithoutShadow::Psychic => "tera_used_psychic",
-            PokemonTypeWithoutShadow::Ice => "tera_used_ice",
-            PokemonTypeWithoutShadow::Dragon => "tera_used_dragon",
-            PokemonTypeWithoutShadow::Dark => "tera_used_dark",
-            PokemonTypeWithoutShadow::Fairy => "tera_used_fairy",
-        }
-    }
 }
 
 impl Display for PokemonTypeWithoutShadow {
@@ -332,17 +286,6 @@ impl MysteryDungeonRank {
         }
     }
 
-    pub fn from_level(level: u8) -> Self {
-        match level {
-            1 => MysteryDungeonRank::Bronze,
-            2..=3 => MysteryDungeonRank::Silver,
-            4..=7 => MysteryDungeonRank::Gold,
-            8..=15 => MysteryDungeonRank::Platinum,
-            16..=19 => MysteryDungeonRank::Diamond,
-            _ => MysteryDungeonRank::Master,
-        }
-    }
-
     pub fn emoji_string(&self) -> &str {
         match self {
             MysteryDungeonRank::Bronze => "<:badgebronze:1272532685197152349>",
@@ -561,3 +504,50 @@ pub enum QuestParticipantSelectionMechanism {
     GMPicks = 2,
     Random = 3,
 }
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter, sqlx::Type, FromRepr,
+)]
+#[repr(i64)]
+pub enum TeraChargeReset {
+    #[default]
+    Monthly = 1,
+    Weekly = 2,
+    #[name = "On Rest"]
+    OnRest = 3,
+}
+
+impl Display for TeraChargeReset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TeraChargeReset::Monthly => "Monthly",
+            TeraChargeReset::Weekly => "Weekly",
+            TeraChargeReset::OnRest => "On Rest",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter, sqlx::Type, FromRepr)]
+#[repr(i64)]
+pub enum PayoutSchedule {
+    Weekly = 1,
+    Monthly = 2,
+}
+
+impl Display for PayoutSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PayoutSchedule::Weekly => "Weekly",
+            PayoutSchedule::Monthly => "Monthly",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, FromRepr)]
+#[repr(i64)]
+pub enum CharacterSubmissionStatus {
+    Pending = 1,
+    Approved = 2,
+    Rejected = 3,
+    ChangesRequested = 4,
+}
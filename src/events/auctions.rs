@@ -0,0 +1,378 @@
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use chrono::Utc;
+use serenity::all::{
+    ChannelId, ComponentInteraction, CreateButton, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateMessage, EditMessage, MessageId, User,
+};
+use serenity::builder::CreateActionRow;
+use serenity::client::Context;
+use sqlx::{Pool, Sqlite};
+
+use crate::auctions::Auction;
+use crate::cache::Cache;
+use crate::commands::characters::{send_action_log, ActionLogEntry, ActionLogTarget, ActionType};
+use crate::commands::ensure_character_has_money;
+use crate::commands::inventory::{add_item_to_inventory_in_transaction, find_item};
+use crate::data::Data;
+use crate::errors::CommandInvocationError;
+use crate::events::{
+    send_error, send_error_to_log_channel, update_character_post, update_wallet_post,
+};
+use crate::game_data::GameData;
+use crate::{auctions, emoji, helpers, Error};
+
+const SECONDS_BETWEEN_SETTLEMENT_CHECKS: u64 = 60;
+
+enum MessageType {
+    UpdateMessage,
+    NewMessage,
+}
+
+pub async fn bid(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    let (Some(Ok(auction_id)), Some(Ok(amount))) = (
+        args.first().map(|x| i64::from_str(x)),
+        args.get(1).map(|x| i64::from_str(x)),
+    ) else {
+        return Err(Box::new(
+            CommandInvocationError::new(format!("Invalid auction bid request: {:?}", args)).log(),
+        ));
+    };
+
+    let Some(auction) = auctions::load(&data.database, auction_id).await? else {
+        return send_error(&interaction, context, "This auction doesn't exist anymore.").await;
+    };
+
+    let guild_id = auction.guild_id;
+    let user_id = interaction.user.id.get() as i64;
+    let available_characters = sqlx::query!(
+        "SELECT id, name FROM character WHERE user_id = ? AND guild_id = ? AND is_retired = false",
+        user_id,
+        guild_id
+    )
+    .fetch_all(&data.database)
+    .await?;
+
+    let (character_id, response_type) = if let Some(character_id) = args.get(2) {
+        let character_id = i64::from_str(character_id)?;
+        if available_characters.iter().all(|x| x.id != character_id) {
+            return send_error(&interaction, context, "That's not one of your characters!").await;
+        }
+        (character_id, MessageType::UpdateMessage)
+    } else if available_characters.len() == 1 {
+        (available_characters[0].id, MessageType::NewMessage)
+    } else if available_characters.is_empty() {
+        return send_error(
+            &interaction,
+            context,
+            "You don't have any characters who could place a bid.",
+        )
+        .await;
+    } else {
+        let character_buttons = available_characters
+            .iter()
+            .map(|x| {
+                helpers::create_button(
+                    x.name.as_str(),
+                    &format!("auction-bid_{}_{}_{}", auction_id, amount, x.id),
+                    false,
+                )
+            })
+            .collect::<Vec<CreateButton>>()
+            .chunks(5)
+            .map(|chunk| CreateActionRow::Buttons(chunk.to_vec()))
+            .collect::<Vec<CreateActionRow>>();
+
+        interaction
+            .create_response(
+                context,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .ephemeral(true)
+                        .content(format!(
+                            "Which character would like to bid {} {}?",
+                            amount,
+                            emoji::POKE_COIN
+                        ))
+                        .components(character_buttons),
+                ),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    let text = match place_bid(data, &auction, character_id, amount).await {
+        Ok(_) => format!(
+            "You are now the highest bidder with {} {}!",
+            amount,
+            emoji::POKE_COIN
+        ),
+        Err(e) => e.to_string(),
+    };
+    let message = CreateInteractionResponseMessage::new()
+        .ephemeral(true)
+        .content(text)
+        .components(Vec::new());
+    match response_type {
+        MessageType::UpdateMessage => {
+            interaction.create_response(context, CreateInteractionResponse::UpdateMessage(message))
+        }
+        MessageType::NewMessage => {
+            interaction.create_response(context, CreateInteractionResponse::Message(message))
+        }
+    }
+    .await?;
+
+    let Some(updated_auction) = auctions::load(&data.database, auction_id).await? else {
+        return Ok(());
+    };
+    if updated_auction.highest_bidder_id != Some(character_id)
+        || updated_auction.highest_bid != Some(amount)
+    {
+        // Bid failed, nothing else to update.
+        return Ok(());
+    }
+
+    update_auction_message(context, &updated_auction).await;
+    update_character_post(context, &data.database, &data.game, character_id).await;
+    let mut log_message = format!(
+        "{} bid {} {} on auction #{} ({}).",
+        updated_auction
+            .highest_bidder_name
+            .clone()
+            .unwrap_or_default(),
+        amount,
+        emoji::POKE_COIN,
+        auction.id,
+        auction.name
+    );
+    if let (Some(previous_bidder_id), Some(previous_bid), Some(previous_bidder_name)) = (
+        auction.highest_bidder_id,
+        auction.highest_bid,
+        &auction.highest_bidder_name,
+    ) {
+        if previous_bidder_id != character_id {
+            update_character_post(context, &data.database, &data.game, previous_bidder_id).await;
+        }
+        log_message.push_str(&format!(
+            " Refunded {} {} to {}.",
+            previous_bid,
+            emoji::POKE_COIN,
+            previous_bidder_name
+        ));
+    }
+
+    send_action_log(
+        context,
+        &data.database,
+        ActionLogEntry {
+            guild_id,
+            channel_id: interaction.channel_id.get() as i64,
+            actor: &interaction.user,
+            origin: format!(" in {}", interaction.message.link()),
+            action_type: &ActionType::Auction,
+            message: &log_message,
+            target: ActionLogTarget::character(character_id, Some(-amount)),
+        },
+    )
+    .await
+}
+
+async fn place_bid(
+    data: &Data,
+    auction: &Auction,
+    character_id: i64,
+    amount: i64,
+) -> Result<(), Error> {
+    let Some(character) = data.cache.get_character(character_id).await else {
+        return Err(Box::new(CommandInvocationError::new(format!(
+            "Unable to find character {} in cache.",
+            character_id
+        ))));
+    };
+
+    // Raising your own bid only requires the difference.
+    let required_money = if auction.highest_bidder_id == Some(character_id) {
+        amount - auction.highest_bid.unwrap_or(0)
+    } else {
+        amount
+    };
+    ensure_character_has_money(data, &character, required_money, "bid").await?;
+
+    auctions::place_bid(
+        &data.database,
+        auction,
+        character_id,
+        &character.name,
+        amount,
+        Utc::now().timestamp(),
+    )
+    .await
+}
+
+async fn update_auction_message(context: &Context, auction: &Auction) {
+    let _ = ChannelId::new(auction.channel_id as u64)
+        .edit_message(
+            context,
+            MessageId::new(auction.message_id as u64),
+            EditMessage::new()
+                .content(auction.build_message())
+                .components(auction.create_bid_buttons()),
+        )
+        .await;
+}
+
+pub async fn start_auction_thread(ctx: &Context, data: &Data) {
+    let ctx = Arc::new(ctx.clone());
+    if !data.is_auction_thread_running.load(Ordering::Relaxed) {
+        let ctx_in_thread = Arc::clone(&ctx);
+        let database = data.database.clone();
+        let game_data_in_thread = Arc::clone(&data.game);
+        let cache_in_thread = Arc::clone(&data.cache);
+        tokio::spawn(async move {
+            loop {
+                settle_due_auctions(
+                    &ctx_in_thread,
+                    &database,
+                    &game_data_in_thread,
+                    &cache_in_thread,
+                )
+                .await;
+                tokio::time::sleep(std::time::Duration::from_secs(
+                    SECONDS_BETWEEN_SETTLEMENT_CHECKS,
+                ))
+                .await;
+            }
+        });
+
+        data.is_auction_thread_running.swap(true, Ordering::Relaxed);
+    }
+}
+
+async fn settle_due_auctions(
+    ctx: &Arc<Context>,
+    database: &Pool<Sqlite>,
+    game_data: &Arc<GameData>,
+    cache: &Arc<Cache>,
+) {
+    let auction_ids = match auctions::get_due_auction_ids(database, Utc::now().timestamp()).await {
+        Ok(auction_ids) => auction_ids,
+        Err(error) => {
+            send_error_to_log_channel(ctx, error.to_string()).await;
+            return;
+        }
+    };
+
+    for auction_id in auction_ids {
+        if let Err(error) = settle_auction(ctx, database, game_data, cache, auction_id).await {
+            send_error_to_log_channel(
+                ctx,
+                format!("Unable to settle auction #{}: {}", auction_id, error),
+            )
+            .await;
+        }
+    }
+}
+
+async fn settle_auction(
+    ctx: &Arc<Context>,
+    database: &Pool<Sqlite>,
+    game_data: &Arc<GameData>,
+    cache: &Arc<Cache>,
+    auction_id: i64,
+) -> Result<(), Error> {
+    let Some(auction) = auctions::load(database, auction_id).await? else {
+        return Ok(());
+    };
+    if !auctions::settle(database, &auction).await? {
+        return Ok(());
+    }
+
+    let channel = ChannelId::new(auction.channel_id as u64);
+    let (announcement, target) = match (
+        auction.highest_bidder_id,
+        auction.highest_bid,
+        &auction.highest_bidder_name,
+    ) {
+        (Some(winner_id), Some(bid), Some(winner_name)) => {
+            let winner = cache.get_character(winner_id).await;
+            let mention = winner
+                .as_ref()
+                .map(|x| format!("<@{}> ", x.user_id))
+                .unwrap_or_default();
+            let mut announcement = format!(
+                "{}**{}** won the auction for **{}** with a bid of {} {}!",
+                mention,
+                winner_name,
+                auction.name,
+                bid,
+                emoji::POKE_COIN
+            );
+            if let (Some(item_name), Some(winner)) = (&auction.item_name, &winner) {
+                if let Err(e) = deliver_item(database, game_data, winner, item_name).await {
+                    announcement.push_str(&format!(
+                        "\n**Unable to deliver {}:** {}\nPlease ask a GM to hand it out manually.",
+                        item_name, e
+                    ));
+                }
+            }
+
+            update_wallet_post(ctx, database, auction.seller_wallet_id).await;
+            update_character_post(ctx, database, game_data, winner_id).await;
+            (
+                announcement,
+                ActionLogTarget::wallet(auction.seller_wallet_id, Some(bid)),
+            )
+        }
+        _ => (
+            format!(
+                "The auction for **{}** has ended without any bids.",
+                auction.name
+            ),
+            ActionLogTarget::wallet(auction.seller_wallet_id, None),
+        ),
+    };
+
+    if let Some(auction) = auctions::load(database, auction_id).await? {
+        update_auction_message(ctx, &auction).await;
+    }
+    let _ = channel
+        .send_message(ctx, CreateMessage::new().content(&announcement))
+        .await;
+
+    let bot = User::from(ctx.cache.current_user().clone());
+    send_action_log(
+        ctx,
+        database,
+        ActionLogEntry {
+            guild_id: auction.guild_id,
+            channel_id: auction.channel_id,
+            actor: &bot,
+            origin: format!(" because auction #{} has ended", auction.id),
+            action_type: &ActionType::Auction,
+            message: &announcement,
+            target,
+        },
+    )
+    .await
+}
+
+async fn deliver_item(
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    winner: &crate::cache::CharacterCacheItem,
+    item_name: &str,
+) -> Result<(), Error> {
+    let item = find_item(game_data, item_name)?;
+    let mut transaction = database.begin().await?;
+    add_item_to_inventory_in_transaction(&mut transaction, game_data, winner, item, 1).await?;
+    transaction.commit().await?;
+    Ok(())
+}
@@ -10,7 +10,8 @@ use serenity::client::Context;
 use crate::commands::{efficiency, learns};
 use crate::errors::CommandInvocationError;
 use crate::events::{
-    character_stat_edit, parse_interaction_command, quests, send_ephemeral_reply, FrameworkContext,
+    auctions, character_stat_edit, character_submission, parse_interaction_command, quests,
+    send_ephemeral_reply, undo, FrameworkContext,
 };
 use crate::{commands, emoji, helpers, Error};
 
@@ -154,6 +155,21 @@ pub async fn handle_button_interaction(
         "quest-history" => {
             return post_quest_history(context, &framework, interaction, args).await;
         }
+        "auction-bid" => {
+            return auctions::bid(context, interaction, framework.user_data, args).await;
+        }
+        "undo" => {
+            return undo::undo(context, interaction, framework.user_data, args).await;
+        }
+        "character-submission" => {
+            return character_submission::review_character_submission(
+                context,
+                interaction,
+                framework.user_data,
+                args,
+            )
+            .await;
+        }
         "ce" => {
             character_stat_edit::handle_character_editor_command(
                 context,
@@ -4,12 +4,12 @@ use serenity::all::{ComponentInteraction, Context, CreateInteractionResponse};
 
 use crate::character_stats::GenericCharacterStats;
 use crate::data::Data;
-use crate::enums::MysteryDungeonRank;
 use crate::events::character_stat_edit::{
     create_stat_edit_overview_message, reset_stat_edit_values, StatType,
 };
 use crate::events::send_error;
 use crate::game_data::PokemonApiId;
+use crate::guild_settings::GuildSettings;
 use crate::{helpers, Error};
 
 pub async fn initialize(
@@ -36,7 +36,7 @@ async fn initialize_combat(
     if let Some(character_id) = args.first() {
         let character_id = i64::from_str(character_id)?;
         let record = sqlx::query!(
-            "SELECT experience, species_api_id, species_override_for_stats,\
+            "SELECT guild_id, experience, species_api_id, species_override_for_stats, active_form_api_id, \
                       stat_strength, stat_dexterity, stat_vitality, stat_special, stat_insight 
                 FROM character WHERE id = ? AND user_id = ? \
                 ORDER BY rowid \
@@ -49,9 +49,8 @@ async fn initialize_combat(
 
         return match record {
             Ok(record) => {
-                let level = helpers::calculate_level_from_experience(record.experience);
-                let experience = record.experience % 100;
-                let rank = MysteryDungeonRank::from_level(level as u8);
+                let settings = GuildSettings::load(&data.database, record.guild_id).await;
+                let level = settings.calculate_level_from_experience(record.experience);
                 let pokemon = data
                     .game
                     .pokemon_by_api_id
@@ -65,7 +64,7 @@ async fn initialize_combat(
 
                 let pokemon_evolution_form_for_stats = helpers::get_usual_evolution_stage_for_level(
                     level,
-                    pokemon,
+                    helpers::get_active_form(pokemon, &data.game, record.active_form_api_id),
                     &data.game,
                     record.species_override_for_stats,
                 );
@@ -78,7 +77,7 @@ async fn initialize_combat(
                     record.stat_insight,
                 );
 
-                let remaining_points = helpers::calculate_available_combat_points(level)
+                let remaining_points = settings.calculate_available_combat_points(level)
                     - combat_stats.calculate_invested_stat_points();
 
                 if remaining_points <= 0 {
@@ -128,7 +127,7 @@ async fn initialize_social(
     if let Some(character_id) = args.first() {
         let character_id = i64::from_str(character_id)?;
         let record = sqlx::query!(
-            "SELECT experience, species_api_id, species_override_for_stats, \
+            "SELECT guild_id, experience, species_api_id, species_override_for_stats, \
                     stat_tough, stat_cool, stat_beauty, stat_cute, stat_clever
                 FROM character WHERE id = ? AND user_id = ? \
                 ORDER BY rowid \
@@ -141,9 +140,9 @@ async fn initialize_social(
 
         return match record {
             Ok(record) => {
-                let level = helpers::calculate_level_from_experience(record.experience);
-                let experience = record.experience % 100;
-                let rank = MysteryDungeonRank::from_level(level as u8);
+                let settings = GuildSettings::load(&data.database, record.guild_id).await;
+                let level = settings.calculate_level_from_experience(record.experience);
+                let rank = settings.calculate_rank(level);
                 let pokemon = data
                     .game
                     .pokemon_by_api_id
@@ -169,7 +168,7 @@ async fn initialize_social(
                     record.stat_clever,
                 );
 
-                let remaining_points = helpers::calculate_available_social_points(&rank) as i64
+                let remaining_points = settings.calculate_available_social_points(&rank)
                     - social_stats.calculate_invested_stat_points();
 
                 if remaining_points <= 0 {
@@ -5,11 +5,12 @@ use serenity::all::{
 use serenity::builder::CreateButton;
 use serenity::client::Context;
 
-use crate::character_stats::GenericCharacterStats;
+use crate::character_stats::{CharacterStat, GenericCharacterStats, SingleCharacterStatType};
 use crate::data::Data;
 use crate::enums::{Gender, MysteryDungeonRank};
 use crate::events::send_error;
 use crate::game_data::PokemonApiId;
+use crate::guild_settings::GuildSettings;
 use crate::{emoji, helpers, Error};
 
 mod initialize;
@@ -54,24 +55,58 @@ WHERE id = ?",
     .await;
 }
 
-fn create_combat_buttons(character_id: i64) -> Vec<CreateActionRow> {
+/// Maxed stats can only be increased further by limit breaking them, so make that obvious.
+fn create_add_button(
+    character_id: i64,
+    stat_name: &str,
+    label: &str,
+    stat: &CharacterStat,
+) -> CreateButton {
+    let button = CreateButton::new(format!("ce_edit-stat_{}_add_{}", character_id, stat_name));
+    if stat.is_at_or_above_max() {
+        button
+            .label(format!("{} Limit Break", label))
+            .style(ButtonStyle::Primary)
+    } else {
+        button
+            .label(format!("+{}", label))
+            .style(ButtonStyle::Success)
+    }
+}
+
+fn create_combat_buttons(character_id: i64, stats: &GenericCharacterStats) -> Vec<CreateActionRow> {
     vec![
         CreateActionRow::Buttons(vec![
-            CreateButton::new(format!("ce_edit-stat_{}_add_strength", character_id))
-                .label("+STR")
-                .style(ButtonStyle::Success),
-            CreateButton::new(format!("ce_edit-stat_{}_add_dexterity", character_id))
-                .label("+DEX")
-                .style(ButtonStyle::Success),
-            CreateButton::new(format!("ce_edit-stat_{}_add_vitality", character_id))
-                .label("+VIT")
-                .style(ButtonStyle::Success),
-            CreateButton::new(format!("ce_edit-stat_{}_add_special", character_id))
-                .label("+SPE")
-                .style(ButtonStyle::Success),
-            CreateButton::new(format!("ce_edit-stat_{}_add_insight", character_id))
-                .label("+INS")
-                .style(ButtonStyle::Success),
+            create_add_button(
+                character_id,
+                "strength",
+                "STR",
+                stats.get(SingleCharacterStatType::Strength),
+            ),
+            create_add_button(
+                character_id,
+                "dexterity",
+                "DEX",
+                stats.get(SingleCharacterStatType::Dexterity),
+            ),
+            create_add_button(
+                character_id,
+                "vitality",
+                "VIT",
+                stats.get(SingleCharacterStatType::Vitality),
+            ),
+            create_add_button(
+                character_id,
+                "special",
+                "SPE",
+                stats.get(SingleCharacterStatType::Special),
+            ),
+            create_add_button(
+                character_id,
+                "insight",
+                "INS",
+                stats.get(SingleCharacterStatType::Insight),
+            ),
         ]),
         CreateActionRow::Buttons(vec![
             CreateButton::new(format!("ce_edit-stat_{}_subtract_strength", character_id))
@@ -103,24 +138,39 @@ fn create_combat_buttons(character_id: i64) -> Vec<CreateActionRow> {
     ]
 }
 
-fn create_social_buttons(character_id: i64) -> Vec<CreateActionRow> {
+fn create_social_buttons(character_id: i64, stats: &GenericCharacterStats) -> Vec<CreateActionRow> {
     vec![
         CreateActionRow::Buttons(vec![
-            CreateButton::new(format!("ce_edit-stat_{}_add_tough", character_id))
-                .label("+Tough")
-                .style(ButtonStyle::Success),
-            CreateButton::new(format!("ce_edit-stat_{}_add_cool", character_id))
-                .label("+Cool")
-                .style(ButtonStyle::Success),
-            CreateButton::new(format!("ce_edit-stat_{}_add_beauty", character_id))
-                .label("+Beauty")
-                .style(ButtonStyle::Success),
-            CreateButton::new(format!("ce_edit-stat_{}_add_cute", character_id))
-                .label("+Cute")
-                .style(ButtonStyle::Success),
-            CreateButton::new(format!("ce_edit-stat_{}_add_clever", character_id))
-                .label("+Clever")
-                .style(ButtonStyle::Success),
+            create_add_button(
+                character_id,
+                "tough",
+                "Tough",
+                stats.get(SingleCharacterStatType::Tough),
+            ),
+            create_add_button(
+                character_id,
+                "cool",
+                "Cool",
+                stats.get(SingleCharacterStatType::Cool),
+            ),
+            create_add_button(
+                character_id,
+                "beauty",
+                "Beauty",
+                stats.get(SingleCharacterStatType::Beauty),
+            ),
+            create_add_button(
+                character_id,
+                "cute",
+                "Cute",
+                stats.get(SingleCharacterStatType::Cute),
+            ),
+            create_add_button(
+                character_id,
+                "clever",
+                "Clever",
+                stats.get(SingleCharacterStatType::Clever),
+            ),
         ]),
         CreateActionRow::Buttons(vec![
             CreateButton::new(format!("ce_edit-stat_{}_subtract_tough", character_id))
@@ -158,17 +208,18 @@ struct CharacterDataForStatEditing {
     emoji: String,
     level: i64,
     rank: MysteryDungeonRank,
+    settings: GuildSettings,
     combat_stats: GenericCharacterStats,
     social_stats: GenericCharacterStats,
 }
 
 impl CharacterDataForStatEditing {
     pub fn remaining_combat_points(&self) -> i64 {
-        helpers::calculate_available_combat_points(self.level)
+        self.settings.calculate_available_combat_points(self.level)
             - self.combat_stats.calculate_invested_stat_points()
     }
     pub fn remaining_social_points(&self) -> i64 {
-        helpers::calculate_available_social_points(&self.rank) as i64
+        self.settings.calculate_available_social_points(&self.rank)
             - self.social_stats.calculate_invested_stat_points()
     }
 }
@@ -183,7 +234,7 @@ async fn get_character_data_for_edit(
                       stat_edit_strength, stat_edit_dexterity, stat_edit_vitality, stat_edit_special, stat_edit_insight,
                       stat_tough, stat_cool, stat_beauty, stat_cute, stat_clever,
                       stat_edit_tough, stat_edit_cool, stat_edit_beauty, stat_edit_cute, stat_edit_clever,
-                      species_override_for_stats
+                      species_override_for_stats, active_form_api_id
                 FROM character WHERE id = ? \
                 ORDER BY rowid \
                 LIMIT 1",
@@ -193,8 +244,9 @@ async fn get_character_data_for_edit(
         .await
         .unwrap();
 
-    let level = helpers::calculate_level_from_experience(record.experience);
-    let rank = MysteryDungeonRank::from_level(level as u8);
+    let settings = GuildSettings::load(&data.database, record.guild_id).await;
+    let level = settings.calculate_level_from_experience(record.experience);
+    let rank = settings.calculate_rank(level);
     let pokemon = data
         .game
         .pokemon_by_api_id
@@ -218,7 +270,7 @@ async fn get_character_data_for_edit(
 
     let pokemon_evolution_form_for_stats = helpers::get_usual_evolution_stage_for_level(
         level,
-        pokemon,
+        helpers::get_active_form(pokemon, &data.game, record.active_form_api_id),
         &data.game,
         record.species_override_for_stats,
     );
@@ -255,6 +307,7 @@ async fn get_character_data_for_edit(
         emoji,
         level,
         rank,
+        settings,
         combat_stats,
         social_stats,
     }
@@ -342,8 +395,8 @@ async fn create_stat_edit_overview_message(
         content: message,
         ephemeral: true,
         components: match stat_type {
-            StatType::Combat => create_combat_buttons(character_id),
-            StatType::Social => create_social_buttons(character_id),
+            StatType::Combat => create_combat_buttons(character_id, stats),
+            StatType::Social => create_social_buttons(character_id, stats),
         },
     }
 }
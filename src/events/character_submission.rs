@@ -0,0 +1,431 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+use std::time::Duration;
+
+use poise::Modal;
+use serenity::all::{
+    ChannelId, ComponentInteraction, CreateInteractionResponse, CreateInteractionResponseFollowup,
+    CreateMessage, EditMessage, GuildId, Mentionable, ModalInteractionCollector, UserId,
+};
+use serenity::client::Context;
+
+use crate::character_submission::{CharacterSubmission, APPROVE, REJECT, REQUEST_CHANGES};
+use crate::commands::characters::{
+    insert_character, post_placeholder_stat_message_in_channel, send_action_log, ActionLogEntry,
+    ActionLogTarget, ActionType, NewCharacter, DEFAULT_STARTING_MONEY,
+};
+use crate::commands::{create_emojis, is_user_admin_or_gm};
+use crate::data::Data;
+use crate::enums::CharacterSubmissionStatus;
+use crate::errors::{CommandInvocationError, ValidationError};
+use crate::events::{send_error, update_character_post};
+use crate::game_data::PokemonApiId;
+use crate::{character_submission, emoji, Error};
+
+const FEEDBACK_TIMEOUT: Duration = Duration::from_secs(60 * 10);
+
+#[derive(Debug, Modal)]
+#[name = "Review Feedback"]
+struct FeedbackModal {
+    #[name = "Feedback"]
+    #[placeholder = "What should the player know?"]
+    #[paragraph]
+    #[max_length = 500]
+    feedback: Option<String>,
+}
+
+pub async fn review_character_submission(
+    context: &Context,
+    interaction: &&ComponentInteraction,
+    data: &Data,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    let (Some(action), Some(Ok(submission_id))) =
+        (args.first(), args.get(1).map(|x| i64::from_str(x)))
+    else {
+        return Err(Box::new(
+            CommandInvocationError::new(format!(
+                "Invalid character submission request: {:?}",
+                args
+            ))
+            .log(),
+        ));
+    };
+
+    let is_admin_or_gm = interaction
+        .member
+        .as_ref()
+        .is_some_and(|x| is_user_admin_or_gm(Cow::Borrowed(x)));
+    if !is_admin_or_gm {
+        return send_error(
+            interaction,
+            context,
+            "Only GMs can review character submissions.",
+        )
+        .await;
+    }
+
+    let Some(submission) = CharacterSubmission::load(&data.database, submission_id).await? else {
+        return send_error(
+            interaction,
+            context,
+            "This submission doesn't exist anymore.",
+        )
+        .await;
+    };
+    if submission.status != CharacterSubmissionStatus::Pending {
+        return send_error(
+            interaction,
+            context,
+            "This submission has already been reviewed.",
+        )
+        .await;
+    }
+
+    match *action {
+        APPROVE => approve(context, interaction, data, submission).await,
+        REJECT => {
+            decline(
+                context,
+                interaction,
+                data,
+                submission,
+                CharacterSubmissionStatus::Rejected,
+            )
+            .await
+        }
+        REQUEST_CHANGES => {
+            decline(
+                context,
+                interaction,
+                data,
+                submission,
+                CharacterSubmissionStatus::ChangesRequested,
+            )
+            .await
+        }
+        _ => Err(Box::new(
+            CommandInvocationError::new(format!("Unknown character submission action: {}", action))
+                .log(),
+        )),
+    }
+}
+
+async fn approve(
+    context: &Context,
+    interaction: &&ComponentInteraction,
+    data: &Data,
+    submission: CharacterSubmission,
+) -> Result<(), Error> {
+    let reviewer_id = interaction.user.id.get() as i64;
+    let status = CharacterSubmissionStatus::Approved;
+    if !character_submission::resolve(&data.database, submission.id, status, reviewer_id, None)
+        .await?
+    {
+        return send_error(
+            interaction,
+            context,
+            "This submission has already been reviewed.",
+        )
+        .await;
+    }
+
+    // Creating emojis might take a while.
+    interaction
+        .create_response(context, CreateInteractionResponse::Acknowledge)
+        .await?;
+
+    let character_id = match create_character(context, data, &submission).await {
+        Ok(character_id) => character_id,
+        Err(e) => {
+            character_submission::reopen(&data.database, submission.id).await?;
+            interaction
+                .create_followup(
+                    context,
+                    CreateInteractionResponseFollowup::new()
+                        .ephemeral(true)
+                        .content(format!("Was unable to create the character: {}", e)),
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let pokemon = data
+        .game
+        .pokemon_by_api_id
+        .get(&PokemonApiId(submission.species_api_id as u16))
+        .expect("Pokemon was validated during character creation");
+    let results = create_emojis::create_missing_emojis_for_pokemon(
+        &context.http,
+        &data.database,
+        GuildId::new(submission.guild_id as u64),
+        pokemon,
+        &submission.gender,
+        submission.is_shiny,
+    )
+    .await;
+    for notification in results.into_iter().map(|x| x.unwrap_or_else(|e| e)) {
+        interaction
+            .create_followup(
+                context,
+                CreateInteractionResponseFollowup::new()
+                    .ephemeral(true)
+                    .content(notification),
+            )
+            .await?;
+    }
+
+    update_character_post(context, &data.database, &data.game, character_id).await;
+    data.cache.update_character_names(&data.database).await;
+    update_review_message(context, interaction, data, submission.id).await?;
+
+    let stat_channel = ChannelId::new(submission.channel_id as u64);
+    notify_player(
+        context,
+        &submission,
+        format!(
+            "**{}** has been approved! Have a look at their character post in {}.",
+            submission.name,
+            stat_channel.mention()
+        ),
+    )
+    .await;
+
+    send_action_log(
+        context,
+        &data.database,
+        ActionLogEntry {
+            guild_id: submission.guild_id,
+            channel_id: interaction.channel_id.get() as i64,
+            actor: &interaction.user,
+            origin: format!(" in {}", interaction.message.link()),
+            action_type: &ActionType::Initialization,
+            message: &format!(
+                "Initialized character {} ({}) with {} {} and 0 exp.",
+                submission.name,
+                pokemon.name,
+                DEFAULT_STARTING_MONEY,
+                emoji::POKE_COIN
+            ),
+            target: ActionLogTarget::character(character_id, None),
+        },
+    )
+    .await
+}
+
+/// Posts the character post into the channel the character was submitted in and creates the character,
+/// just like /create_character would.
+async fn create_character(
+    context: &Context,
+    data: &Data,
+    submission: &CharacterSubmission,
+) -> Result<i64, Error> {
+    let Some(pokemon) = data
+        .game
+        .pokemon_by_api_id
+        .get(&PokemonApiId(submission.species_api_id as u16))
+    else {
+        return Err(Box::new(CommandInvocationError::new(format!(
+            "Unable to find a pokemon with ID {}.",
+            submission.species_api_id
+        ))));
+    };
+
+    let Some(message) = post_placeholder_stat_message_in_channel(
+        context,
+        ChannelId::new(submission.channel_id as u64),
+    )
+    .await?
+    else {
+        return Err(Box::new(ValidationError::new(format!(
+            "{} doesn't look like a dedicated character channel. Please ask the player to submit their character from within a new channel.",
+            ChannelId::new(submission.channel_id as u64).mention()
+        ))));
+    };
+
+    let character_id = match insert_character(
+        &data.database,
+        &data.game,
+        NewCharacter {
+            user_id: submission.user_id,
+            guild_id: submission.guild_id,
+            name: &submission.name,
+            pokemon,
+            gender: submission.gender,
+            is_shiny: submission.is_shiny,
+            experience: 0,
+            money: DEFAULT_STARTING_MONEY,
+            stat_channel_id: message.channel_id.get() as i64,
+            stat_message_id: message.id.get() as i64,
+        },
+    )
+    .await
+    {
+        Ok(character_id) => character_id,
+        Err(e) => {
+            let _ = message.delete(context).await;
+            return Err(Box::new(e));
+        }
+    };
+
+    sqlx::query!(
+        "UPDATE character SET backstory = ? WHERE id = ?",
+        submission.backstory,
+        character_id
+    )
+    .execute(&data.database)
+    .await?;
+    sqlx::query!(
+        "UPDATE character_submission SET character_id = ? WHERE id = ?",
+        character_id,
+        submission.id
+    )
+    .execute(&data.database)
+    .await?;
+
+    Ok(character_id)
+}
+
+async fn decline(
+    context: &Context,
+    interaction: &&ComponentInteraction,
+    data: &Data,
+    submission: CharacterSubmission,
+    status: CharacterSubmissionStatus,
+) -> Result<(), Error> {
+    let Some(feedback) = await_feedback(context, interaction).await? else {
+        return Ok(());
+    };
+
+    let feedback = feedback
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty());
+    let reviewer_id = interaction.user.id.get() as i64;
+    if !character_submission::resolve(
+        &data.database,
+        submission.id,
+        status,
+        reviewer_id,
+        feedback.as_deref(),
+    )
+    .await?
+    {
+        interaction
+            .create_followup(
+                context,
+                CreateInteractionResponseFollowup::new()
+                    .ephemeral(true)
+                    .content("This submission has already been reviewed."),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    update_review_message(context, interaction, data, submission.id).await?;
+
+    let mut message = match status {
+        CharacterSubmissionStatus::ChangesRequested => format!(
+            "A GM has requested some changes to **{}**. Feel free to submit them again once you're done!",
+            submission.name
+        ),
+        _ => format!("Sorry, **{}** has been rejected.", submission.name),
+    };
+    if let Some(feedback) = &feedback {
+        message.push_str(&format!("\n**Feedback:** {}", feedback));
+    }
+    notify_player(context, &submission, message).await;
+
+    send_action_log(
+        context,
+        &data.database,
+        ActionLogEntry {
+            guild_id: submission.guild_id,
+            channel_id: interaction.channel_id.get() as i64,
+            actor: &interaction.user,
+            origin: format!(" in {}", interaction.message.link()),
+            action_type: &ActionType::CharacterSubmission,
+            message: &format!(
+                "{} the submission for {}.",
+                match status {
+                    CharacterSubmissionStatus::ChangesRequested => "Requested changes to",
+                    _ => "Rejected",
+                },
+                submission.name
+            ),
+            target: ActionLogTarget::default(),
+        },
+    )
+    .await
+}
+
+/// Shows the feedback modal to the reviewer. Returns None if they didn't submit it in time.
+/// poise::execute_modal_on_component_interaction requires a poise context, which we don't have in here.
+async fn await_feedback(
+    context: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<Option<Option<String>>, Error> {
+    let modal_custom_id = interaction.id.to_string();
+    interaction
+        .create_response(
+            context,
+            FeedbackModal::create(None, modal_custom_id.clone()),
+        )
+        .await?;
+
+    let Some(response) = ModalInteractionCollector::new(&context.shard)
+        .filter(move |x| x.data.custom_id == modal_custom_id)
+        .timeout(FEEDBACK_TIMEOUT)
+        .await
+    else {
+        return Ok(None);
+    };
+
+    response
+        .create_response(context, CreateInteractionResponse::Acknowledge)
+        .await?;
+    let modal = FeedbackModal::parse(response.data.clone())
+        .map_err(|e| CommandInvocationError::new(e).log())?;
+
+    Ok(Some(modal.feedback))
+}
+
+async fn update_review_message(
+    context: &Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    submission_id: i64,
+) -> Result<(), Error> {
+    let Some(submission) = CharacterSubmission::load(&data.database, submission_id).await? else {
+        return Ok(());
+    };
+
+    let mut message = interaction.message.clone();
+    message
+        .edit(
+            context,
+            EditMessage::new()
+                .content(submission.build_review_message(&data.game))
+                .components(Vec::new()),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Sends the player a DM, or pings them in the channel they submitted their character in if their DMs are closed.
+async fn notify_player(context: &Context, submission: &CharacterSubmission, content: String) {
+    let user_id = UserId::new(submission.user_id as u64);
+    if user_id
+        .direct_message(context, CreateMessage::new().content(&content))
+        .await
+        .is_err()
+    {
+        let _ = ChannelId::new(submission.channel_id as u64)
+            .send_message(
+                context,
+                CreateMessage::new().content(format!("{} {}", user_id.mention(), content)),
+            )
+            .await;
+    }
+}
@@ -13,13 +13,17 @@ use crate::data::Data;
 use crate::game_data::GameData;
 use crate::{helpers, Error};
 
+mod auctions;
 mod backups;
 mod button_interaction;
 mod character_stat_edit;
+mod character_submission;
 mod monthly_reset;
 mod quests;
+mod recurring_payouts;
 mod role_reaction;
 mod select_menu_interaction;
+mod undo;
 mod weekly_reset;
 
 type FrameworkContext<'a> = poise::FrameworkContext<'a, Data, Error>;
@@ -83,6 +87,7 @@ pub async fn handle_events<'a>(
             backups::start_backup_thread(context, framework.user_data).await;
             weekly_reset::start_weekly_reset_thread(context, framework.user_data).await;
             monthly_reset::start_monthly_reset_thread(context, framework.user_data).await;
+            auctions::start_auction_thread(context, framework.user_data).await;
             Ok(())
         }
         _ => Ok(()),
@@ -337,6 +342,34 @@ async fn update_character_post<'a>(
     }
 }
 
+async fn update_wallet_post(ctx: &Context, database: &Pool<Sqlite>, id: i64) {
+    if let Some(result) = crate::commands::wallets::build_wallet_string(database, id).await {
+        let message = ctx
+            .http
+            .get_message(
+                ChannelId::from(result.stat_channel_id as u64),
+                MessageId::from(result.stat_message_id as u64),
+            )
+            .await;
+        if let Ok(mut message) = message {
+            if let Err(e) = message
+                .edit(ctx, EditMessage::new().content(&result.message))
+                .await
+            {
+                handle_error_during_message_edit(
+                    ctx,
+                    e,
+                    message,
+                    result.message,
+                    None,
+                    result.name,
+                )
+                .await;
+            }
+        }
+    }
+}
+
 async fn handle_error_during_message_edit(
     ctx: &Context,
     e: serenity::Error,
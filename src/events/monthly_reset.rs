@@ -1,15 +1,18 @@
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 
-use chrono::{Datelike, NaiveDate, Utc};
+use chrono::{Datelike, Months, NaiveDate, Utc};
 use serenity::all::CreateMessage;
 use serenity::model::id::ChannelId;
 use serenity::prelude::Context;
 use sqlx::{Pool, Sqlite};
 
 use crate::data::Data;
+use crate::enums::{PayoutSchedule, TeraChargeReset};
+use crate::events::recurring_payouts::execute_recurring_payouts;
 use crate::events::{send_error_to_log_channel, update_character_post};
 use crate::game_data::GameData;
+use crate::{helpers, tera_charges, wallet_statement};
 
 pub async fn start_monthly_reset_thread(ctx: &Context, data: &Data) {
     let ctx = Arc::new(ctx.clone());
@@ -64,51 +67,98 @@ async fn execute_monthly_reset(
     database: Pool<Sqlite>,
     game_data: Arc<GameData>,
 ) {
-    match sqlx::query!(
-        "UPDATE character SET 
-tera_used_normal = 0,
-tera_used_fighting = 0,
-tera_used_flying = 0,
-tera_used_poison = 0,
-tera_used_ground = 0,
-tera_used_rock = 0,
-tera_used_bug = 0,
-tera_used_ghost = 0,
-tera_used_steel = 0,
-tera_used_fire = 0,
-tera_used_water = 0,
-tera_used_grass = 0,
-tera_used_electric = 0,
-tera_used_psychic = 0,
-tera_used_ice = 0,
-tera_used_dragon = 0,
-tera_used_dark = 0,
-tera_used_fairy = 0
-    RETURNING id, is_retired
-"
-    )
-    .fetch_all(&database)
-    .await
-    {
-        Ok(records) => {
+    match tera_charges::reset_used_charges(&database, TeraChargeReset::Monthly).await {
+        Ok(character_ids) => {
             notify_guilds(&ctx, &database).await;
-            for record in records {
-                if record.is_retired {
-                    continue;
-                }
-                update_character_post(&ctx, &database, &game_data, record.id).await;
+            for character_id in character_ids {
+                update_character_post(&ctx, &database, &game_data, character_id).await;
             }
         }
         Err(error) => {
             send_error_to_log_channel(&ctx, error.to_string()).await;
         }
     }
+
+    execute_recurring_payouts(&ctx, &database, &game_data, PayoutSchedule::Monthly).await;
+    post_wallet_statements(&ctx, &database).await;
 }
 
-async fn notify_guilds(ctx: &Arc<Context>, database: &Pool<Sqlite>) {
-    match sqlx::query!("SELECT action_log_channel_id FROM guild")
-        .fetch_all(database)
+/// Posts last month's statement into the channel of every wallet which opted into it.
+async fn post_wallet_statements(ctx: &Arc<Context>, database: &Pool<Sqlite>) {
+    let now = Utc::now().date_naive();
+    let this_month = NaiveDate::from_ymd_opt(now.year(), now.month(), 1)
+        .expect("First day of the month should always be valid");
+    let last_month = this_month
+        .checked_sub_months(Months::new(1))
+        .expect("Previous month should always be valid");
+    let from = last_month
+        .and_hms_opt(0, 0, 0)
+        .expect("Midnight should always be valid")
+        .and_utc()
+        .timestamp();
+    let until = this_month
+        .and_hms_opt(0, 0, 0)
+        .expect("Midnight should always be valid")
+        .and_utc()
+        .timestamp();
+
+    let wallets = match sqlx::query!(
+        "SELECT id, guild_id, name, bot_message_channel_id FROM wallet WHERE post_monthly_statement = true"
+    )
+    .fetch_all(database)
+    .await
+    {
+        Ok(wallets) => wallets,
+        Err(error) => {
+            send_error_to_log_channel(ctx, error.to_string()).await;
+            return;
+        }
+    };
+
+    for wallet in wallets {
+        let entries = match wallet_statement::load_entries(
+            database,
+            wallet.guild_id,
+            Some(wallet.id),
+            Some(from),
+            Some(until),
+        )
         .await
+        {
+            Ok(entries) => entries,
+            Err(error) => {
+                send_error_to_log_channel(ctx, error.to_string()).await;
+                continue;
+            }
+        };
+
+        let statement = wallet_statement::build_statement_string(
+            &wallet.name,
+            &entries,
+            Some(from),
+            Some(until),
+        );
+        let channel = ChannelId::from(wallet.bot_message_channel_id as u64);
+        for part in helpers::split_long_messages(statement) {
+            let _ = channel
+                .send_message(&ctx, CreateMessage::new().content(part))
+                .await;
+        }
+    }
+}
+
+async fn notify_guilds(ctx: &Arc<Context>, database: &Pool<Sqlite>) {
+    let default_reset = TeraChargeReset::default() as i64;
+    let monthly_reset = TeraChargeReset::Monthly as i64;
+    match sqlx::query!(
+        "SELECT guild.action_log_channel_id FROM guild \
+            LEFT JOIN guild_settings ON guild_settings.guild_id = guild.id \
+         WHERE COALESCE(guild_settings.tera_charge_reset, ?) = ?",
+        default_reset,
+        monthly_reset
+    )
+    .fetch_all(database)
+    .await
     {
         Ok(records) => {
             let channel_ids: Vec<i64> = records
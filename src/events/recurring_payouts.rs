@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use serenity::all::User;
+use serenity::prelude::Context;
+use sqlx::{Pool, Sqlite};
+
+use crate::commands::characters::{send_action_log, ActionLogEntry, ActionLogTarget, ActionType};
+use crate::enums::PayoutSchedule;
+use crate::events::{send_error_to_log_channel, update_character_post, update_wallet_post};
+use crate::game_data::GameData;
+use crate::recurring_payouts::RecurringPayout;
+use crate::{emoji, recurring_payouts};
+
+/// Executes all payouts for the given schedule. Payouts which can't be afforded are skipped and logged.
+/// Weekly payouts don't refresh any posts, as forum threads can't be reopened without sending a message yet.
+pub async fn execute_recurring_payouts(
+    ctx: &Arc<Context>,
+    database: &Pool<Sqlite>,
+    game_data: &Arc<GameData>,
+    schedule: PayoutSchedule,
+) {
+    let payouts = match recurring_payouts::load(database, None, Some(schedule)).await {
+        Ok(payouts) => payouts,
+        Err(error) => {
+            send_error_to_log_channel(ctx, error.to_string()).await;
+            return;
+        }
+    };
+
+    let bot = User::from(ctx.cache.current_user().clone());
+    for payout in payouts {
+        let message = match recurring_payouts::execute(database, &payout).await {
+            Ok(_) => {
+                if schedule != PayoutSchedule::Weekly {
+                    update_posts(ctx, database, game_data, &payout).await;
+                }
+
+                format!(
+                    "Paid out {} {} from {} to {}.",
+                    payout.amount,
+                    emoji::POKE_COIN,
+                    payout.source_wallet_name,
+                    payout.target_name
+                )
+            }
+            Err(error) => format!(
+                "Skipped payout #{} from {} to {}: {}",
+                payout.id,
+                payout.source_wallet_name,
+                payout.target_name,
+                error.to_string().replace('\n', " ")
+            ),
+        };
+
+        let _ = send_action_log(
+            ctx,
+            database,
+            ActionLogEntry {
+                guild_id: payout.guild_id,
+                channel_id: payout.source_wallet_channel_id,
+                actor: &bot,
+                origin: format!(" through {} recurring payout", payout.schedule),
+                action_type: &ActionType::RecurringPayout,
+                message: &message,
+                target: build_log_target(&payout),
+            },
+        )
+        .await;
+    }
+}
+
+async fn update_posts(
+    ctx: &Arc<Context>,
+    database: &Pool<Sqlite>,
+    game_data: &Arc<GameData>,
+    payout: &RecurringPayout,
+) {
+    if let Some(wallet_id) = payout.source_wallet_id {
+        update_wallet_post(ctx, database, wallet_id).await;
+    }
+    if let Some(character_id) = payout.target_character_id {
+        update_character_post(ctx, database, game_data, character_id).await;
+    }
+    if let Some(wallet_id) = payout.target_wallet_id {
+        update_wallet_post(ctx, database, wallet_id).await;
+    }
+}
+
+fn build_log_target(payout: &RecurringPayout) -> ActionLogTarget {
+    match (payout.target_character_id, payout.target_wallet_id) {
+        (Some(character_id), _) => ActionLogTarget::character(character_id, Some(payout.amount)),
+        (_, Some(wallet_id)) => ActionLogTarget::wallet(wallet_id, Some(payout.amount)),
+        _ => ActionLogTarget::default(),
+    }
+}
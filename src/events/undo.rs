@@ -0,0 +1,113 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use serenity::all::{
+    ComponentInteraction, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use serenity::client::Context;
+
+use crate::commands::characters::{
+    get_stat_display_name, send_action_log, ActionLogEntry, ActionLogTarget, ActionType,
+};
+use crate::commands::is_user_admin_or_gm;
+use crate::data::Data;
+use crate::errors::CommandInvocationError;
+use crate::events::{send_error, update_character_post, update_wallet_post};
+use crate::ledger::RevertedLedgerEntry;
+use crate::{ledger, Error};
+
+pub async fn undo(
+    context: &Context,
+    interaction: &&ComponentInteraction,
+    data: &Data,
+    args: Vec<&str>,
+) -> Result<(), Error> {
+    let Ok(interaction_id) = u64::from_str(args[0]) else {
+        return Err(Box::new(
+            CommandInvocationError::new(format!("Invalid interaction ID in request: {}", args[0]))
+                .log(),
+        ));
+    };
+    let guild_id = interaction
+        .guild_id
+        .expect("Undo buttons should only exist in guilds")
+        .get() as i64;
+    let is_admin_or_gm = interaction
+        .member
+        .as_ref()
+        .is_some_and(|x| is_user_admin_or_gm(Cow::Borrowed(x)));
+
+    let entries = match ledger::revert_interaction(
+        data,
+        interaction_id as i64,
+        interaction.id.get() as i64,
+        interaction.user.id.get() as i64,
+        is_admin_or_gm,
+    )
+    .await
+    {
+        Ok(entries) => entries,
+        Err(e) => return send_error(interaction, context, &e.to_string()).await,
+    };
+
+    interaction
+        .create_response(
+            context,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new().components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    let text = build_undo_message(&entries);
+    interaction
+        .message
+        .reply(context, format!("**Undone!**\n{}", text))
+        .await?;
+
+    let mut updated_characters = HashSet::new();
+    let mut updated_wallets = HashSet::new();
+    for entry in &entries {
+        if let Some(id) = entry.character_id {
+            if updated_characters.insert(id) {
+                update_character_post(context, &data.database, &data.game, id).await;
+            }
+        }
+        if let Some(id) = entry.wallet_id {
+            if updated_wallets.insert(id) {
+                update_wallet_post(context, &data.database, id).await;
+            }
+        }
+    }
+
+    send_action_log(
+        context,
+        &data.database,
+        ActionLogEntry {
+            guild_id,
+            channel_id: interaction.channel_id.get() as i64,
+            actor: &interaction.user,
+            origin: format!(" in {}", interaction.message.link()),
+            action_type: &ActionType::Undo,
+            message: &text.replace('\n', ", "),
+            target: ActionLogTarget::default(),
+        },
+    )
+    .await
+}
+
+fn build_undo_message(entries: &[RevertedLedgerEntry]) -> String {
+    entries
+        .iter()
+        .map(|x| {
+            let stat = get_stat_display_name(&x.stat);
+            if x.amount > 0 {
+                format!("Removed {} {} from {}", x.amount, stat, x.target_name)
+            } else {
+                format!("Added {} {} to {}", -x.amount, stat, x.target_name)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -8,7 +8,11 @@ use serenity::prelude::Context;
 use sqlx::{Pool, Sqlite};
 
 use crate::data::Data;
+use crate::enums::{PayoutSchedule, TeraChargeReset};
+use crate::events::recurring_payouts::execute_recurring_payouts;
 use crate::events::send_error_to_log_channel;
+use crate::game_data::GameData;
+use crate::tera_charges;
 
 // Added: Seasonal constants
 const SEASON_CHANNEL_ID: u64 = 1290754769140580353; // Channel for season announcements
@@ -19,10 +23,16 @@ pub async fn start_weekly_reset_thread(ctx: &Context, data: &Data) {
     if !data.is_weekly_reset_thread_running.load(Ordering::Relaxed) {
         let ctx_in_thread = Arc::clone(&ctx);
         let database = data.database.clone();
+        let game_data_in_thread = Arc::clone(&data.game);
         tokio::spawn(async move {
             loop {
                 tokio::time::sleep(calculate_duration_until_next_run()).await;
-                execute_weekly_reset(Arc::clone(&ctx_in_thread), database.clone()).await;
+                execute_weekly_reset(
+                    Arc::clone(&ctx_in_thread),
+                    database.clone(),
+                    Arc::clone(&game_data_in_thread),
+                )
+                .await;
             }
         });
 
@@ -49,7 +59,7 @@ fn calculate_duration_until_next_run() -> std::time::Duration {
     std::time::Duration::from_secs(seconds_until_next_run)
 }
 
-async fn execute_weekly_reset(ctx: Arc<Context>, database: Pool<Sqlite>) {
+async fn execute_weekly_reset(ctx: Arc<Context>, database: Pool<Sqlite>, game_data: Arc<GameData>) {
     match sqlx::query!("UPDATE character SET weekly_spar_count = 0")
         .execute(&database)
         .await
@@ -57,33 +67,45 @@ async fn execute_weekly_reset(ctx: Arc<Context>, database: Pool<Sqlite>) {
         Ok(_) => {
             notify_guilds(&ctx, &database).await;
             announce_season(&ctx).await; // Added: Announce the new season
-            notify_dragapult_bidding_over(&ctx).await; // Added: Notify Dragapult bidding is over
             // Updating character posts is disabled until we figure out how to reopen forum threads without sending a message...
         }
         Err(error) => {
             send_error_to_log_channel(&ctx, error.to_string()).await;
         }
     }
+
+    // Character posts aren't refreshed here for the same reason as above.
+    if let Err(error) = tera_charges::reset_used_charges(&database, TeraChargeReset::Weekly).await {
+        send_error_to_log_channel(&ctx, error.to_string()).await;
+    }
+
+    execute_recurring_payouts(&ctx, &database, &game_data, PayoutSchedule::Weekly).await;
 }
 
 async fn notify_guilds(ctx: &Arc<Context>, database: &Pool<Sqlite>) {
-    match sqlx::query!("SELECT action_log_channel_id FROM guild")
-        .fetch_all(database)
-        .await
+    let default_reset = TeraChargeReset::default() as i64;
+    match sqlx::query!(
+        "SELECT guild.action_log_channel_id, COALESCE(guild_settings.tera_charge_reset, ?) as \"tera_charge_reset!: i64\" FROM guild \
+            LEFT JOIN guild_settings ON guild_settings.guild_id = guild.id",
+        default_reset
+    )
+    .fetch_all(database)
+    .await
     {
         Ok(records) => {
-            let channel_ids: Vec<i64> = records
-                .iter()
-                .filter_map(|x| x.action_log_channel_id)
-                .collect();
+            for record in records {
+                let Some(action_log_channel_id) = record.action_log_channel_id else {
+                    continue;
+                };
+
+                let mut message = String::from("📅 [System] Performing weekly reset.\n- Weekly Spar counts have been reset.");
+                if record.tera_charge_reset == TeraChargeReset::Weekly as i64 {
+                    message.push_str("\n- Terastallization Charges have been reset.");
+                }
 
-            for action_log_channel_id in channel_ids {
                 let channel = ChannelId::from(action_log_channel_id as u64);
                 let _ = channel
-                    .send_message(
-                        &ctx,
-                        CreateMessage::new().content("📅 [System] Performing weekly reset.\n- Weekly Spar counts have been reset."),
-                    )
+                    .send_message(&ctx, CreateMessage::new().content(message))
                     .await;
             }
         }
@@ -131,20 +153,3 @@ fn get_current_season() -> &'static str {
 
     SEASONS[season_index]
 }
-
-// Added: Function to notify about Dragapult's Hoarded Goods Bidding being over
-async fn notify_dragapult_bidding_over(ctx: &Arc<Context>) {
-    let forum_thread_id = ChannelId::new(1271951519540842617); // Forum thread ID for Dragapult's Hoarded Goods
-
-    if let Err(error) = forum_thread_id
-        .send_message(
-            &ctx,
-            CreateMessage::new().content(format!(
-                "🚨 [System] Bidding is over! <@264426311789969408>",
-            )),
-        )
-        .await
-    {
-        send_error_to_log_channel(ctx, error.to_string()).await;
-    }
-}
@@ -0,0 +1,33 @@
+use serde_with::{serde_as, NoneAsEmptyString};
+
+/// The character statistics which can be used as achievement requirements.
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AchievementCounter {
+    CompletedQuests,
+    RpSessions,
+    GivenTours,
+    GivenCombatTutorials,
+    BattlePoints,
+}
+
+/// Awarded to characters once their counter reaches the threshold, e.g. "complete 10 quests".
+#[serde_as]
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct Achievement {
+    pub name: String,
+    pub description: String,
+    #[serde_as(as = "NoneAsEmptyString")]
+    #[serde(default)]
+    pub emoji: Option<String>,
+    pub counter: AchievementCounter,
+    pub threshold: i64,
+}
+
+impl Achievement {
+    pub fn badge(&self) -> String {
+        match &self.emoji {
+            Some(emoji) => format!("{} {}", emoji, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
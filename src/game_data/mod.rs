@@ -4,6 +4,7 @@ use std::sync::Arc;
 use crate::game_data::zmove::ZMove;
 
 pub(crate) mod ability;
+pub(crate) mod achievement;
 pub(crate) mod item;
 pub(crate) mod r#move;
 pub mod zmove;
@@ -24,6 +25,7 @@ pub(crate) mod type_efficiency;
 
 /// Data which is stored and accessible in all command invocations
 pub struct GameData {
+    pub achievements: Arc<Vec<achievement::Achievement>>,
     pub abilities: Arc<HashMap<String, ability::Ability>>,
     pub ability_names: Arc<Vec<String>>,
     pub potions: Arc<HashMap<String, potion::Potion>>,
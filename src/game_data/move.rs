@@ -163,26 +163,15 @@ impl Move {
             result.push_str(self.target.to_string().as_str());
             result.push('\n');
 
-            if self.damage1.is_some() || self.happiness_damage.is_some() || self.power > 0 {
+            if let Some(damage_dice) = self.build_damage_dice_string() {
                 result.push_str("**Damage Dice**: ");
-                if let Some(stat) = self.damage1 {
-                    result.push_str(std::format!("{}", stat).as_str());
-                    result.push_str(" + ");
-                }
-                if let Some(stat) = self.happiness_damage {
-                    result.push_str(std::format!("{}", stat).as_str());
-                    result.push_str(" + ");
-                }
-                result.push_str(&std::format!("{}\n", self.power));
+                result.push_str(&damage_dice);
+                result.push('\n');
             }
 
             result.push_str("**Accuracy Dice**: ");
-            if let Some(stat) = self.accuracy1 {
-                result.push_str(std::format!("{}", stat).as_str());
-
-                if self.accuracy2.is_some() {
-                    result.push_str(" + Rank");
-                }
+            if let Some(accuracy_dice) = self.build_accuracy_dice_string() {
+                result.push_str(&accuracy_dice);
             }
         }
 
@@ -194,4 +183,31 @@ impl Move {
 
         result
     }
+
+    pub(crate) fn build_damage_dice_string(&self) -> Option<String> {
+        if self.damage1.is_none() && self.happiness_damage.is_none() && self.power == 0 {
+            return None;
+        }
+
+        let mut result = String::new();
+        if let Some(stat) = self.damage1 {
+            result.push_str(std::format!("{}", stat).as_str());
+            result.push_str(" + ");
+        }
+        if let Some(stat) = self.happiness_damage {
+            result.push_str(std::format!("{}", stat).as_str());
+            result.push_str(" + ");
+        }
+        result.push_str(&self.power.to_string());
+        Some(result)
+    }
+
+    pub(crate) fn build_accuracy_dice_string(&self) -> Option<String> {
+        let stat = self.accuracy1?;
+        if self.accuracy2.is_some() {
+            Some(std::format!("{} + Rank", stat))
+        } else {
+            Some(stat.to_string())
+        }
+    }
 }
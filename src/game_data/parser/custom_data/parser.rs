@@ -6,6 +6,7 @@ use crate::game_data::parser::custom_data::custom_pokemon::CustomPokemon;
 use crate::game_data::parser::custom_data::custom_potion::CustomPotion;
 use crate::game_data::parser::custom_data::custom_status_effect::CustomStatusEffect;
 use crate::game_data::parser::custom_data::custom_weather::CustomWeather;
+use crate::game_data::achievement::Achievement;
 use crate::game_data::parser::helpers;
 use crate::game_data::rule::Rule;
 
@@ -19,6 +20,7 @@ pub struct CustomDataParseResult {
     pub potions: Vec<CustomPotion>,
     pub weather: Vec<CustomWeather>,
     pub rules: Vec<Rule>,
+    pub achievements: Vec<Achievement>,
 }
 
 pub fn parse(custom_data_path: &str) -> CustomDataParseResult {
@@ -33,5 +35,15 @@ pub fn parse(custom_data_path: &str) -> CustomDataParseResult {
         potions: helpers::parse_directory(custom_data_path.to_owned() + "Potions"),
         weather: helpers::parse_directory(custom_data_path.to_owned() + "Weather"),
         rules: helpers::parse_directory(custom_data_path.to_owned() + "Rules"),
+        achievements: parse_achievements(base_path.join("Achievements")),
+    }
+}
+
+/// Achievements are optional, so servers which don't use them won't need an empty directory.
+fn parse_achievements(path: std::path::PathBuf) -> Vec<Achievement> {
+    if path.is_dir() {
+        helpers::parse_directory(path)
+    } else {
+        Vec::new()
     }
 }
@@ -44,6 +44,7 @@ pub async fn initialize_data() -> GameData {
     let (item_names, item_hash_map) = parse_items(pokerole_data, &custom_data);
     let (potion_names, potion_hash_map) = parse_potions(&custom_data);
     GameData {
+        achievements: Arc::new(custom_data.achievements),
         abilities: Arc::new(ability_hash_map),
         ability_names: Arc::new(ability_names),
         potions: Arc::new(potion_hash_map),
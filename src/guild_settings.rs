@@ -0,0 +1,283 @@
+use sqlx::{Pool, Sqlite};
+
+use crate::enums::{MysteryDungeonRank, TeraChargeReset};
+
+/// Progression rules which can be adjusted by each server to fit their homebrew.
+/// Servers without any custom settings use the defaults.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct GuildSettings {
+    pub experience_per_level: i64,
+    pub silver_rank_level: i64,
+    pub gold_rank_level: i64,
+    pub platinum_rank_level: i64,
+    pub diamond_rank_level: i64,
+    pub master_rank_level: i64,
+    pub base_combat_points: i64,
+    pub combat_points_per_level: i64,
+    pub base_social_points: i64,
+    pub social_points_per_rank: i64,
+    pub backpack_upgrade_base_price: i64,
+    pub backpack_upgrade_price_increase: i64,
+    pub tera_charge_reset: TeraChargeReset,
+    pub shop_tax_percentage: i64,
+    pub backpack_upgrade_fee_percentage: i64,
+    pub move_limit_bonus: i64,
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        GuildSettings {
+            experience_per_level: 100,
+            silver_rank_level: 2,
+            gold_rank_level: 4,
+            platinum_rank_level: 8,
+            diamond_rank_level: 16,
+            master_rank_level: 20,
+            base_combat_points: 4,
+            combat_points_per_level: 1,
+            base_social_points: 4,
+            social_points_per_rank: 2,
+            backpack_upgrade_base_price: 500,
+            backpack_upgrade_price_increase: 500,
+            tera_charge_reset: TeraChargeReset::default(),
+            shop_tax_percentage: 0,
+            backpack_upgrade_fee_percentage: 0,
+            move_limit_bonus: 2,
+        }
+    }
+}
+
+impl GuildSettings {
+    pub async fn load(database: &Pool<Sqlite>, guild_id: i64) -> Self {
+        match sqlx::query_as!(
+            GuildSettings,
+            "SELECT experience_per_level, silver_rank_level, gold_rank_level, platinum_rank_level, diamond_rank_level, master_rank_level, \
+                base_combat_points, combat_points_per_level, base_social_points, social_points_per_rank, \
+                backpack_upgrade_base_price, backpack_upgrade_price_increase, \
+                tera_charge_reset as \"tera_charge_reset: TeraChargeReset\", \
+                shop_tax_percentage, backpack_upgrade_fee_percentage, move_limit_bonus \
+             FROM guild_settings WHERE guild_id = ?",
+            guild_id
+        )
+        .fetch_optional(database)
+        .await
+        {
+            Ok(Some(settings)) => settings,
+            Ok(None) => GuildSettings::default(),
+            Err(e) => {
+                log::error!("Was unable to load settings for guild {}: {}", guild_id, e);
+                GuildSettings::default()
+            }
+        }
+    }
+
+    pub async fn save(&self, database: &Pool<Sqlite>, guild_id: i64) -> Result<(), sqlx::Error> {
+        let tera_charge_reset = self.tera_charge_reset as i64;
+        sqlx::query!(
+            "INSERT INTO guild_settings (guild_id, experience_per_level, silver_rank_level, gold_rank_level, platinum_rank_level, diamond_rank_level, master_rank_level, \
+                base_combat_points, combat_points_per_level, base_social_points, social_points_per_rank, \
+                backpack_upgrade_base_price, backpack_upgrade_price_increase, tera_charge_reset, \
+                shop_tax_percentage, backpack_upgrade_fee_percentage, move_limit_bonus) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+             ON CONFLICT (guild_id) DO UPDATE SET \
+                experience_per_level = excluded.experience_per_level, \
+                silver_rank_level = excluded.silver_rank_level, \
+                gold_rank_level = excluded.gold_rank_level, \
+                platinum_rank_level = excluded.platinum_rank_level, \
+                diamond_rank_level = excluded.diamond_rank_level, \
+                master_rank_level = excluded.master_rank_level, \
+                base_combat_points = excluded.base_combat_points, \
+                combat_points_per_level = excluded.combat_points_per_level, \
+                base_social_points = excluded.base_social_points, \
+                social_points_per_rank = excluded.social_points_per_rank, \
+                backpack_upgrade_base_price = excluded.backpack_upgrade_base_price, \
+                backpack_upgrade_price_increase = excluded.backpack_upgrade_price_increase, \
+                tera_charge_reset = excluded.tera_charge_reset, \
+                shop_tax_percentage = excluded.shop_tax_percentage, \
+                backpack_upgrade_fee_percentage = excluded.backpack_upgrade_fee_percentage, \
+                move_limit_bonus = excluded.move_limit_bonus",
+            guild_id,
+            self.experience_per_level,
+            self.silver_rank_level,
+            self.gold_rank_level,
+            self.platinum_rank_level,
+            self.diamond_rank_level,
+            self.master_rank_level,
+            self.base_combat_points,
+            self.combat_points_per_level,
+            self.base_social_points,
+            self.social_points_per_rank,
+            self.backpack_upgrade_base_price,
+            self.backpack_upgrade_price_increase,
+            tera_charge_reset,
+            self.shop_tax_percentage,
+            self.backpack_upgrade_fee_percentage,
+            self.move_limit_bonus,
+        )
+        .execute(database)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns a description of the first problem which would make these settings unusable.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.experience_per_level < 1 {
+            return Err(String::from("Experience per level needs to be at least 1."));
+        }
+
+        let rank_levels = [
+            self.silver_rank_level,
+            self.gold_rank_level,
+            self.platinum_rank_level,
+            self.diamond_rank_level,
+            self.master_rank_level,
+        ];
+        if rank_levels[0] < 2 || rank_levels.windows(2).any(|x| x[0] >= x[1]) {
+            return Err(String::from(
+                "Rank levels need to be above 1 and strictly increasing from Silver to Master.",
+            ));
+        }
+
+        if self.base_combat_points < 0
+            || self.combat_points_per_level < 0
+            || self.base_social_points < 0
+            || self.social_points_per_rank < 0
+        {
+            return Err(String::from("Stat points can't be negative."));
+        }
+
+        if self.move_limit_bonus < 0 {
+            return Err(String::from("The move limit bonus can't be negative."));
+        }
+
+        if self.backpack_upgrade_base_price < 0 || self.backpack_upgrade_price_increase < 0 {
+            return Err(String::from("Prices can't be negative."));
+        }
+
+        let percentages = [
+            self.shop_tax_percentage,
+            self.backpack_upgrade_fee_percentage,
+        ];
+        if percentages.iter().any(|x| !(0..=100).contains(x)) {
+            return Err(String::from(
+                "Taxes and fees need to be between 0 and 100%.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn calculate_level_from_experience(&self, experience: i64) -> i64 {
+        experience / self.experience_per_level + 1
+    }
+
+    pub fn calculate_current_experience(&self, experience: i64) -> i64 {
+        experience % self.experience_per_level
+    }
+
+    pub fn calculate_rank(&self, level: i64) -> MysteryDungeonRank {
+        if level >= self.master_rank_level {
+            MysteryDungeonRank::Master
+        } else if level >= self.diamond_rank_level {
+            MysteryDungeonRank::Diamond
+        } else if level >= self.platinum_rank_level {
+            MysteryDungeonRank::Platinum
+        } else if level >= self.gold_rank_level {
+            MysteryDungeonRank::Gold
+        } else if level >= self.silver_rank_level {
+            MysteryDungeonRank::Silver
+        } else {
+            MysteryDungeonRank::Bronze
+        }
+    }
+
+    pub fn calculate_available_combat_points(&self, level: i64) -> i64 {
+        self.base_combat_points + self.combat_points_per_level * (level - 1)
+    }
+
+    pub fn calculate_available_social_points(&self, rank: &MysteryDungeonRank) -> i64 {
+        let rank_index = match rank {
+            MysteryDungeonRank::Bronze => 0,
+            MysteryDungeonRank::Silver => 1,
+            MysteryDungeonRank::Gold => 2,
+            MysteryDungeonRank::Platinum => 3,
+            MysteryDungeonRank::Diamond => 4,
+            MysteryDungeonRank::Master => 5,
+        };
+
+        self.base_social_points + self.social_points_per_rank * rank_index
+    }
+
+    pub fn calculate_backpack_upgrade_price(&self, backpack_upgrade_count: i64) -> i64 {
+        self.backpack_upgrade_base_price
+            + self.backpack_upgrade_price_increase * backpack_upgrade_count
+    }
+
+    pub fn build_string(&self) -> String {
+        format!(
+            "\
+### Experience
+- Experience per Level: **{}**
+### Rank Levels
+- Silver: **{}** | Gold: **{}** | Platinum: **{}** | Diamond: **{}** | Master: **{}**
+### Stat Points
+- Combat: **{}** at level 1, **+{}** per level
+- Social: **{}** at Bronze Rank, **+{}** per rank
+### Moves
+- Move Limit: Insight **+{}**
+### Prices
+- Backpack Upgrade: **{}**, **+{}** per upgrade
+### Terastallization
+- Used charges reset: **{}**
+### Guild Treasury
+- Shop Tax: **{}%** | Backpack Upgrade Fee: **{}%**",
+            self.experience_per_level,
+            self.silver_rank_level,
+            self.gold_rank_level,
+            self.platinum_rank_level,
+            self.diamond_rank_level,
+            self.master_rank_level,
+            self.base_combat_points,
+            self.combat_points_per_level,
+            self.base_social_points,
+            self.social_points_per_rank,
+            self.move_limit_bonus,
+            self.backpack_upgrade_base_price,
+            self.backpack_upgrade_price_increase,
+            self.tera_charge_reset,
+            self.shop_tax_percentage,
+            self.backpack_upgrade_fee_percentage,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::guild_settings::GuildSettings;
+    use crate::{database_helpers, Error};
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn guilds_without_settings_should_use_defaults(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        assert_eq!(
+            GuildSettings::default(),
+            GuildSettings::load(&db, guild_id).await
+        );
+
+        let settings = GuildSettings {
+            experience_per_level: 50,
+            move_limit_bonus: 3,
+            ..Default::default()
+        };
+        settings.save(&db, guild_id).await?;
+        let loaded = GuildSettings::load(&db, guild_id).await;
+        assert_eq!(settings, loaded);
+        assert_eq!(3, loaded.calculate_level_from_experience(120));
+        assert_eq!(20, loaded.calculate_current_experience(120));
+
+        Ok(())
+    }
+}
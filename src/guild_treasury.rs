@@ -0,0 +1,128 @@
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::errors::ValidationError;
+use crate::{emoji, wallet_statement, Error};
+
+/// The guild treasury can be selected in place of a wallet under this name.
+pub const GUILD_TREASURY_NAME: &str = "Guild Treasury";
+
+pub fn is_guild_treasury(name: &str) -> bool {
+    name.to_lowercase() == GUILD_TREASURY_NAME.to_lowercase()
+}
+
+pub async fn get_balance(database: &Pool<Sqlite>, guild_id: i64) -> Result<i64, Error> {
+    let record = sqlx::query!("SELECT money FROM guild WHERE id = ?", guild_id)
+        .fetch_optional(database)
+        .await?;
+
+    Ok(record.map(|x| x.money).unwrap_or(0))
+}
+
+pub async fn deposit_in_transaction(
+    connection: &mut SqliteConnection,
+    guild_id: i64,
+    amount: i64,
+    counterpart_character_id: Option<i64>,
+    memo: &str,
+) -> Result<(), Error> {
+    sqlx::query!(
+        "UPDATE guild SET money = money + ? WHERE id = ?",
+        amount,
+        guild_id
+    )
+    .execute(&mut *connection)
+    .await?;
+    wallet_statement::record_for_guild_treasury(
+        connection,
+        guild_id,
+        amount,
+        counterpart_character_id,
+        memo,
+    )
+    .await
+}
+
+/// Fails without changing anything if the treasury can't afford it.
+pub async fn withdraw_in_transaction(
+    connection: &mut SqliteConnection,
+    guild_id: i64,
+    amount: i64,
+    counterpart_character_id: Option<i64>,
+    memo: &str,
+) -> Result<(), Error> {
+    let result = sqlx::query!(
+        "UPDATE guild SET money = money - ? WHERE id = ? AND money >= ?",
+        amount,
+        guild_id,
+        amount
+    )
+    .execute(&mut *connection)
+    .await?;
+
+    if result.rows_affected() != 1 {
+        return Err(Box::new(ValidationError::new(format!(
+            "**The {} can't afford to pay {} {} right now.**",
+            GUILD_TREASURY_NAME,
+            amount,
+            emoji::POKE_COIN
+        ))));
+    }
+
+    wallet_statement::record_for_guild_treasury(
+        connection,
+        guild_id,
+        -amount,
+        counterpart_character_id,
+        memo,
+    )
+    .await
+}
+
+/// Taxes are rounded down, so small amounts stay tax-free.
+pub fn calculate_tax(amount: i64, percentage: i64) -> i64 {
+    amount * percentage / 100
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Pool, Sqlite};
+
+    use crate::guild_treasury::{
+        calculate_tax, deposit_in_transaction, get_balance, withdraw_in_transaction,
+    };
+    use crate::wallet_statement::{calculate_totals, load_entries};
+    use crate::{database_helpers, Error};
+
+    #[sqlx::test]
+    async fn treasury_should_not_go_below_zero(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+
+        let mut connection = db.acquire().await?;
+        deposit_in_transaction(
+            &mut connection,
+            guild_id,
+            calculate_tax(1050, 10),
+            None,
+            "Shop tax",
+        )
+        .await?;
+        assert_eq!(105, get_balance(&db, guild_id).await?);
+
+        assert!(
+            withdraw_in_transaction(&mut connection, guild_id, 106, None, "Too much")
+                .await
+                .is_err()
+        );
+        withdraw_in_transaction(&mut connection, guild_id, 100, None, "Payout").await?;
+        assert_eq!(5, get_balance(&db, guild_id).await?);
+        assert_eq!(0, calculate_tax(9, 10));
+
+        // Failed withdrawals don't show up on the statement.
+        let entries = load_entries(&db, guild_id, None, None, None).await?;
+        assert_eq!(2, entries.len());
+        assert_eq!((105, 100), calculate_totals(&entries));
+
+        Ok(())
+    }
+}
@@ -5,9 +5,10 @@ use serenity::all::{
 use std::sync::Arc;
 
 use crate::data::Data;
-use crate::enums::{MysteryDungeonRank, QuestParticipantSelectionMechanism};
-use crate::game_data::pokemon::Pokemon;
+use crate::enums::QuestParticipantSelectionMechanism;
+use crate::game_data::pokemon::{Pokemon, PokemonStat};
 use crate::game_data::{GameData, PokemonApiId};
+use crate::guild_settings::GuildSettings;
 use crate::{discord_error_codes, emoji, Error};
 
 pub const ADMIN_PING_STRING: &str = "<@878982444412448829>";
@@ -85,7 +86,7 @@ fn find_best_split_pos(message: &str) -> usize {
 
 struct Signup {
     character_name: String,
-    character_experience: i64,
+    character_level: i64,
     user_id: i64,
     accepted: bool,
     emoji: String,
@@ -127,7 +128,7 @@ pub async fn create_quest_participant_list(
     stop_at_character_limit: bool,
 ) -> Result<(String, bool), Error> {
     let records = sqlx::query!(
-        "SELECT character.id as character_id, character.name as character_name, character.user_id as user_id, character.species_api_id as character_species_id, character.experience as character_experience, quest_signup.accepted as accepted
+        "SELECT character.id as character_id, character.name as character_name, character.user_id as user_id, character.species_api_id as character_species_id, character.experience as character_experience, character.guild_id as guild_id, quest_signup.accepted as accepted
 FROM quest_signup
 INNER JOIN character ON
     quest_signup.character_id = character.id
@@ -139,6 +140,11 @@ ORDER BY quest_signup.accepted DESC, quest_signup.timestamp ASC
         .fetch_all(&data.database)
         .await?;
 
+    let settings = match records.first() {
+        Some(record) => GuildSettings::load(&data.database, record.guild_id).await,
+        None => GuildSettings::default(),
+    };
+
     let mut quest_signups = Vec::new();
     for record in records {
         let emoji = match emoji::get_character_emoji(data, record.character_id).await {
@@ -148,7 +154,7 @@ ORDER BY quest_signup.accepted DESC, quest_signup.timestamp ASC
 
         quest_signups.push(Signup {
             character_name: record.character_name.clone(),
-            character_experience: record.character_experience,
+            character_level: settings.calculate_level_from_experience(record.character_experience),
             user_id: record.user_id,
             accepted: record.accepted,
             emoji,
@@ -229,10 +235,7 @@ fn add_character_names(text: &mut String, quest_signups: Vec<&Signup>, max: usiz
         text.push_str(
             format!(
                 "- {}{} (<@{}>) Lv.{}\n",
-                record.emoji,
-                record.character_name,
-                record.user_id,
-                1 + record.character_experience / 100,
+                record.emoji, record.character_name, record.user_id, record.character_level,
             )
             .as_str(),
         );
@@ -319,19 +322,15 @@ pub async fn update_quest_message(
     Ok(())
 }
 
-pub fn calculate_available_social_points(rank: &MysteryDungeonRank) -> u8 {
-    match rank {
-        MysteryDungeonRank::Bronze => 4,
-        MysteryDungeonRank::Silver => 4 + 2,
-        MysteryDungeonRank::Gold => 4 + 4,
-        MysteryDungeonRank::Platinum => 4 + 6,
-        MysteryDungeonRank::Diamond => 4 + 8,
-        MysteryDungeonRank::Master => 4 + 10,
-    }
-}
-
-pub fn calculate_available_combat_points(level: i64) -> i64 {
-    level + 3
+/// Returns the form a character is currently in, or its species if it hasn't switched forms.
+pub fn get_active_form<'a>(
+    pokemon: &'a Pokemon,
+    game_data: &'a GameData,
+    active_form_api_id: Option<i64>,
+) -> &'a Pokemon {
+    active_form_api_id
+        .and_then(|x| game_data.pokemon_by_api_id.get(&PokemonApiId(x as u16)))
+        .unwrap_or(pokemon)
 }
 
 const STAGE1_EVOLUTION_LEVEL_THRESHOLD: i64 = 3;
@@ -383,12 +382,20 @@ pub fn get_usual_evolution_stage_for_level<'a>(
         .expect("Pre-Evolutions should be implemented!")
 }
 
-pub fn calculate_level_from_experience(experience: i64) -> i64 {
-    experience / 100 + 1
+/// Moves the invested points over to another species or form and clamps them to its limits.
+/// Limit breaks are kept, any points which no longer fit become available again.
+pub fn rebase_stat(current: i64, old: &PokemonStat, new: &PokemonStat) -> i64 {
+    let limit_breaks = (current - old.max as i64).max(0);
+    let invested_points = current - old.min as i64;
+    (new.min as i64 + invested_points).clamp(new.min as i64, new.max as i64 + limit_breaks)
+}
+
+pub fn calculate_max_hp(base_hp: u8, vitality: i64) -> i64 {
+    (base_hp as i64 + vitality) * 2
 }
 
-pub fn calculate_current_experience(experience: i64) -> i64 {
-    experience % 100
+pub fn calculate_max_will(insight: i64) -> i64 {
+    insight + 2
 }
 
 pub fn channel_id_link(channel_id: ChannelId) -> String {
@@ -0,0 +1,306 @@
+use chrono::Utc;
+use serenity::all::{ButtonStyle, CreateActionRow};
+use sqlx::SqliteConnection;
+
+use crate::commands::characters::ActionType;
+use crate::data::Data;
+use crate::errors::ValidationError;
+use crate::wallet_statement::WalletTransaction;
+use crate::{helpers, tera_charges, wallet_statement, Error};
+
+/// Players may undo their own changes for this long. Admins and GMs can always undo.
+const UNDO_TIME_LIMIT_IN_SECONDS: i64 = 5 * 60;
+
+pub enum LedgerTarget {
+    Character(i64),
+    Wallet(i64),
+    /// Stats stored in user_in_guild, such as gm_experience.
+    User(i64),
+}
+
+pub struct LedgerEntry<'a> {
+    pub guild_id: i64,
+    pub interaction_id: i64,
+    pub actor_id: i64,
+    pub target: LedgerTarget,
+    pub stat: &'a str,
+    pub amount: i64,
+    pub action_type: &'a ActionType,
+}
+
+pub struct RevertedLedgerEntry {
+    pub character_id: Option<i64>,
+    pub wallet_id: Option<i64>,
+    pub target_name: String,
+    pub stat: String,
+    pub amount: i64,
+}
+
+pub async fn record(
+    connection: &mut SqliteConnection,
+    entry: LedgerEntry<'_>,
+) -> Result<(), Error> {
+    insert(connection, entry, None).await
+}
+
+async fn insert(
+    connection: &mut SqliteConnection,
+    entry: LedgerEntry<'_>,
+    reverts_id: Option<i64>,
+) -> Result<(), Error> {
+    let (character_id, wallet_id, user_id) = match entry.target {
+        LedgerTarget::Character(id) => (Some(id), None, None),
+        LedgerTarget::Wallet(id) => (None, Some(id), None),
+        LedgerTarget::User(id) => (None, None, Some(id)),
+    };
+    let action_type: &'static str = entry.action_type.into();
+    let timestamp = Utc::now().timestamp();
+
+    sqlx::query!(
+        "INSERT INTO ledger (guild_id, interaction_id, actor_id, character_id, wallet_id, user_id, stat, amount, action_type, timestamp, reverts_id) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        entry.guild_id,
+        entry.interaction_id,
+        entry.actor_id,
+        character_id,
+        wallet_id,
+        user_id,
+        entry.stat,
+        entry.amount,
+        action_type,
+        timestamp,
+        reverts_id
+    )
+    .execute(&mut *connection)
+    .await?;
+
+    Ok(())
+}
+
+pub fn create_undo_button(interaction_id: u64) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![helpers::create_styled_button(
+        "Undo",
+        &format!("undo_{}", interaction_id),
+        false,
+        ButtonStyle::Danger,
+    )])
+}
+
+/// Reverts every change made during the given interaction within a single transaction.
+/// Either all changes get reverted, or none of them.
+pub async fn revert_interaction(
+    data: &Data,
+    interaction_id: i64,
+    undo_interaction_id: i64,
+    user_id: i64,
+    is_admin_or_gm: bool,
+) -> Result<Vec<RevertedLedgerEntry>, Error> {
+    let mut transaction = data.database.begin().await?;
+
+    let entries = sqlx::query!(
+        "SELECT ledger.id, ledger.guild_id, ledger.actor_id, ledger.character_id, ledger.wallet_id, ledger.user_id, ledger.stat, ledger.amount, ledger.timestamp, \
+            COALESCE(character.name, wallet.name, '<@' || ledger.user_id || '>') as \"target_name!: String\" \
+         FROM ledger \
+            LEFT JOIN character ON character.id = ledger.character_id \
+            LEFT JOIN wallet ON wallet.id = ledger.wallet_id \
+         WHERE ledger.interaction_id = ? AND ledger.reverts_id IS NULL \
+            AND ledger.id NOT IN (SELECT reverts_id FROM ledger WHERE reverts_id IS NOT NULL)",
+        interaction_id
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    if entries.is_empty() {
+        return Err(Box::new(ValidationError::new(
+            "There's nothing left to undo here.",
+        )));
+    }
+
+    // Used as counterpart on wallet statements, e.g. when undoing a payment.
+    let counterpart_character_id = entries.iter().find_map(|x| x.character_id);
+    let now = Utc::now().timestamp();
+    let mut result = Vec::new();
+    for entry in entries {
+        if !is_admin_or_gm {
+            if entry.actor_id != user_id {
+                return Err(Box::new(ValidationError::new(
+                    "Only the person who triggered this can undo it.",
+                )));
+            }
+            if now - entry.timestamp > UNDO_TIME_LIMIT_IN_SECONDS {
+                return Err(Box::new(ValidationError::new(
+                    "This is too old to be undone. Please ask a GM for help.",
+                )));
+            }
+        }
+
+        // Column names can't be bound as parameters, so make sure we never format anything weird into the query.
+        if !entry
+            .stat
+            .chars()
+            .all(|x| x.is_ascii_lowercase() || x == '_')
+        {
+            return Err(Box::new(
+                ValidationError::new(format!("Invalid stat in ledger entry {}", entry.id)).log(),
+            ));
+        }
+
+        let (target, table, key_column, key_value) =
+            match (entry.character_id, entry.wallet_id, entry.user_id) {
+                (Some(id), _, _) => (LedgerTarget::Character(id), "character", "id", id),
+                (_, Some(id), _) => (LedgerTarget::Wallet(id), "wallet", "id", id),
+                (_, _, Some(id)) => (LedgerTarget::User(id), "user_in_guild", "user_id", id),
+                _ => {
+                    return Err(Box::new(
+                        ValidationError::new(format!("Ledger entry {} has no target", entry.id))
+                            .log(),
+                    ))
+                }
+            };
+        let is_user_target = matches!(target, LedgerTarget::User(_));
+        let tera_type = match target {
+            LedgerTarget::Character(_) => tera_charges::parse_ledger_stat(&entry.stat),
+            _ => None,
+        };
+        let (table, column, key_column) = if tera_type.is_some() {
+            ("character_tera_charge", "unlocked", "character_id")
+        } else {
+            (table, entry.stat.as_str(), key_column)
+        };
+
+        let query = format!(
+            "UPDATE {} SET {} = {} - ? WHERE {} = ? AND {} - ? >= 0{}",
+            table,
+            column,
+            column,
+            key_column,
+            column,
+            if is_user_target {
+                " AND guild_id = ?"
+            } else if tera_type.is_some() {
+                " AND tera_type = ?"
+            } else {
+                ""
+            }
+        );
+        let mut query = sqlx::query(&query)
+            .bind(entry.amount)
+            .bind(key_value)
+            .bind(entry.amount);
+        if is_user_target {
+            query = query.bind(entry.guild_id);
+        }
+        if let Some(tera_type) = tera_type {
+            query = query.bind(tera_charges::to_database_value(tera_type));
+        }
+        let rows_affected = query.execute(&mut *transaction).await?.rows_affected();
+
+        if rows_affected != 1 {
+            return Err(Box::new(ValidationError::new(format!(
+                "**Unable to undo this.**\n*{} doesn't have enough {} left.*",
+                entry.target_name, entry.stat
+            ))));
+        }
+
+        insert(
+            &mut transaction,
+            LedgerEntry {
+                guild_id: entry.guild_id,
+                interaction_id: undo_interaction_id,
+                actor_id: user_id,
+                target,
+                stat: &entry.stat,
+                amount: -entry.amount,
+                action_type: &ActionType::Undo,
+            },
+            Some(entry.id),
+        )
+        .await?;
+
+        if let Some(wallet_id) = entry.wallet_id {
+            wallet_statement::record(
+                &mut transaction,
+                WalletTransaction {
+                    wallet_id,
+                    amount: -entry.amount,
+                    counterpart_character_id,
+                    memo: Some("Undo"),
+                },
+            )
+            .await?;
+        }
+
+        result.push(RevertedLedgerEntry {
+            character_id: entry.character_id,
+            wallet_id: entry.wallet_id,
+            target_name: entry.target_name,
+            stat: entry.stat,
+            amount: entry.amount,
+        });
+    }
+
+    transaction.commit().await?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::characters::ActionType;
+    use crate::ledger::{record, revert_interaction, LedgerEntry, LedgerTarget};
+    use crate::{database_helpers, Error};
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn reverting_an_interaction_should_work_only_once(db: Pool<Sqlite>) -> Result<(), Error> {
+        let data = database_helpers::create_mock::data(db).await;
+        let guild_id = 100;
+        let user_id = 200;
+        let character_id = 300;
+        let interaction_id = 400;
+
+        database_helpers::create_mock::guild(&data.database, guild_id).await;
+        database_helpers::create_mock::user(&data.database, user_id).await;
+        database_helpers::create_mock::character(
+            &data,
+            guild_id,
+            user_id,
+            character_id,
+            &String::from("Test"),
+        )
+        .await;
+
+        let mut transaction = data.database.begin().await?;
+        sqlx::query!("UPDATE character SET money = 50 WHERE id = ?", character_id)
+            .execute(&mut *transaction)
+            .await?;
+        record(
+            &mut transaction,
+            LedgerEntry {
+                guild_id,
+                interaction_id,
+                actor_id: user_id,
+                target: LedgerTarget::Character(character_id),
+                stat: "money",
+                amount: 50,
+                action_type: &ActionType::Reward,
+            },
+        )
+        .await?;
+        transaction.commit().await?;
+
+        let reverted = revert_interaction(&data, interaction_id, 500, user_id, false).await?;
+        assert_eq!(1, reverted.len());
+        assert!(
+            revert_interaction(&data, interaction_id, 600, user_id, false)
+                .await
+                .is_err()
+        );
+
+        let record = sqlx::query!("SELECT money FROM character WHERE id = ?", character_id)
+            .fetch_one(&data.database)
+            .await?;
+        assert_eq!(0, record.money);
+
+        Ok(())
+    }
+}
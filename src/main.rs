@@ -1,5 +1,9 @@
+mod achievements;
+mod auctions;
 mod cache;
+mod character_card;
 mod character_stats;
+mod character_submission;
 mod commands;
 mod csv_utils;
 mod data;
@@ -10,8 +14,15 @@ mod enums;
 mod errors;
 mod events;
 mod game_data;
+mod guild_settings;
+mod guild_treasury;
 mod helpers;
+mod ledger;
 mod logger;
+mod recurring_payouts;
+mod tera_charges;
+mod trades;
+mod wallet_statement;
 
 use crate::data::Data;
 use crate::errors::CommandInvocationError;
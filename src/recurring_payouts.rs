@@ -0,0 +1,237 @@
+use sqlx::{Pool, Sqlite};
+
+use crate::cache::WalletCacheItem;
+use crate::commands::ensure_wallet_has_money;
+use crate::enums::PayoutSchedule;
+use crate::errors::ValidationError;
+use crate::guild_treasury::GUILD_TREASURY_NAME;
+use crate::wallet_statement::WalletTransaction;
+use crate::{emoji, guild_treasury, wallet_statement, Error};
+
+/// Money which regularly gets transferred from a wallet to a character or another wallet.
+/// Payouts without a source wallet are funded by the guild treasury.
+pub struct RecurringPayout {
+    pub id: i64,
+    pub guild_id: i64,
+    pub source_wallet_id: Option<i64>,
+    pub source_wallet_name: String,
+    pub source_wallet_channel_id: i64,
+    pub target_character_id: Option<i64>,
+    pub target_wallet_id: Option<i64>,
+    pub target_name: String,
+    pub amount: i64,
+    pub schedule: PayoutSchedule,
+    pub memo: Option<String>,
+}
+
+impl RecurringPayout {
+    pub fn build_description(&self) -> String {
+        let mut result = format!(
+            "#{}: {} {} {} from {} to {}",
+            self.id,
+            self.schedule,
+            self.amount,
+            emoji::POKE_COIN,
+            self.source_wallet_name,
+            self.target_name
+        );
+        if let Some(memo) = &self.memo {
+            result.push_str(&format!(" – *{}*", memo));
+        }
+
+        result
+    }
+}
+
+/// Loads all recurring payouts matching the given filters.
+pub async fn load(
+    database: &Pool<Sqlite>,
+    guild_id: Option<i64>,
+    schedule: Option<PayoutSchedule>,
+) -> Result<Vec<RecurringPayout>, Error> {
+    let records = sqlx::query!(
+        "SELECT payout.id, payout.guild_id, payout.source_wallet_id, payout.target_character_id, payout.target_wallet_id, \
+            payout.amount, payout.schedule, payout.memo, \
+            COALESCE(source.name, ?) as \"source_wallet_name!: String\", \
+            COALESCE(source.bot_message_channel_id, guild.action_log_channel_id, 0) as \"bot_message_channel_id!: i64\", \
+            COALESCE(character.name, target.name) as \"target_name!: String\" \
+         FROM recurring_payout payout \
+            JOIN guild ON guild.id = payout.guild_id \
+            LEFT JOIN wallet source ON source.id = payout.source_wallet_id \
+            LEFT JOIN character ON character.id = payout.target_character_id \
+            LEFT JOIN wallet target ON target.id = payout.target_wallet_id \
+         WHERE (? IS NULL OR payout.guild_id = ?) AND (? IS NULL OR payout.schedule = ?) \
+         ORDER BY payout.id",
+        GUILD_TREASURY_NAME,
+        guild_id,
+        guild_id,
+        schedule,
+        schedule
+    )
+    .fetch_all(database)
+    .await?;
+
+    Ok(records
+        .into_iter()
+        .filter_map(|x| {
+            Some(RecurringPayout {
+                id: x.id,
+                guild_id: x.guild_id,
+                source_wallet_id: x.source_wallet_id,
+                source_wallet_name: x.source_wallet_name,
+                source_wallet_channel_id: x.bot_message_channel_id,
+                target_character_id: x.target_character_id,
+                target_wallet_id: x.target_wallet_id,
+                target_name: x.target_name,
+                amount: x.amount,
+                schedule: PayoutSchedule::from_repr(x.schedule)?,
+                memo: x.memo,
+            })
+        })
+        .collect())
+}
+
+/// Moves the money for a single payout. Fails without changing anything if the source can't afford it.
+pub async fn execute(database: &Pool<Sqlite>, payout: &RecurringPayout) -> Result<(), Error> {
+    let source_memo = match &payout.memo {
+        Some(memo) => memo.clone(),
+        None => format!("Recurring payout to {}", payout.target_name),
+    };
+    let mut transaction = database.begin().await?;
+    match payout.source_wallet_id {
+        Some(source_wallet_id) => {
+            let wallet = WalletCacheItem {
+                id: source_wallet_id,
+                name: payout.source_wallet_name.clone(),
+                guild_id: payout.guild_id as u64,
+            };
+            ensure_wallet_has_money(database, &wallet, payout.amount, "pay out").await?;
+
+            let result = sqlx::query!(
+                "UPDATE wallet SET money = money - ? WHERE id = ? AND money >= ?",
+                payout.amount,
+                source_wallet_id,
+                payout.amount
+            )
+            .execute(&mut *transaction)
+            .await?;
+            if result.rows_affected() != 1 {
+                return Err(Box::new(ValidationError::new(format!(
+                    "{} can't afford this payout anymore.",
+                    payout.source_wallet_name
+                ))));
+            }
+        }
+        None => {
+            guild_treasury::withdraw_in_transaction(
+                &mut transaction,
+                payout.guild_id,
+                payout.amount,
+                payout.target_character_id,
+                &source_memo,
+            )
+            .await?;
+        }
+    }
+
+    if let Some(character_id) = payout.target_character_id {
+        sqlx::query!(
+            "UPDATE character SET money = money + ? WHERE id = ?",
+            payout.amount,
+            character_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+    } else if let Some(wallet_id) = payout.target_wallet_id {
+        sqlx::query!(
+            "UPDATE wallet SET money = money + ? WHERE id = ?",
+            payout.amount,
+            wallet_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+        wallet_statement::record(
+            &mut transaction,
+            WalletTransaction {
+                wallet_id,
+                amount: payout.amount,
+                counterpart_character_id: None,
+                memo: Some(&format!(
+                    "Recurring payout from {}",
+                    payout.source_wallet_name
+                )),
+            },
+        )
+        .await?;
+    }
+
+    if let Some(source_wallet_id) = payout.source_wallet_id {
+        wallet_statement::record(
+            &mut transaction,
+            WalletTransaction {
+                wallet_id: source_wallet_id,
+                amount: -payout.amount,
+                counterpart_character_id: payout.target_character_id,
+                memo: Some(&source_memo),
+            },
+        )
+        .await?;
+    }
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Pool, Sqlite};
+
+    use crate::enums::PayoutSchedule;
+    use crate::{database_helpers, recurring_payouts, Error};
+
+    #[sqlx::test]
+    async fn payouts_should_be_skipped_if_the_wallet_is_broke(
+        db: Pool<Sqlite>,
+    ) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        database_helpers::create_mock::character_in_database(&db, guild_id, user_id, 1, "Clerk", 0)
+            .await;
+        database_helpers::create_mock::wallet_with_money(&db, guild_id, 1, "Shop", 150).await;
+        let weekly = PayoutSchedule::Weekly;
+        sqlx::query!(
+            "INSERT INTO recurring_payout (guild_id, source_wallet_id, target_character_id, amount, schedule, creator_id, creation_timestamp) \
+             VALUES (?, 1, 1, 100, ?, ?, 0)",
+            guild_id,
+            weekly,
+            user_id
+        )
+        .execute(&db)
+        .await?;
+
+        let payouts = recurring_payouts::load(&db, None, Some(weekly)).await?;
+        assert_eq!(1, payouts.len());
+        assert_eq!("Clerk", payouts[0].target_name);
+        assert!(
+            recurring_payouts::load(&db, None, Some(PayoutSchedule::Monthly))
+                .await?
+                .is_empty()
+        );
+
+        recurring_payouts::execute(&db, &payouts[0]).await?;
+        assert!(recurring_payouts::execute(&db, &payouts[0]).await.is_err());
+
+        let character = sqlx::query!("SELECT money FROM character WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        let wallet = sqlx::query!("SELECT money FROM wallet WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(100, character.money);
+        assert_eq!(50, wallet.money);
+
+        Ok(())
+    }
+}
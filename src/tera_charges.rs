@@ -0,0 +1,262 @@
+use std::str::FromStr;
+
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::enums::{PokemonTypeWithoutShadow, TeraChargeReset};
+use crate::Error;
+
+/// Unlocked charges used to be stored as `tera_unlocked_<type>` columns on the character table.
+/// The ledger still uses those names, so older entries can be reverted just the same.
+const LEDGER_STAT_PREFIX: &str = "tera_unlocked_";
+
+pub struct TeraCharge {
+    pub tera_type: PokemonTypeWithoutShadow,
+    pub unlocked: i64,
+    pub used: i64,
+}
+
+impl TeraCharge {
+    pub fn remaining(&self) -> i64 {
+        self.unlocked - self.used
+    }
+}
+
+pub fn to_database_value(tera_type: PokemonTypeWithoutShadow) -> String {
+    format!("{:?}", tera_type)
+}
+
+pub fn get_ledger_stat(tera_type: PokemonTypeWithoutShadow) -> String {
+    format!(
+        "{}{}",
+        LEDGER_STAT_PREFIX,
+        to_database_value(tera_type).to_lowercase()
+    )
+}
+
+pub fn parse_ledger_stat(stat: &str) -> Option<PokemonTypeWithoutShadow> {
+    stat.strip_prefix(LEDGER_STAT_PREFIX)
+        .and_then(|x| PokemonTypeWithoutShadow::from_str(x).ok())
+}
+
+/// Returns all unlocked charges of the given character, sorted by type name.
+pub async fn get_tera_charges(database: &Pool<Sqlite>, character_id: i64) -> Vec<TeraCharge> {
+    match sqlx::query!(
+        "SELECT tera_type, unlocked, used FROM character_tera_charge \
+         WHERE character_id = ? AND unlocked > 0 \
+         ORDER BY tera_type",
+        character_id
+    )
+    .fetch_all(database)
+    .await
+    {
+        Ok(records) => records
+            .into_iter()
+            .filter_map(|x| match PokemonTypeWithoutShadow::from_str(&x.tera_type) {
+                Ok(tera_type) => Some(TeraCharge {
+                    tera_type,
+                    unlocked: x.unlocked,
+                    used: x.used,
+                }),
+                Err(_) => {
+                    log::error!(
+                        "Character {} has tera charges of unknown type {}",
+                        character_id,
+                        x.tera_type
+                    );
+                    None
+                }
+            })
+            .collect(),
+        Err(e) => {
+            log::error!(
+                "Was unable to load tera charges for character {}: {}",
+                character_id,
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
+pub async fn add_unlocked_charges(
+    connection: &mut SqliteConnection,
+    character_id: i64,
+    tera_type: PokemonTypeWithoutShadow,
+    amount: i64,
+) -> Result<(), Error> {
+    let tera_type = to_database_value(tera_type);
+    sqlx::query!(
+        "INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used) VALUES (?, ?, ?, 0) \
+         ON CONFLICT (character_id, tera_type) DO UPDATE SET unlocked = unlocked + excluded.unlocked",
+        character_id,
+        tera_type,
+        amount
+    )
+    .execute(connection)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn set_tera_charges(
+    connection: &mut SqliteConnection,
+    character_id: i64,
+    tera_type: PokemonTypeWithoutShadow,
+    unlocked: i64,
+    used: i64,
+) -> Result<(), Error> {
+    let tera_type = to_database_value(tera_type);
+    sqlx::query!(
+        "INSERT INTO character_tera_charge (character_id, tera_type, unlocked, used) VALUES (?, ?, ?, ?) \
+         ON CONFLICT (character_id, tera_type) DO UPDATE SET unlocked = excluded.unlocked, used = excluded.used",
+        character_id,
+        tera_type,
+        unlocked,
+        used
+    )
+    .execute(connection)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns false if the character doesn't have any charges of that type left.
+pub async fn use_charge(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+    tera_type: PokemonTypeWithoutShadow,
+) -> Result<bool, Error> {
+    let tera_type = to_database_value(tera_type);
+    let result = sqlx::query!(
+        "UPDATE character_tera_charge SET used = used + 1 \
+         WHERE character_id = ? AND tera_type = ? AND used < unlocked",
+        character_id,
+        tera_type
+    )
+    .execute(database)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Resets the used charges of all characters in guilds using the given reset interval.
+/// Returns the IDs of all active characters which had any charges reset.
+pub async fn reset_used_charges(
+    database: &Pool<Sqlite>,
+    reset: TeraChargeReset,
+) -> Result<Vec<i64>, Error> {
+    let default_reset = TeraChargeReset::default() as i64;
+    let reset = reset as i64;
+    let mut transaction = database.begin().await?;
+
+    let records = sqlx::query!(
+        "SELECT DISTINCT character.id, character.is_retired FROM character_tera_charge \
+            INNER JOIN character ON character.id = character_tera_charge.character_id \
+            LEFT JOIN guild_settings ON guild_settings.guild_id = character.guild_id \
+         WHERE character_tera_charge.used > 0 AND COALESCE(guild_settings.tera_charge_reset, ?) = ?",
+        default_reset,
+        reset
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE character_tera_charge SET used = 0 \
+         WHERE used > 0 AND character_id IN ( \
+            SELECT character.id FROM character \
+               LEFT JOIN guild_settings ON guild_settings.guild_id = character.guild_id \
+            WHERE COALESCE(guild_settings.tera_charge_reset, ?) = ?)",
+        default_reset,
+        reset
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+    Ok(records
+        .into_iter()
+        .filter(|x| !x.is_retired)
+        .map(|x| x.id)
+        .collect())
+}
+
+/// Returns true if any charges were reset.
+pub async fn reset_used_charges_for_character(
+    database: &Pool<Sqlite>,
+    character_id: i64,
+) -> Result<bool, Error> {
+    let result = sqlx::query!(
+        "UPDATE character_tera_charge SET used = 0 WHERE character_id = ? AND used > 0",
+        character_id
+    )
+    .execute(database)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enums::{PokemonTypeWithoutShadow, TeraChargeReset};
+    use crate::guild_settings::GuildSettings;
+    use crate::{database_helpers, tera_charges, Error};
+    use sqlx::{Pool, Sqlite};
+
+    #[sqlx::test]
+    async fn charges_should_only_reset_in_guilds_with_matching_interval(
+        db: Pool<Sqlite>,
+    ) -> Result<(), Error> {
+        let monthly_guild_id = 100;
+        let weekly_guild_id = 101;
+        let user_id = 200;
+        let monthly_character_id = 300;
+        let weekly_character_id = 301;
+        database_helpers::create_mock::guild(&db, monthly_guild_id).await;
+        database_helpers::create_mock::guild(&db, weekly_guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        for (character_id, guild_id) in [
+            (monthly_character_id, monthly_guild_id),
+            (weekly_character_id, weekly_guild_id),
+        ] {
+            database_helpers::create_mock::character_in_database(
+                &db,
+                guild_id,
+                user_id,
+                character_id,
+                "Test",
+                0,
+            )
+            .await;
+        }
+        GuildSettings {
+            tera_charge_reset: TeraChargeReset::Weekly,
+            ..Default::default()
+        }
+        .save(&db, weekly_guild_id)
+        .await?;
+
+        for character_id in [monthly_character_id, weekly_character_id] {
+            let mut connection = db.acquire().await?;
+            tera_charges::add_unlocked_charges(
+                &mut connection,
+                character_id,
+                PokemonTypeWithoutShadow::Fire,
+                2,
+            )
+            .await?;
+            assert!(
+                tera_charges::use_charge(&db, character_id, PokemonTypeWithoutShadow::Fire).await?
+            );
+        }
+
+        let reset = tera_charges::reset_used_charges(&db, TeraChargeReset::Weekly).await?;
+        assert_eq!(vec![weekly_character_id], reset);
+
+        let weekly = tera_charges::get_tera_charges(&db, weekly_character_id).await;
+        assert_eq!(2, weekly[0].remaining());
+        let monthly = tera_charges::get_tera_charges(&db, monthly_character_id).await;
+        assert_eq!(1, monthly[0].remaining());
+
+        Ok(())
+    }
+}
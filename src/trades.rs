@@ -0,0 +1,214 @@
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::cache::CharacterCacheItem;
+use crate::commands::inventory::{
+    add_item_to_inventory_in_transaction, remove_item_from_inventory_in_transaction,
+};
+use crate::errors::ValidationError;
+use crate::game_data::item::Item;
+use crate::game_data::GameData;
+use crate::{emoji, Error};
+
+/// Everything one character puts up in a trade.
+pub struct TradeSide<'a> {
+    pub character: &'a CharacterCacheItem,
+    pub money: i64,
+    pub battle_points: i64,
+    pub item: Option<(&'a Item, i64)>,
+}
+
+impl TradeSide<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.money == 0 && self.battle_points == 0 && self.item.is_none()
+    }
+
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.money > 0 {
+            parts.push(format!("{} {}", self.money, emoji::POKE_COIN));
+        }
+        if self.battle_points > 0 {
+            parts.push(format!("{} {}", self.battle_points, emoji::BATTLE_POINT));
+        }
+        if let Some((item, amount)) = self.item {
+            parts.push(format!("{}x {}", amount, item.name));
+        }
+
+        if parts.is_empty() {
+            String::from("*nothing*")
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// Swaps everything both sides put up. Either all of it happens, or nothing does.
+pub async fn execute(
+    database: &Pool<Sqlite>,
+    game_data: &GameData,
+    first: &TradeSide<'_>,
+    second: &TradeSide<'_>,
+) -> Result<(), Error> {
+    let mut transaction = database.begin().await?;
+    exchange_currencies_in_transaction(&mut transaction, first, second).await?;
+
+    // Remove everything before adding anything, so the traded items' slots are already free.
+    for side in [first, second] {
+        if let Some((item, amount)) = side.item {
+            remove_item_from_inventory_in_transaction(
+                &mut transaction,
+                side.character,
+                item,
+                amount,
+            )
+            .await?;
+        }
+    }
+    for (giver, receiver) in [(first, second), (second, first)] {
+        if let Some((item, amount)) = giver.item {
+            add_item_to_inventory_in_transaction(
+                &mut transaction,
+                game_data,
+                receiver.character,
+                item,
+                amount,
+            )
+            .await?;
+        }
+    }
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+async fn exchange_currencies_in_transaction(
+    connection: &mut SqliteConnection,
+    first: &TradeSide<'_>,
+    second: &TradeSide<'_>,
+) -> Result<(), Error> {
+    for side in [first, second] {
+        let result = sqlx::query!(
+            "UPDATE character SET money = money - ?, battle_points = battle_points - ? \
+             WHERE id = ? AND money >= ? AND battle_points >= ?",
+            side.money,
+            side.battle_points,
+            side.character.id,
+            side.money,
+            side.battle_points
+        )
+        .execute(&mut *connection)
+        .await?;
+        if result.rows_affected() != 1 {
+            return Err(Box::new(ValidationError::new(format!(
+                "**{} can't afford to trade {}.**",
+                side.character.name,
+                side.describe()
+            ))));
+        }
+    }
+
+    for (giver, receiver) in [(first, second), (second, first)] {
+        sqlx::query!(
+            "UPDATE character SET money = money + ?, battle_points = battle_points + ? WHERE id = ?",
+            giver.money,
+            giver.battle_points,
+            receiver.character.id
+        )
+        .execute(&mut *connection)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Pool, Sqlite};
+
+    use crate::cache::CharacterCacheItem;
+    use crate::trades::{exchange_currencies_in_transaction, TradeSide};
+    use crate::{database_helpers, Error};
+
+    async fn get_money_and_battle_points(db: &Pool<Sqlite>, character_id: i64) -> (i64, i64) {
+        let record = sqlx::query!(
+            "SELECT money, battle_points FROM character WHERE id = ?",
+            character_id
+        )
+        .fetch_one(db)
+        .await
+        .unwrap();
+        (record.money, record.battle_points)
+    }
+
+    #[sqlx::test]
+    async fn trades_should_not_be_applied_partially(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        database_helpers::create_mock::character_in_database(
+            &db, guild_id, user_id, 1, "Alice", 500,
+        )
+        .await;
+        database_helpers::create_mock::character_in_database(&db, guild_id, user_id, 2, "Bob", 0)
+            .await;
+        sqlx::query!("UPDATE character SET battle_points = 3 WHERE id = 2")
+            .execute(&db)
+            .await?;
+
+        let alice = CharacterCacheItem::new(
+            1,
+            String::from("Alice"),
+            user_id as u64,
+            guild_id as u64,
+            false,
+            String::new(),
+        );
+        let bob = CharacterCacheItem::new(
+            2,
+            String::from("Bob"),
+            user_id as u64,
+            guild_id as u64,
+            false,
+            String::new(),
+        );
+        let alice_pays = TradeSide {
+            character: &alice,
+            money: 300,
+            battle_points: 0,
+            item: None,
+        };
+        let bob_pays_too_much = TradeSide {
+            character: &bob,
+            money: 0,
+            battle_points: 5,
+            item: None,
+        };
+
+        let mut transaction = db.begin().await?;
+        assert!(exchange_currencies_in_transaction(
+            &mut transaction,
+            &alice_pays,
+            &bob_pays_too_much
+        )
+        .await
+        .is_err());
+        drop(transaction);
+        assert_eq!((500, 0), get_money_and_battle_points(&db, 1).await);
+        assert_eq!((0, 3), get_money_and_battle_points(&db, 2).await);
+
+        let bob_pays = TradeSide {
+            character: &bob,
+            money: 0,
+            battle_points: 2,
+            item: None,
+        };
+        let mut transaction = db.begin().await?;
+        exchange_currencies_in_transaction(&mut transaction, &alice_pays, &bob_pays).await?;
+        transaction.commit().await?;
+        assert_eq!((200, 2), get_money_and_battle_points(&db, 1).await);
+        assert_eq!((300, 1), get_money_and_battle_points(&db, 2).await);
+
+        Ok(())
+    }
+}
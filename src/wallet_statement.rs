@@ -0,0 +1,206 @@
+use chrono::Utc;
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::{emoji, Error};
+
+/// A single change to a wallet, as it should appear on its statement.
+pub struct WalletTransaction<'a> {
+    pub wallet_id: i64,
+    pub amount: i64,
+    pub counterpart_character_id: Option<i64>,
+    pub memo: Option<&'a str>,
+}
+
+pub struct StatementEntry {
+    pub amount: i64,
+    pub counterpart: Option<String>,
+    pub memo: Option<String>,
+    pub timestamp: i64,
+}
+
+pub async fn record(
+    connection: &mut SqliteConnection,
+    transaction: WalletTransaction<'_>,
+) -> Result<(), Error> {
+    let timestamp = Utc::now().timestamp();
+    sqlx::query!(
+        "INSERT INTO wallet_transaction (guild_id, wallet_id, amount, counterpart_character_id, memo, timestamp) \
+         VALUES ((SELECT guild_id FROM wallet WHERE id = ?), ?, ?, ?, ?, ?)",
+        transaction.wallet_id,
+        transaction.wallet_id,
+        transaction.amount,
+        transaction.counterpart_character_id,
+        transaction.memo,
+        timestamp
+    )
+    .execute(&mut *connection)
+    .await?;
+
+    Ok(())
+}
+
+/// Guild treasury transactions are stored without a wallet.
+pub async fn record_for_guild_treasury(
+    connection: &mut SqliteConnection,
+    guild_id: i64,
+    amount: i64,
+    counterpart_character_id: Option<i64>,
+    memo: &str,
+) -> Result<(), Error> {
+    let timestamp = Utc::now().timestamp();
+    sqlx::query!(
+        "INSERT INTO wallet_transaction (guild_id, wallet_id, amount, counterpart_character_id, memo, timestamp) \
+         VALUES (?, NULL, ?, ?, ?, ?)",
+        guild_id,
+        amount,
+        counterpart_character_id,
+        memo,
+        timestamp
+    )
+    .execute(&mut *connection)
+    .await?;
+
+    Ok(())
+}
+
+/// Loads all transactions of a wallet within the given time range, oldest first.
+/// Without a `wallet_id`, the transactions of the guild treasury are loaded instead.
+/// `until` is exclusive.
+pub async fn load_entries(
+    database: &Pool<Sqlite>,
+    guild_id: i64,
+    wallet_id: Option<i64>,
+    from: Option<i64>,
+    until: Option<i64>,
+) -> Result<Vec<StatementEntry>, Error> {
+    let entries = sqlx::query_as!(
+        StatementEntry,
+        "SELECT wallet_transaction.amount, wallet_transaction.memo, wallet_transaction.timestamp, \
+            character.name as counterpart \
+         FROM wallet_transaction \
+            LEFT JOIN character ON character.id = wallet_transaction.counterpart_character_id \
+         WHERE wallet_transaction.guild_id = ? AND wallet_transaction.wallet_id IS ? \
+            AND (? IS NULL OR wallet_transaction.timestamp >= ?) \
+            AND (? IS NULL OR wallet_transaction.timestamp < ?) \
+         ORDER BY wallet_transaction.timestamp, wallet_transaction.id",
+        guild_id,
+        wallet_id,
+        from,
+        from,
+        until,
+        until
+    )
+    .fetch_all(database)
+    .await?;
+
+    Ok(entries)
+}
+
+pub fn calculate_totals(entries: &[StatementEntry]) -> (i64, i64) {
+    let total_in = entries.iter().map(|x| x.amount.max(0)).sum();
+    let total_out = entries.iter().map(|x| (-x.amount).max(0)).sum();
+    (total_in, total_out)
+}
+
+pub fn build_statement_string(
+    wallet_name: &str,
+    entries: &[StatementEntry],
+    from: Option<i64>,
+    until: Option<i64>,
+) -> String {
+    let period = match (from, until) {
+        (Some(from), Some(until)) => format!("<t:{}:D> – <t:{}:D>", from, until - 1),
+        (Some(from), None) => format!("Since <t:{}:D>", from),
+        (None, Some(until)) => format!("Until <t:{}:D>", until - 1),
+        (None, None) => String::from("All time"),
+    };
+
+    let mut result = format!("## 📜 Statement for {}\n*{}*\n", wallet_name, period);
+    if entries.is_empty() {
+        result.push_str("No transactions during this period.\n");
+        return result;
+    }
+
+    for entry in entries {
+        let mut line = format!(
+            "<t:{}:d> {}{} {}",
+            entry.timestamp,
+            if entry.amount > 0 { "+" } else { "" },
+            entry.amount,
+            emoji::POKE_COIN
+        );
+        if let Some(counterpart) = &entry.counterpart {
+            let from_or_to = if entry.amount < 0 { "to" } else { "from" };
+            line.push_str(&format!(" {} {}", from_or_to, counterpart));
+        }
+        if let Some(memo) = &entry.memo {
+            line.push_str(&format!(" – *{}*", memo));
+        }
+        result.push_str(&line);
+        result.push('\n');
+    }
+
+    let (total_in, total_out) = calculate_totals(entries);
+    result.push_str(&format!(
+        "**In:** {} {} | **Out:** {} {} | **Net:** {} {}\n",
+        total_in,
+        emoji::POKE_COIN,
+        total_out,
+        emoji::POKE_COIN,
+        total_in - total_out,
+        emoji::POKE_COIN
+    ));
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Pool, Sqlite};
+
+    use crate::wallet_statement::{calculate_totals, load_entries, record, WalletTransaction};
+    use crate::{database_helpers, Error};
+
+    #[sqlx::test]
+    async fn statements_should_sum_up_money_in_and_out(db: Pool<Sqlite>) -> Result<(), Error> {
+        let guild_id = 100;
+        let user_id = 200;
+        let wallet_id = 300;
+        database_helpers::create_mock::guild(&db, guild_id).await;
+        database_helpers::create_mock::user(&db, user_id).await;
+        database_helpers::create_mock::character_in_database(
+            &db, guild_id, user_id, 1, "Customer", 0,
+        )
+        .await;
+        database_helpers::create_mock::wallet_with_money(&db, guild_id, wallet_id, "Shop", 0).await;
+
+        let mut connection = db.acquire().await?;
+        for (amount, memo) in [(100, Some("Rent")), (-30, None), (0, Some("Renamed"))] {
+            record(
+                &mut connection,
+                WalletTransaction {
+                    wallet_id,
+                    amount,
+                    counterpart_character_id: Some(1),
+                    memo,
+                },
+            )
+            .await?;
+        }
+
+        let entries = load_entries(&db, guild_id, Some(wallet_id), None, None).await?;
+        assert_eq!(3, entries.len());
+        assert_eq!(Some(String::from("Customer")), entries[0].counterpart);
+        assert_eq!(Some(String::from("Rent")), entries[0].memo);
+        assert_eq!((100, 30), calculate_totals(&entries));
+
+        let future = chrono::Utc::now().timestamp() + 60;
+        assert!(
+            load_entries(&db, guild_id, Some(wallet_id), Some(future), None)
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+}